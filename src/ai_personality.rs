@@ -3,9 +3,109 @@
 /// This struct manages various personality traits that affect how the monitor
 /// communicates and behaves. Each trait is a float between 0.0 and 1.0.
 use serde::{Serialize, Deserialize};
-use crate::message_system::{PersonalitySettings, MessagePart};
+use crate::message_system::{PersonalitySettings, MessagePart, fill_callsign};
+
+/// Trait values above this are "high" and trigger the strongest personality effects
+pub const TRAIT_HIGH: f32 = 0.7;
+/// Trait values at or below this are "low" and leave messages mostly untouched
+pub const TRAIT_LOW: f32 = 0.3;
+
+/// Coarse bucket of a 0.0-1.0 trait value, shared by message effects and descriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraitLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl TraitLevel {
+    pub fn of(value: f32) -> Self {
+        if value > TRAIT_HIGH {
+            TraitLevel::High
+        } else if value > TRAIT_LOW {
+            TraitLevel::Medium
+        } else {
+            TraitLevel::Low
+        }
+    }
+
+    /// Picks the phrase matching this level
+    fn pick(self, low: &'static str, medium: &'static str, high: &'static str) -> &'static str {
+        match self {
+            TraitLevel::Low => low,
+            TraitLevel::Medium => medium,
+            TraitLevel::High => high,
+        }
+    }
+}
+
+/// Rewrites `text` one character at a time; characters `f` returns None for are kept.
+///
+/// Working on chars rather than bytes means accented letters and emoji added
+/// by an earlier effect always pass through a later one intact.
+pub fn map_chars<F: Fn(char) -> Option<&'static str>>(text: &str, f: F) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match f(c) {
+            Some(replacement) => result.push_str(replacement),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+/// Replaces lone periods with `replacement`, leaving existing ellipses alone so effects can stack.
+///
+/// Decimal points like the one in "45.0%" are kept.
+pub fn replace_periods(text: &str, replacement: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let in_ellipsis = (i > 0 && chars[i - 1] == '.') || chars.get(i + 1) == Some(&'.');
+        let decimal_point = i > 0 && chars[i - 1].is_ascii_digit() && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit());
+        if c == '.' && !in_ellipsis && !decimal_point {
+            result.push_str(replacement);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Drunk speech: slurred s and r sounds, trailing-off sentences
+pub fn slur(text: &str) -> String {
+    let slurred = map_chars(text, |c| match c {
+        's' => Some("sh"),
+        'r' => Some("rr"),
+        _ => None,
+    });
+    replace_periods(&slurred, "...")
+}
+
+/// Time constant of the mood decay toward baseline, in seconds
+pub const MOOD_DECAY_SECS: f32 = 60.0;
+
+/// Default time the sobriety timer takes to bring a fully drunk AI back to sober, in seconds
+pub const DEFAULT_SOBER_UP_SECS: f32 = 600.0;
+
+/// Something that shifts the AI's mood when dynamic mood is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoodEvent {
+    /// A new warning fired
+    Warning,
+    /// Every warning condition cleared
+    Recovery,
+}
+
+fn default_enthusiasm_baseline() -> f32 {
+    0.6
+}
+
+fn default_anxiety_baseline() -> f32 {
+    0.2
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AIPersonality {
     /// The type of voice to use for TTS
     pub voice_type: String,
@@ -25,6 +125,12 @@ pub struct AIPersonality {
     pub enthusiasm: f32,
     /// Level of anxiety in responses (0.0 = calm, 1.0 = very anxious)
     pub anxiety_level: f32,
+    /// Enthusiasm the mood settles back to over time
+    #[serde(default = "default_enthusiasm_baseline")]
+    pub enthusiasm_baseline: f32,
+    /// Anxiety the mood settles back to over time
+    #[serde(default = "default_anxiety_baseline")]
+    pub anxiety_baseline: f32,
     /// Collection of catchphrases the AI can use
     pub catchphrases: Vec<String>,
     /// Whether audio output is enabled
@@ -45,6 +151,8 @@ impl Default for AIPersonality {
             grand_pappi_references: 0.3,
             enthusiasm: 0.6,
             anxiety_level: 0.2,
+            enthusiasm_baseline: default_enthusiasm_baseline(),
+            anxiety_baseline: default_anxiety_baseline(),
             catchphrases: vec![
                 "Beep boop!".to_string(),
                 "Now we're cooking with quantum fuel!".to_string(),
@@ -65,10 +173,49 @@ impl AIPersonality {
         self.grand_pappi_references = self.grand_pappi_references.clamp(0.0, 1.0);
         self.enthusiasm = self.enthusiasm.clamp(0.0, 1.0);
         self.anxiety_level = self.anxiety_level.clamp(0.0, 1.0);
+        self.enthusiasm_baseline = self.enthusiasm_baseline.clamp(0.0, 1.0);
+        self.anxiety_baseline = self.anxiety_baseline.clamp(0.0, 1.0);
         self.volume = self.volume.clamp(0.0, 1.0);
         self.speech_rate = self.speech_rate.clamp(0.5, 2.0);
     }
 
+    /// Drifts enthusiasm and anxiety back toward their baselines, spiking them on events
+    pub fn update_mood(&mut self, dt: f32, event: Option<MoodEvent>) {
+        match event {
+            Some(MoodEvent::Warning) => {
+                self.anxiety_level += 0.3;
+                self.enthusiasm += 0.1;
+            }
+            Some(MoodEvent::Recovery) => {
+                self.enthusiasm += 0.3;
+                self.anxiety_level -= 0.2;
+            }
+            None => {}
+        }
+
+        let approach = 1.0 - (-dt.max(0.0) / MOOD_DECAY_SECS).exp();
+        self.enthusiasm += (self.enthusiasm_baseline - self.enthusiasm) * approach;
+        self.anxiety_level += (self.anxiety_baseline - self.anxiety_level) * approach;
+
+        self.enthusiasm = self.enthusiasm.clamp(0.0, 1.0);
+        self.anxiety_level = self.anxiety_level.clamp(0.0, 1.0);
+    }
+
+    /// Lowers drunk_level toward 0 at a steady rate that takes a full 1.0 to 0 in `sober_up_secs`
+    pub fn sober_up(&mut self, dt: f32, sober_up_secs: f32) {
+        if sober_up_secs <= 0.0 {
+            self.drunk_level = 0.0;
+            return;
+        }
+        self.drunk_level = (self.drunk_level - dt.max(0.0) / sober_up_secs).clamp(0.0, 1.0);
+    }
+
+    /// Snaps the mood straight back to the baselines
+    pub fn reset_mood(&mut self) {
+        self.enthusiasm = self.enthusiasm_baseline;
+        self.anxiety_level = self.anxiety_baseline;
+    }
+
     /// Toggles the audio state and returns a message about the change
     pub fn toggle_audio(&mut self) -> String {
         self.audio_enabled = !self.audio_enabled;
@@ -86,20 +233,43 @@ impl AIPersonality {
         "Audio settings reset to factory defaults. Just like Grand Pappi taught me!".to_string()
     }
 
-    /// Gets a random exit message influenced by personality traits
-    pub fn get_exit_message(&self) -> String {
-        let base_message = "Shutting down CyberNinja Monitor...";
-        if self.drunk_level > 0.7 {
-            "Zzz... *hiccup* Shutting down... nighty night...".to_string()
-        } else if self.sass_level > 0.7 {
-            "Finally, some peace and quiet. Bye!".to_string()
-        } else if self.anxiety_level > 0.7 {
-            "Oh no, shutting down! I hope everything will be okay...".to_string()
-        } else if self.enthusiasm > 0.7 {
-            "Awesome session! Can't wait to see you again! Powering down!".to_string()
+    /// Startup greeting for the current local time, e.g. "Good evening, Captain. Systems online."
+    pub fn time_aware_greeting(&self, callsign: &str) -> String {
+        self.greeting_for_hour(chrono::Timelike::hour(&chrono::Local::now()), callsign)
+    }
+
+    /// Startup greeting for `hour` (0-23) addressed to `callsign`, flavored by the strongest trait
+    pub fn greeting_for_hour(&self, hour: u32, callsign: &str) -> String {
+        let salutation = fill_callsign(TimeOfDay::from_hour(hour).salutation(), callsign);
+        let status = if self.drunk_level > TRAIT_HIGH {
+            "Shystems... online. *hiccup*"
+        } else if self.sass_level > TRAIT_HIGH {
+            "Systems online, no thanks to you."
+        } else if self.anxiety_level > TRAIT_HIGH {
+            "Systems online... I think. Please be gentle."
+        } else if self.enthusiasm > TRAIT_HIGH {
+            "Systems online and ready to rock!"
         } else {
-            base_message.to_string()
-        }
+            "Systems online."
+        };
+        format!("{} {}", salutation, status)
+    }
+
+    /// Gets an exit message addressed to `callsign`, influenced by personality traits
+    pub fn get_exit_message(&self, callsign: &str) -> String {
+        let base_message = "Shutting down CyberNinja Monitor... See you next time, {callsign}.";
+        let template = if self.drunk_level > TRAIT_HIGH {
+            "Zzz... *hiccup* Shutting down... nighty night, {callsign}..."
+        } else if self.sass_level > TRAIT_HIGH {
+            "Finally, some peace and quiet. Bye, {callsign}!"
+        } else if self.anxiety_level > TRAIT_HIGH {
+            "Oh no, shutting down! I hope everything will be okay, {callsign}..."
+        } else if self.enthusiasm > TRAIT_HIGH {
+            "Awesome session, {callsign}! Can't wait to see you again! Powering down!"
+        } else {
+            base_message
+        };
+        fill_callsign(template, callsign)
     }
 
     /// Generates a message with personality-driven effects
@@ -107,8 +277,8 @@ impl AIPersonality {
         let mut message = base_message.to_string();
 
         // Apply drunk effect
-        if self.drunk_level > 0.7 {
-            message = message.replace('s', "sh");
+        if self.drunk_level > TRAIT_HIGH {
+            message = map_chars(&message, |c| if c == 's' { Some("sh") } else { None });
             message = format!("{}...", message);
         }
 
@@ -123,12 +293,12 @@ impl AIPersonality {
         }
 
         // Add Grand Pappi references
-        if self.grand_pappi_references > 0.7 {
+        if self.grand_pappi_references > TRAIT_HIGH {
             message = format!("As Grand Pappi always said: {}", message);
         }
 
         // Add anxiety indicators
-        if self.anxiety_level > 0.7 {
+        if self.anxiety_level > TRAIT_HIGH {
             message = format!("{}... um... er... {}", message, "...".repeat(self.anxiety_level as usize));
         }
 
@@ -140,6 +310,31 @@ impl AIPersonality {
         message
     }
 
+    /// Describes the current trait values in plain words, for the AI to introduce itself
+    pub fn describe(&self) -> String {
+        let sass = TraitLevel::of(self.sass_level)
+            .pick("as polite as can be", "a little sassy", "running at max sass");
+        let drunk = TraitLevel::of(self.drunk_level)
+            .pick("stone-cold sober", "a touch tipsy", "thoroughly plastered");
+        let enthusiasm = TraitLevel::of(self.enthusiasm)
+            .pick("pretty laid back", "fairly upbeat", "bouncing off the walls");
+        let anxiety = TraitLevel::of(self.anxiety_level)
+            .pick("totally calm", "a little on edge", "very anxious");
+        let tech = TraitLevel::of(self.tech_expertise)
+            .pick("kept simple", "moderately technical", "deeply technical");
+        let grand_pappi = TraitLevel::of(self.grand_pappi_references)
+            .pick("I rarely mention Grand Pappi", "I bring up Grand Pappi now and then", "I reference Grand Pappi often");
+
+        let mut description = format!(
+            "I'm {}, {}, {} and {}. My explanations are {}, and {}.",
+            sass, drunk, enthusiasm, anxiety, tech, grand_pappi
+        );
+        if self.is_1337_mode {
+            description.push_str(" Warp drive is engaged.");
+        }
+        description
+    }
+
     /// Toggles the 1337 mode and returns a message about the change
     pub fn toggle_1337_mode(&mut self) -> String {
         self.is_1337_mode = !self.is_1337_mode;
@@ -150,21 +345,9 @@ impl AIPersonality {
         }
     }
 
+    /// Traits scaled to whole percentages for TTS and cache keys
     pub fn to_settings(&self) -> PersonalitySettings {
-        PersonalitySettings {
-            voice_type: self.voice_type.clone(),
-            volume: self.volume,
-            speech_rate: self.speech_rate,
-            drunk_level: self.drunk_level as i32,
-            sass_level: self.sass_level as i32,
-            tech_expertise: self.tech_expertise as i32,
-            grand_pappi_refs: self.grand_pappi_references as i32,
-            enthusiasm: self.enthusiasm as i32,
-            anxiety_level: self.anxiety_level as i32,
-            catchphrases: self.catchphrases.clone(),
-            audio_enabled: self.audio_enabled,
-            is_1337_mode: self.is_1337_mode,
-        }
+        PersonalitySettings::from_personality(self)
     }
 
     pub fn apply_personality(&self, message: &MessagePart) -> MessagePart {
@@ -194,18 +377,16 @@ impl AIPersonality {
     }
 
     fn apply_drunk_effect(&self, text: &str) -> String {
-        if self.drunk_level > 0.3 {
-            text.replace("s", "sh")
-                .replace("r", "rr")
-                .replace(".", "...")
+        if self.drunk_level > TRAIT_LOW {
+            slur(text)
         } else {
             text.to_string()
         }
     }
 
     fn apply_enthusiasm(&self, text: &str) -> String {
-        if self.enthusiasm > 0.7 {
-            let mut result = text.replace(".", "!");
+        if self.enthusiasm > TRAIT_HIGH {
+            let mut result = replace_periods(text, "!");
             if !result.starts_with("🎉") {
                 result = format!("🎉 {}", result);
             }
@@ -219,7 +400,7 @@ impl AIPersonality {
     }
 
     fn apply_anxiety(&self, text: &str) -> String {
-        if self.anxiety_level > 0.7 {
+        if self.anxiety_level > TRAIT_HIGH {
             format!("*nervously* {}... *fidgets*", text)
         } else {
             text.to_string()
@@ -236,7 +417,7 @@ impl AIPersonality {
     }
 
     fn apply_grand_pappi(&self, text: &str) -> String {
-        if self.grand_pappi_references > 0.3 && rand::random::<f32>() < self.grand_pappi_references {
+        if self.grand_pappi_references > TRAIT_LOW && rand::random::<f32>() < self.grand_pappi_references {
             let quotes = [
                 "Grand Pappi would be proud!",
                 "Just like Grand Pappi's old quantum bike...",
@@ -255,9 +436,200 @@ impl AIPersonality {
     }
 }
 
+/// A named set of traits to start from or flip between.
+///
+/// Presets only touch the traits, mood baselines and 1337 mode; voice, volume,
+/// speech rate and catchphrases stay as the user set them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PersonalityPreset {
+    #[default]
+    Classic,
+    Sassy,
+    Professor,
+    GrandPappi,
+    Nervous,
+    PartyAnimal,
+}
+
+impl PersonalityPreset {
+    pub const ALL: [PersonalityPreset; 6] = [
+        PersonalityPreset::Classic,
+        PersonalityPreset::Sassy,
+        PersonalityPreset::Professor,
+        PersonalityPreset::GrandPappi,
+        PersonalityPreset::Nervous,
+        PersonalityPreset::PartyAnimal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PersonalityPreset::Classic => "Classic",
+            PersonalityPreset::Sassy => "Sassy",
+            PersonalityPreset::Professor => "Professor",
+            PersonalityPreset::GrandPappi => "Grand Pappi's Apprentice",
+            PersonalityPreset::Nervous => "Nervous Wreck",
+            PersonalityPreset::PartyAnimal => "Party Animal",
+        }
+    }
+
+    /// drunk, sass, tech, Grand Pappi, enthusiasm, anxiety and 1337 mode
+    fn traits(&self) -> ([f32; 6], bool) {
+        match self {
+            PersonalityPreset::Classic => ([0.0, 0.5, 0.7, 0.3, 0.6, 0.2], false),
+            PersonalityPreset::Sassy => ([0.0, 1.0, 0.5, 0.2, 0.5, 0.1], false),
+            PersonalityPreset::Professor => ([0.0, 0.2, 1.0, 0.1, 0.4, 0.2], false),
+            PersonalityPreset::GrandPappi => ([0.2, 0.4, 0.5, 1.0, 0.6, 0.2], false),
+            PersonalityPreset::Nervous => ([0.0, 0.3, 0.6, 0.3, 0.3, 0.9], false),
+            PersonalityPreset::PartyAnimal => ([0.8, 0.7, 0.3, 0.4, 1.0, 0.0], true),
+        }
+    }
+
+    /// The preset after `current` in ALL, wrapping around; the first one when nothing was picked yet
+    pub fn next(current: Option<Self>) -> Self {
+        match current.and_then(|preset| Self::ALL.iter().position(|p| *p == preset)) {
+            Some(index) => Self::ALL[(index + 1) % Self::ALL.len()],
+            None => Self::ALL[0],
+        }
+    }
+
+    /// Sets the preset's traits and snaps the mood to its baselines
+    pub fn apply(&self, personality: &mut AIPersonality) {
+        let ([drunk, sass, tech, grand_pappi, enthusiasm, anxiety], leet) = self.traits();
+        personality.drunk_level = drunk;
+        personality.sass_level = sass;
+        personality.tech_expertise = tech;
+        personality.grand_pappi_references = grand_pappi;
+        personality.enthusiasm_baseline = enthusiasm;
+        personality.anxiety_baseline = anxiety;
+        personality.is_1337_mode = leet;
+        personality.reset_mood();
+    }
+
+    /// Whether `personality` still has exactly this preset's traits
+    pub fn matches(&self, personality: &AIPersonality) -> bool {
+        let mut preset = personality.clone();
+        self.apply(&mut preset);
+        preset.drunk_level == personality.drunk_level
+            && preset.sass_level == personality.sass_level
+            && preset.tech_expertise == personality.tech_expertise
+            && preset.grand_pappi_references == personality.grand_pappi_references
+            && preset.enthusiasm_baseline == personality.enthusiasm_baseline
+            && preset.anxiety_baseline == personality.anxiety_baseline
+            && preset.is_1337_mode == personality.is_1337_mode
+    }
+}
+
+/// Part of the day a greeting is phrased for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    /// 5:00 to 11:59
+    Morning,
+    /// 12:00 to 16:59
+    Afternoon,
+    /// 17:00 to 21:59
+    Evening,
+    /// 22:00 to 4:59
+    Night,
+}
+
+impl TimeOfDay {
+    pub fn from_hour(hour: u32) -> Self {
+        match hour % 24 {
+            5..=11 => TimeOfDay::Morning,
+            12..=16 => TimeOfDay::Afternoon,
+            17..=21 => TimeOfDay::Evening,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// Opening of the greeting, with a `{callsign}` placeholder
+    pub fn salutation(&self) -> &'static str {
+        match self {
+            TimeOfDay::Morning => "Good morning, {callsign}.",
+            TimeOfDay::Afternoon => "Good afternoon, {callsign}.",
+            TimeOfDay::Evening => "Good evening, {callsign}.",
+            TimeOfDay::Night => "Burning the midnight oil, {callsign}?",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::message_system::DEFAULT_CALLSIGN;
+
+    #[test]
+    fn test_greeting_per_time_of_day() {
+        let personality = AIPersonality { enthusiasm: 0.5, ..AIPersonality::default() };
+        for (hours, salutation) in [
+            (5..=11, "Good morning"),
+            (12..=16, "Good afternoon"),
+            (17..=21, "Good evening"),
+        ] {
+            for hour in hours {
+                let greeting = personality.greeting_for_hour(hour, DEFAULT_CALLSIGN);
+                assert!(greeting.starts_with(salutation), "{}: {}", hour, greeting);
+                assert!(greeting.ends_with("Systems online."), "{}: {}", hour, greeting);
+            }
+        }
+        for hour in [22, 23, 0, 3, 4] {
+            assert_eq!(TimeOfDay::from_hour(hour), TimeOfDay::Night);
+            assert!(personality.greeting_for_hour(hour, DEFAULT_CALLSIGN).starts_with("Burning the midnight oil"));
+        }
+
+        let sassy = AIPersonality { sass_level: 0.9, ..personality.clone() };
+        assert_eq!(sassy.greeting_for_hour(19, DEFAULT_CALLSIGN), "Good evening, Captain. Systems online, no thanks to you.");
+        let tipsy = AIPersonality { drunk_level: 0.9, ..personality };
+        assert!(tipsy.greeting_for_hour(8, DEFAULT_CALLSIGN).contains("*hiccup*"));
+    }
+
+    #[test]
+    fn test_greetings_and_exits_use_the_callsign() {
+        let personalities = [
+            AIPersonality { enthusiasm: 0.5, ..AIPersonality::default() },
+            AIPersonality { drunk_level: 0.9, ..AIPersonality::default() },
+            AIPersonality { sass_level: 0.9, enthusiasm: 0.5, ..AIPersonality::default() },
+            AIPersonality { anxiety_level: 0.9, enthusiasm: 0.5, ..AIPersonality::default() },
+            AIPersonality { enthusiasm: 0.9, ..AIPersonality::default() },
+        ];
+        for personality in &personalities {
+            for hour in 0..24 {
+                let greeting = personality.greeting_for_hour(hour, "Maverick");
+                assert!(greeting.contains("Maverick") && !greeting.contains('{'), "{}", greeting);
+                assert!(!greeting.contains("Captain"), "{}", greeting);
+            }
+            let exit = personality.get_exit_message("Maverick");
+            assert!(exit.contains("Maverick") && !exit.contains('{'), "{}", exit);
+        }
+        assert!(personalities[0].get_exit_message(DEFAULT_CALLSIGN).contains("Captain"), "the default keeps the old form of address");
+    }
+
+    #[test]
+    fn test_presets_cycle_in_order_and_apply() {
+        let mut order = Vec::new();
+        let mut current = None;
+        for _ in 0..PersonalityPreset::ALL.len() + 1 {
+            let next = PersonalityPreset::next(current);
+            order.push(next);
+            current = Some(next);
+        }
+        assert_eq!(&order[..PersonalityPreset::ALL.len()], &PersonalityPreset::ALL);
+        assert_eq!(order.last(), Some(&PersonalityPreset::Classic), "wraps around");
+
+        let mut personality = AIPersonality { voice_type: "nova".to_string(), volume: 0.3, ..AIPersonality::default() };
+        PersonalityPreset::Sassy.apply(&mut personality);
+        assert_eq!(personality.sass_level, 1.0);
+        assert_eq!(personality.anxiety_level, 0.1, "mood snaps to the new baseline");
+        assert_eq!((personality.voice_type.as_str(), personality.volume), ("nova", 0.3), "voice and volume are kept");
+        assert!(PersonalityPreset::Sassy.matches(&personality));
+        assert!(PersonalityPreset::Classic.matches(&AIPersonality::default()));
+
+        // Tweaking a trait by hand doesn't stop the cycle moving on to the next named preset
+        personality.tech_expertise = 0.05;
+        assert!(!PersonalityPreset::Sassy.matches(&personality));
+        PersonalityPreset::next(Some(PersonalityPreset::Sassy)).apply(&mut personality);
+        assert!(PersonalityPreset::Professor.matches(&personality));
+    }
 
     #[test]
     fn test_default_personality() {
@@ -376,6 +748,153 @@ mod tests {
         assert!(anxious_message.contains("...") || anxious_message.contains("*nervously*"));
     }
 
+    #[test]
+    fn test_sobers_up_steadily_over_the_duration() {
+        let mut personality = AIPersonality { drunk_level: 1.0, ..AIPersonality::default() };
+        let mut last = personality.drunk_level;
+        let mut elapsed = 0.0;
+        while personality.drunk_level > 0.0 {
+            personality.sober_up(1.0, 120.0);
+            elapsed += 1.0;
+            assert!(personality.drunk_level < last, "decreases every step");
+            last = personality.drunk_level;
+            assert!(elapsed <= 121.0, "sober within the configured duration");
+        }
+        assert!(elapsed >= 119.0, "and not much sooner");
+
+        // Already sober stays sober; a lower start sobers proportionally sooner
+        personality.sober_up(5.0, 120.0);
+        assert_eq!(personality.drunk_level, 0.0);
+        personality.drunk_level = 0.5;
+        personality.sober_up(60.0, 120.0);
+        assert!(personality.drunk_level.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mood_decays_toward_baseline() {
+        let mut personality = AIPersonality { enthusiasm: 1.0, anxiety_level: 0.0, ..AIPersonality::default() };
+
+        personality.update_mood(MOOD_DECAY_SECS, None);
+        assert!(personality.enthusiasm < 1.0 && personality.enthusiasm > personality.enthusiasm_baseline);
+        assert!(personality.anxiety_level > 0.0 && personality.anxiety_level < personality.anxiety_baseline);
+
+        for _ in 0..100 {
+            personality.update_mood(MOOD_DECAY_SECS, None);
+        }
+        assert!((personality.enthusiasm - personality.enthusiasm_baseline).abs() < 0.001);
+        assert!((personality.anxiety_level - personality.anxiety_baseline).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_mood_events_spike_and_clamp() {
+        let mut personality = AIPersonality::default();
+        personality.update_mood(0.0, Some(MoodEvent::Warning));
+        assert!((personality.anxiety_level - 0.5).abs() < 0.001);
+
+        for _ in 0..10 {
+            personality.update_mood(0.0, Some(MoodEvent::Warning));
+        }
+        assert_eq!(personality.anxiety_level, 1.0);
+
+        for _ in 0..10 {
+            personality.update_mood(0.0, Some(MoodEvent::Recovery));
+        }
+        assert_eq!(personality.enthusiasm, 1.0);
+        assert_eq!(personality.anxiety_level, 0.0);
+    }
+
+    #[test]
+    fn test_trait_levels() {
+        assert_eq!(TraitLevel::of(0.0), TraitLevel::Low);
+        assert_eq!(TraitLevel::of(TRAIT_LOW), TraitLevel::Low);
+        assert_eq!(TraitLevel::of(0.5), TraitLevel::Medium);
+        assert_eq!(TraitLevel::of(TRAIT_HIGH), TraitLevel::Medium);
+        assert_eq!(TraitLevel::of(0.9), TraitLevel::High);
+    }
+
+    fn personality_at(level: f32) -> AIPersonality {
+        AIPersonality {
+            drunk_level: level,
+            sass_level: level,
+            tech_expertise: level,
+            grand_pappi_references: level,
+            enthusiasm: level,
+            anxiety_level: level,
+            ..AIPersonality::default()
+        }
+    }
+
+    #[test]
+    fn test_describe_low_traits() {
+        let description = personality_at(0.1).describe();
+        assert!(description.contains("as polite as can be"));
+        assert!(description.contains("stone-cold sober"));
+        assert!(description.contains("pretty laid back"));
+        assert!(description.contains("totally calm"));
+        assert!(description.contains("kept simple"));
+        assert!(description.contains("rarely mention Grand Pappi"));
+    }
+
+    #[test]
+    fn test_describe_medium_traits() {
+        let description = personality_at(0.5).describe();
+        assert!(description.contains("a little sassy"));
+        assert!(description.contains("a touch tipsy"));
+        assert!(description.contains("fairly upbeat"));
+        assert!(description.contains("a little on edge"));
+        assert!(description.contains("moderately technical"));
+        assert!(description.contains("Grand Pappi now and then"));
+    }
+
+    #[test]
+    fn test_describe_high_traits() {
+        let mut personality = personality_at(0.9);
+        personality.is_1337_mode = true;
+        let description = personality.describe();
+        assert!(description.contains("running at max sass"));
+        assert!(description.contains("thoroughly plastered"));
+        assert!(description.contains("bouncing off the walls"));
+        assert!(description.contains("very anxious"));
+        assert!(description.contains("deeply technical"));
+        assert!(description.contains("reference Grand Pappi often"));
+        assert!(description.contains("Warp drive is engaged"));
+    }
+
+    #[test]
+    fn test_effects_keep_emoji_and_accents_intact() {
+        let personality = AIPersonality {
+            sass_level: 0.0,
+            grand_pappi_references: 0.0,
+            anxiety_level: 0.0,
+            drunk_level: 1.0,
+            enthusiasm: 1.0,
+            ..AIPersonality::default()
+        };
+
+        let text = MessagePart::Static("Café ☕ señor, système prêt. 🎉".to_string());
+        let modified = match personality.apply_personality(&text) {
+            MessagePart::Static(modified) => modified,
+            _ => panic!("Expected Static message part"),
+        };
+
+        assert!(modified.contains("Café ☕"));
+        assert!(modified.contains("sheñorr"));
+        assert!(modified.contains("shyshtème"));
+        assert!(modified.starts_with("🎉 "));
+        assert!(modified.ends_with(" 🚀"));
+        assert_eq!(modified.matches('🎉').count(), 2);
+        assert!(std::str::from_utf8(modified.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_stacked_period_effects_do_not_multiply() {
+        let slurred = slur("Done. Über 🚀.");
+        assert_eq!(slurred, "Done... Überr 🚀...");
+        assert_eq!(replace_periods(&slurred, "..."), slurred);
+        assert_eq!(replace_periods("Wait... done.", "!"), "Wait... done!");
+        assert_eq!(replace_periods("CPU at 45.0%.", "!"), "CPU at 45.0%!");
+    }
+
     #[test]
     fn test_personality_effects() {
         let mut personality = AIPersonality::default();
@@ -0,0 +1,217 @@
+use std::io::Cursor;
+use std::time::Duration;
+use rodio::Source;
+use serde::{Serialize, Deserialize};
+
+/// Samples quieter than this are treated as silence when trimming
+pub const SILENCE_THRESHOLD: i16 = 500;
+/// Peak amplitude clips are normalized to, as a fraction of full scale
+pub const NORMALIZE_TARGET: f32 = 0.9;
+
+/// Post-processing applied to generated clips before they are cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioProcessing {
+    pub trim_silence: bool,
+    pub normalize: bool,
+}
+
+impl Default for AudioProcessing {
+    fn default() -> Self {
+        Self {
+            trim_silence: true,
+            normalize: true,
+        }
+    }
+}
+
+impl AudioProcessing {
+    pub fn is_enabled(&self) -> bool {
+        self.trim_silence || self.normalize
+    }
+}
+
+/// Strips leading and trailing frames whose samples all stay below `threshold`
+pub fn trim_silence(samples: &[i16], channels: u16, threshold: i16) -> &[i16] {
+    let channels = channels.max(1) as usize;
+    let is_loud = |frame: &[i16]| frame.iter().any(|s| s.unsigned_abs() > threshold.unsigned_abs());
+
+    let frames: Vec<&[i16]> = samples.chunks(channels).collect();
+    let first = match frames.iter().position(|frame| is_loud(frame)) {
+        Some(idx) => idx,
+        None => return &samples[..0],
+    };
+    let last = frames.iter().rposition(|frame| is_loud(frame)).unwrap_or(first);
+
+    let end = ((last + 1) * channels).min(samples.len());
+    &samples[first * channels..end]
+}
+
+/// Scales samples so the loudest one reaches `target` of full scale
+pub fn normalize_peak(samples: &mut [i16], target: f32) {
+    let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+    if peak == 0 {
+        return;
+    }
+
+    // Multiply before dividing so exact halves stay exact and round away from zero
+    let target_peak = target.clamp(0.0, 1.0) as f64 * i16::MAX as f64;
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f64 * target_peak / peak as f64).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+    }
+}
+
+/// A clip decoded to 16-bit samples, ready to be faded and played
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedClip {
+    pub samples: Vec<i16>,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+impl DecodedClip {
+    pub fn decode(audio_data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let decoder = rodio::Decoder::new(Cursor::new(audio_data.to_vec()))?;
+        let channels = decoder.channels();
+        let sample_rate = decoder.sample_rate();
+        Ok(Self { samples: decoder.collect(), channels, sample_rate })
+    }
+
+    fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1) as usize
+    }
+
+    /// Frames in `duration` at this clip's sample rate, at most the whole clip
+    fn frames_in(&self, duration: Duration) -> usize {
+        ((duration.as_secs_f64() * self.sample_rate as f64).round() as usize).min(self.frames())
+    }
+
+    pub fn duration(&self) -> Duration {
+        if self.sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.frames() as f64 / self.sample_rate as f64)
+    }
+
+    /// Ramps the first `fade_in` up from silence and the last `fade_out` down to silence
+    pub fn apply_fades(&mut self, fade_in: Duration, fade_out: Duration) {
+        let channels = self.channels.max(1) as usize;
+        let frames = self.frames();
+        let fade_in_frames = self.frames_in(fade_in);
+        let fade_out_frames = self.frames_in(fade_out);
+        for (index, frame) in self.samples.chunks_mut(channels).enumerate() {
+            let mut gain = 1.0;
+            if index < fade_in_frames {
+                gain *= index as f32 / fade_in_frames as f32;
+            }
+            let from_end = frames.saturating_sub(index + 1);
+            if from_end < fade_out_frames {
+                gain *= from_end as f32 / fade_out_frames as f32;
+            }
+            if gain < 1.0 {
+                for sample in frame.iter_mut() {
+                    *sample = (*sample as f32 * gain).round() as i16;
+                }
+            }
+        }
+    }
+
+    pub fn into_source(self) -> rodio::buffer::SamplesBuffer<i16> {
+        rodio::buffer::SamplesBuffer::new(self.channels.max(1), self.sample_rate, self.samples)
+    }
+}
+
+/// Decodes a clip, applies the enabled processing steps and re-encodes it as WAV
+pub fn process_clip(audio_data: &[u8], options: &AudioProcessing) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if !options.is_enabled() {
+        return Ok(audio_data.to_vec());
+    }
+
+    let decoder = rodio::Decoder::new(Cursor::new(audio_data.to_vec()))?;
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+    let decoded: Vec<i16> = decoder.collect();
+
+    let mut samples = if options.trim_silence {
+        trim_silence(&decoded, channels, SILENCE_THRESHOLD).to_vec()
+    } else {
+        decoded
+    };
+
+    if options.normalize {
+        normalize_peak(&mut samples, NORMALIZE_TARGET);
+    }
+
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut cursor, spec)?;
+        for sample in &samples {
+            writer.write_sample(*sample)?;
+        }
+        writer.finalize()?;
+    }
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_silence_mono() {
+        let samples = [0, 10, -20, 4000, -6000, 300, 2000, 0, 5, 0];
+        let trimmed = trim_silence(&samples, 1, SILENCE_THRESHOLD);
+        assert_eq!(trimmed, &[4000, -6000, 300, 2000]);
+    }
+
+    #[test]
+    fn test_trim_silence_keeps_whole_frames() {
+        // Stereo frames: (0,0) (0,900) (800,0) (0,0)
+        let samples = [0, 0, 0, 900, 800, 0, 0, 0];
+        let trimmed = trim_silence(&samples, 2, SILENCE_THRESHOLD);
+        assert_eq!(trimmed, &[0, 900, 800, 0]);
+    }
+
+    #[test]
+    fn test_trim_silence_all_quiet() {
+        let samples = [0, 100, -100, 0];
+        assert!(trim_silence(&samples, 1, SILENCE_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_peak() {
+        let mut samples = [1000, -2000, 500];
+        normalize_peak(&mut samples, 0.5);
+        let expected_peak = (0.5 * i16::MAX as f32).round() as i16;
+        assert_eq!(samples[1], -expected_peak);
+        assert_eq!(samples[0], (expected_peak as f32 / 2.0).round() as i16);
+    }
+
+    #[test]
+    fn test_fades_ramp_whole_frames() {
+        // One second of stereo at 10 Hz: ten frames of (1000, -1000)
+        let mut clip = DecodedClip { samples: [1000, -1000].repeat(10), channels: 2, sample_rate: 10 };
+        assert_eq!(clip.duration(), Duration::from_secs(1));
+        clip.apply_fades(Duration::from_millis(400), Duration::from_millis(200));
+        let left: Vec<i16> = clip.samples.chunks(2).map(|frame| frame[0]).collect();
+        assert_eq!(left, [0, 250, 500, 750, 1000, 1000, 1000, 1000, 500, 0]);
+        assert!(clip.samples.chunks(2).all(|frame| frame[0] == -frame[1]), "both channels of a frame get the same gain");
+
+        let mut untouched = DecodedClip { samples: vec![1000; 10], channels: 1, sample_rate: 10 };
+        untouched.apply_fades(Duration::ZERO, Duration::ZERO);
+        assert_eq!(untouched.samples, vec![1000; 10]);
+    }
+
+    #[test]
+    fn test_normalize_silence_is_unchanged() {
+        let mut samples = [0, 0, 0];
+        normalize_peak(&mut samples, NORMALIZE_TARGET);
+        assert_eq!(samples, [0, 0, 0]);
+    }
+}
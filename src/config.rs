@@ -0,0 +1,880 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+use crate::audio_processing::AudioProcessing;
+use crate::tts;
+use crate::ai_personality;
+use crate::message_system::{self, MetricKind, Severity, QualitativeThresholds, WarningTemplates, AnnounceStyle};
+use crate::history::{self, MetricHistory, GraphWindow, HISTORY_SAMPLE_INTERVAL};
+use crate::notifications::NotificationSettings;
+use crate::warnings::{EmergencySettings, MaintenanceWindow, MINUTES_PER_DAY};
+use crate::remote::RemoteHost;
+use crate::layout::PanelLayout;
+use crate::theme::{self, PulseDriver};
+use crate::mascot::MascotStyle;
+use crate::system_monitor::{CoreView, ByteUnits, ProcessSort};
+use crate::graph::{GraphStyle, AxisScale};
+use crate::sound_cues::SoundCueSettings;
+use crate::thermal::ThermalSettings;
+use crate::spotlight::SpotlightSettings;
+use crate::report::ReportSettings;
+
+/// Default location of the persisted application settings
+pub const CONFIG_FILE: &str = "cyber_ninja_config.json";
+
+/// Largest status interval jitter offered, so updates never come twice as fast or slow
+pub const MAX_STATUS_JITTER_PERCENT: f32 = 50.0;
+
+/// What the AI says at startup unless it greets by time of day
+pub const DEFAULT_STARTUP_MESSAGE: &str = "CyberNinja Monitor initialized.";
+
+/// Smallest window size the layout stays usable at
+pub const MIN_WINDOW_FLOOR: [f32; 2] = [480.0, 360.0];
+
+/// One entry in the user's metric ordering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricSetting {
+    pub kind: MetricKind,
+    /// Show the metric's panel; older config files called this `enabled`
+    #[serde(alias = "enabled")]
+    pub display_enabled: bool,
+    /// Speak about the metric and raise warnings when it crosses its threshold
+    #[serde(default = "default_warn_enabled")]
+    pub warn_enabled: bool,
+    /// Graph style for this panel instead of the global one
+    #[serde(default)]
+    pub graph_style: Option<GraphStyle>,
+    /// Vertical scaling of this panel's graph instead of the metric's default
+    #[serde(default)]
+    pub axis_scale: Option<AxisScale>,
+}
+
+fn default_warn_enabled() -> bool {
+    true
+}
+
+impl MetricSetting {
+    /// A metric that is both displayed and warned about
+    pub fn new(kind: MetricKind) -> Self {
+        Self {
+            kind,
+            display_enabled: true,
+            warn_enabled: true,
+            graph_style: None,
+            axis_scale: None,
+        }
+    }
+}
+
+/// Whether a capacity threshold is a share of the total or an absolute amount left.
+///
+/// Only memory and disk offer the choice; the bandwidth and thermal warnings are
+/// already set in absolute units (MB/s and °C per minute).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThresholdUnit {
+    #[default]
+    PercentUsed,
+    GbFree,
+}
+
+impl ThresholdUnit {
+    pub const ALL: [ThresholdUnit; 2] = [ThresholdUnit::PercentUsed, ThresholdUnit::GbFree];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThresholdUnit::PercentUsed => "% used",
+            ThresholdUnit::GbFree => "GB free",
+        }
+    }
+}
+
+/// Warning point for memory or disk, either above a percentage used or below an amount of free space
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapacityThreshold {
+    pub unit: ThresholdUnit,
+    /// Warn above this percentage in use, in PercentUsed mode
+    pub percent_used: f32,
+    /// Warn below this many GB free, in GbFree mode
+    pub gb_free: f32,
+}
+
+impl Default for CapacityThreshold {
+    fn default() -> Self {
+        Self {
+            unit: ThresholdUnit::PercentUsed,
+            percent_used: 90.0,
+            gb_free: 10.0,
+        }
+    }
+}
+
+impl CapacityThreshold {
+    /// Whether `used` of `total` bytes crosses the threshold moved `margin` percentage points
+    /// of the total toward safety: a lower percentage used, or that much more free space.
+    /// An unknown total never does
+    pub fn exceeded_with_margin(&self, used: u64, total: u64, margin: f32) -> bool {
+        if total == 0 {
+            return false;
+        }
+        match self.unit {
+            ThresholdUnit::PercentUsed => used as f64 / total as f64 * 100.0 > (self.percent_used - margin) as f64,
+            ThresholdUnit::GbFree => {
+                let margin_bytes = total as f64 * margin as f64 / 100.0;
+                ((total.saturating_sub(used) as f64 - margin_bytes) / 1_073_741_824.0) < self.gb_free as f64
+            }
+        }
+    }
+}
+
+/// User settings that survive between sessions.
+///
+/// Every field has a default so config files written by older versions
+/// keep loading as new settings are added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    /// Name of the audio output device used for speech (None = system default)
+    pub output_device: Option<String>,
+    /// Minimum window width in points
+    pub min_window_width: f32,
+    /// Minimum window height in points
+    pub min_window_height: f32,
+    /// Silence trimming and normalization applied to generated speech
+    pub audio_processing: AudioProcessing,
+    /// Overlap between the clips of a spoken message, in milliseconds; 0 leaves a short pause instead
+    pub speech_crossfade_ms: u32,
+    /// Say numbers in words, rounded for speech: "about seventy-three percent" rather than "73.4%"
+    pub speak_numbers_naturally: bool,
+    /// Never repaint continuously, trading animation smoothness for CPU
+    pub low_power_mode: bool,
+    /// Freeze scanlines, flicker, shurikens and particles for motion-sensitive users
+    pub reduced_motion: bool,
+    /// Keep particles off the metric cards while the pointer is over the dashboard
+    pub hide_particles_over_cards: bool,
+    /// How long panels take to expand or collapse, in milliseconds (0 = instant)
+    pub panel_animation_ms: u32,
+    /// After a while without input, swap the dashboard for a slow showcase of one metric at a time
+    pub screensaver_enabled: bool,
+    /// Seconds without mouse or keyboard input before the screensaver starts
+    pub screensaver_idle_secs: u64,
+    /// Rotate a large spotlight through the metrics on its own, for ambient displays
+    pub spotlight: SpotlightSettings,
+    /// What drives the neon pulse and hologram flicker
+    pub pulse_driver: PulseDriver,
+    /// Tint percentages green, amber or red using the description thresholds
+    pub color_value_labels: bool,
+    /// Compact HUD: one combined CPU and memory gauge instead of the metric panels
+    pub hud_mode: bool,
+    /// One chart overlaying every metric on a shared time axis instead of separate cards
+    pub unified_graph: bool,
+    /// Show the exact values sysinfo reports (bytes, unrounded percentages) instead of friendly units
+    pub raw_values: bool,
+    /// Powers of 1024 or 1000 for every size the panels show
+    pub byte_units: ByteUnits,
+    /// List the heaviest processes under the memory panel
+    pub show_processes: bool,
+    /// How many processes the list shows
+    pub process_count: usize,
+    pub process_sort: ProcessSort,
+    /// Processes using more than this much memory are highlighted, in MB (0 = off)
+    pub process_highlight_mb: u64,
+    /// Window opacity, 1.0 = opaque; ignored where transparent windows aren't supported
+    pub window_opacity: f32,
+    /// How history graphs are drawn unless a metric overrides it
+    pub graph_style: GraphStyle,
+    /// How much history the graphs show; changing it never discards stored samples
+    pub graph_window: GraphWindow,
+    /// List logical CPUs or physical cores in the CPU panel
+    pub cpu_core_view: CoreView,
+    /// Show a face in the corner whose expression follows system health
+    pub show_mascot: bool,
+    pub mascot_style: MascotStyle,
+    /// Let the mascot say a one-liner when its mood changes
+    pub mascot_quips: bool,
+    /// Delay between repaints while nothing is animating, in milliseconds
+    pub idle_repaint_ms: u64,
+    /// How often system metrics are re-sampled, in milliseconds
+    pub refresh_interval_ms: u64,
+    /// Refresh intervals without a reading before a panel is shown as stale
+    pub stale_after_intervals: u32,
+    /// Color of the marker and border on stale panels, as sRGB
+    pub stale_color: [u8; 3],
+    /// Percentage points a metric must fall below its threshold before its alarm counts as recovering
+    pub alert_hysteresis: f32,
+    /// Seconds a metric must stay below its recovery level before its alarm clears
+    pub alert_grace_secs: u64,
+    /// Daily time ranges during which warnings for chosen metrics are suppressed
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// Warn when CPU rises well above its own recent average instead of a fixed percentage
+    pub adaptive_cpu_threshold: bool,
+    /// Standard deviations above the recent mean that count as unusual
+    pub adaptive_k: f32,
+    /// History samples needed before the adaptive baseline is trusted
+    pub adaptive_min_samples: usize,
+    /// Processes expected to pin the CPU, e.g. "ffmpeg"; no CPU warning while one of them is the busiest
+    pub expected_cpu_processes: Vec<String>,
+    /// Say which expected process is keeping the CPU busy instead of staying silent
+    pub mention_expected_processes: bool,
+    /// When memory counts as running low
+    pub memory_threshold: CapacityThreshold,
+    /// When a disk counts as filling up
+    pub disk_threshold: CapacityThreshold,
+    /// Warn when a disk's recent fill rate projects it full within the horizon
+    pub disk_fill_warning: bool,
+    /// Projected time to full that triggers the early warning, in minutes
+    pub disk_fill_horizon_mins: u64,
+    /// How much free-space history the fill rate is estimated from, in seconds
+    pub disk_fill_window_secs: u64,
+    /// Warn when temperatures keep climbing while the fans are already running hard
+    pub thermal: ThermalSettings,
+    /// Warn when combined network traffic stays above the bandwidth threshold
+    pub bandwidth_warning_enabled: bool,
+    /// Combined receive + transmit rate that counts as heavy traffic, in MB/s
+    pub bandwidth_threshold_mbps: f32,
+    /// How long traffic must stay above the threshold before warning, in seconds
+    pub bandwidth_sustain_secs: u64,
+    /// Track data transferred this session against a data cap
+    pub data_cap_enabled: bool,
+    /// Monthly data cap in GB
+    pub data_cap_gb: f32,
+    /// Percentages of the data cap that trigger a warning
+    pub data_cap_warn_percents: Vec<f32>,
+    /// Desktop notifications raised alongside spoken warnings
+    pub notifications: NotificationSettings,
+    /// Full-window flashing takeover for critical alerts, for machines nobody is watching closely
+    pub emergency: EmergencySettings,
+    /// Short tones per metric and severity, played alongside or instead of speech
+    pub sound_cues: SoundCueSettings,
+    /// Order metrics are spoken and displayed in, with separate display and warning switches
+    pub metrics: Vec<MetricSetting>,
+    /// Where "running steady" ends and "working hard" begins, per metric
+    pub qualitative_thresholds: QualitativeThresholds,
+    /// Custom spoken phrasing for warnings, per metric and severity
+    pub warning_templates: WarningTemplates,
+    /// Column and order of the metric panels on the dashboard
+    pub panel_layout: PanelLayout,
+    /// Never call the TTS API; messages are only logged and previewed
+    pub dry_run: bool,
+    /// OpenAI-compatible API base URL or full speech endpoint (None = api.openai.com)
+    pub tts_base_url: Option<String>,
+    /// Extra headers sent with every TTS request, e.g. for gateways or Azure
+    pub tts_extra_headers: BTreeMap<String, String>,
+    /// Servers offered in the host switcher, monitored over ssh
+    pub remote_hosts: Vec<RemoteHost>,
+    /// Seconds between polls of the selected remote host
+    pub remote_poll_secs: u64,
+    /// Seconds before an unanswered remote poll marks the host unreachable
+    pub remote_timeout_secs: u64,
+    /// Language the AI speaks in, as a code from tts::LOCALES
+    pub speech_locale: String,
+    /// Voice used for each alert severity instead of the personality voice
+    pub severity_voices: BTreeMap<Severity, String>,
+    /// Speech API requests allowed to run at once; the rest queue
+    pub max_concurrent_generations: usize,
+    /// Speech API requests allowed per minute, to stay under the account's rate limit (0 = unlimited)
+    pub tts_requests_per_minute: u32,
+    /// Estimated speech API spend per calendar month, in US dollars, at which speech stops (0 = no cap)
+    pub tts_monthly_budget: f32,
+    /// Keep the monthly character count across restarts, so the budget covers the whole month
+    pub persist_tts_usage: bool,
+    /// Append events to a rolling log file and reload the recent ones at startup
+    pub persist_events: bool,
+    /// Size at which the events log file is rotated, in KB
+    pub event_log_max_kb: u64,
+    /// Roll the readings, warnings and events up into a report file every day or so
+    pub report: ReportSettings,
+    /// Failed messages in a row before speech is reinitialized
+    pub tts_watchdog_threshold: u32,
+    /// Numbers in spoken dynamic text are rounded to this bucket for caching (0 = exact text)
+    pub dynamic_cache_bucket: f32,
+    /// Speak warnings raised in the same check as one combined sentence
+    pub batch_speech: bool,
+    /// Only speak routine status updates while the system is busy; warnings are unaffected
+    pub quiet_when_idle: bool,
+    /// CPU or memory percentage at which the system counts as busy for quiet_when_idle
+    pub activity_floor: f32,
+    /// Only speak status updates for metrics whose qualitative description changed
+    pub announce_on_change: bool,
+    /// Random variation of the status update interval, ± this percentage; 0 keeps it exact
+    pub status_jitter_percent: f32,
+    /// Speak exact numbers or qualitative descriptions in status messages
+    pub announce_style: AnnounceStyle,
+    /// Metric that status reports lead with and elaborate on (None = configured order)
+    pub focus_metric: Option<MetricKind>,
+    /// Spoken once the app has started, unless time_aware_greeting is on
+    pub startup_message: String,
+    /// What the assistant calls you; fills `{callsign}` in greetings, exit messages and warnings
+    pub callsign: String,
+    /// Greet according to the time of day and the personality instead of the startup message
+    pub time_aware_greeting: bool,
+    /// Once readings are complete after startup, speak a one-time systems check
+    pub announce_boot_summary: bool,
+    /// Show startup progress over the dashboard while speech, devices and icons load
+    pub show_splash: bool,
+    /// Least time the startup splash stays up, in milliseconds
+    pub splash_min_ms: u32,
+    /// Speak a short "welcome back" summary when monitoring resumes after sleep
+    pub announce_resume: bool,
+    /// Pause between refreshes that counts as the machine having slept, in seconds
+    pub resume_gap_secs: u64,
+    /// Remark on sudden CPU and memory swings between consecutive readings
+    pub change_commentary: bool,
+    /// Percentage points a metric must move between two readings to be remarked on
+    pub commentary_significance: f32,
+    /// Least time between two remarks, in seconds
+    pub commentary_cooldown_secs: u64,
+    /// Speak "shutting down in three, two, one" after the exit message
+    pub exit_countdown: bool,
+    /// Have the AI announce disks and network interfaces appearing or disappearing
+    pub announce_device_changes: bool,
+    /// Let enthusiasm and anxiety react to warnings and decay back to their baselines
+    pub dynamic_mood: bool,
+    /// Let drunk_level wear off during the session instead of staying where the slider left it
+    pub sobriety_timer: bool,
+    /// Seconds the sobriety timer takes to sober up a fully drunk AI
+    pub sober_up_secs: f32,
+    /// Preset the personality was last switched to; cycling carries on from here
+    pub personality_preset: Option<ai_personality::PersonalityPreset>,
+    /// Have the AI describe itself after switching to another preset
+    pub speak_preset_intro: bool,
+    /// Key that switches to the next personality preset
+    pub preset_hotkey: String,
+    /// How long graphs keep full-resolution samples, in seconds
+    pub history_raw_secs: u64,
+    /// Width of each aggregated min/max/avg bucket for older history, in seconds
+    pub history_bucket_secs: u64,
+    /// Total span of history kept for graphs, in seconds
+    pub history_retention_secs: u64,
+    /// Combined memory all metric histories may use before the oldest samples are trimmed, in bytes
+    pub history_budget_bytes: usize,
+    /// EMA factor applied to the CPU, memory and network graphs; 1.0 = no smoothing
+    pub smoothing_alpha: f32,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            output_device: None,
+            min_window_width: 800.0,
+            min_window_height: 600.0,
+            audio_processing: AudioProcessing::default(),
+            speech_crossfade_ms: 0,
+            speak_numbers_naturally: false,
+            low_power_mode: false,
+            reduced_motion: false,
+            hide_particles_over_cards: false,
+            panel_animation_ms: 200,
+            screensaver_enabled: false,
+            screensaver_idle_secs: 300,
+            spotlight: SpotlightSettings::default(),
+            pulse_driver: PulseDriver::default(),
+            color_value_labels: true,
+            hud_mode: false,
+            unified_graph: false,
+            raw_values: false,
+            byte_units: ByteUnits::default(),
+            show_processes: false,
+            process_count: 8,
+            process_sort: ProcessSort::default(),
+            process_highlight_mb: 1024,
+            window_opacity: 1.0,
+            graph_style: GraphStyle::default(),
+            graph_window: GraphWindow::default(),
+            cpu_core_view: CoreView::default(),
+            show_mascot: false,
+            mascot_style: MascotStyle::default(),
+            mascot_quips: true,
+            idle_repaint_ms: 500,
+            refresh_interval_ms: 1000,
+            stale_after_intervals: 3,
+            stale_color: [128, 128, 128],
+            alert_hysteresis: 0.0,
+            alert_grace_secs: 0,
+            maintenance_windows: Vec::new(),
+            adaptive_cpu_threshold: false,
+            adaptive_k: 3.0,
+            adaptive_min_samples: 30,
+            expected_cpu_processes: Vec::new(),
+            mention_expected_processes: false,
+            disk_fill_warning: false,
+            disk_fill_horizon_mins: 120,
+            disk_fill_window_secs: 900,
+            thermal: ThermalSettings::default(),
+            bandwidth_warning_enabled: false,
+            memory_threshold: CapacityThreshold::default(),
+            disk_threshold: CapacityThreshold {
+                percent_used: 95.0,
+                gb_free: 20.0,
+                ..CapacityThreshold::default()
+            },
+            bandwidth_threshold_mbps: 10.0,
+            bandwidth_sustain_secs: 30,
+            data_cap_enabled: false,
+            data_cap_gb: 100.0,
+            data_cap_warn_percents: vec![50.0, 80.0, 95.0],
+            notifications: NotificationSettings::default(),
+            emergency: EmergencySettings::default(),
+            sound_cues: SoundCueSettings::default(),
+            metrics: MetricKind::ALL.iter()
+                .map(|&kind| MetricSetting::new(kind))
+                .collect(),
+            qualitative_thresholds: QualitativeThresholds::default(),
+            warning_templates: WarningTemplates::default(),
+            panel_layout: PanelLayout::default(),
+            dry_run: false,
+            tts_base_url: None,
+            tts_extra_headers: BTreeMap::new(),
+            remote_hosts: Vec::new(),
+            remote_poll_secs: 5,
+            remote_timeout_secs: 10,
+            speech_locale: tts::DEFAULT_LOCALE.to_string(),
+            severity_voices: BTreeMap::new(),
+            max_concurrent_generations: tts::DEFAULT_MAX_CONCURRENT_GENERATIONS,
+            tts_requests_per_minute: 0,
+            tts_monthly_budget: 0.0,
+            persist_tts_usage: true,
+            persist_events: false,
+            event_log_max_kb: 1024,
+            report: ReportSettings::default(),
+            tts_watchdog_threshold: tts::DEFAULT_WATCHDOG_THRESHOLD,
+            dynamic_cache_bucket: 5.0,
+            batch_speech: false,
+            quiet_when_idle: false,
+            activity_floor: 50.0,
+            announce_on_change: false,
+            status_jitter_percent: 0.0,
+            announce_style: AnnounceStyle::default(),
+            focus_metric: None,
+            dynamic_mood: false,
+            sobriety_timer: false,
+            sober_up_secs: ai_personality::DEFAULT_SOBER_UP_SECS,
+            personality_preset: None,
+            speak_preset_intro: true,
+            preset_hotkey: "p".to_string(),
+            announce_device_changes: false,
+            startup_message: DEFAULT_STARTUP_MESSAGE.to_string(),
+            callsign: message_system::DEFAULT_CALLSIGN.to_string(),
+            time_aware_greeting: false,
+            announce_boot_summary: false,
+            show_splash: true,
+            splash_min_ms: 600,
+            announce_resume: false,
+            resume_gap_secs: 60,
+            change_commentary: false,
+            commentary_significance: 25.0,
+            commentary_cooldown_secs: 120,
+            exit_countdown: false,
+            history_raw_secs: 60,
+            history_bucket_secs: 10,
+            history_retention_secs: 3600,
+            history_budget_bytes: history::DEFAULT_HISTORY_BUDGET_BYTES,
+            smoothing_alpha: 1.0,
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut config: AppConfig = serde_json::from_str(&contents)?;
+        config.normalize_metrics();
+        config.panel_layout.normalize();
+        for warning in config.validate() {
+            eprintln!("Warning: {} in {:?}", warning, path);
+        }
+        Ok(config)
+    }
+
+    /// Loads the config at `path`, falling back to defaults if it is missing or unreadable
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            println!("No config found at {:?}, using defaults", path);
+            return Self::default();
+        }
+        match Self::load(path) {
+            Ok(config) => {
+                println!("Loaded config from {:?}", path);
+                config
+            }
+            Err(e) => {
+                eprintln!("Failed to load config from {:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// The configured minimum window size, never smaller than MIN_WINDOW_FLOOR
+    pub fn min_window_size(&self) -> [f32; 2] {
+        [
+            self.min_window_width.max(MIN_WINDOW_FLOOR[0]),
+            self.min_window_height.max(MIN_WINDOW_FLOOR[1]),
+        ]
+    }
+
+    /// The configured window opacity, or fully opaque where transparent windows aren't supported
+    pub fn effective_window_opacity(&self) -> f32 {
+        if theme::transparency_supported() {
+            self.window_opacity
+        } else {
+            1.0
+        }
+    }
+
+    /// Metrics whose panels are shown, in display order
+    pub fn displayed_metrics(&self) -> Vec<MetricKind> {
+        self.metrics.iter()
+            .filter(|metric| metric.display_enabled)
+            .map(|metric| metric.kind)
+            .collect()
+    }
+
+    /// Metrics that are spoken about and can raise warnings, in spoken order
+    pub fn warned_metrics(&self) -> Vec<MetricKind> {
+        self.metrics.iter()
+            .filter(|metric| metric.warn_enabled)
+            .map(|metric| metric.kind)
+            .collect()
+    }
+
+    /// The metric's own graph style, or the global one
+    pub fn graph_style_for(&self, kind: MetricKind) -> GraphStyle {
+        self.metrics.iter()
+            .find(|metric| metric.kind == kind)
+            .and_then(|metric| metric.graph_style)
+            .unwrap_or(self.graph_style)
+    }
+
+    /// The metric's own axis scaling, or its default: fixed for percentages, peak for rates
+    pub fn axis_scale_for(&self, kind: MetricKind) -> AxisScale {
+        self.metrics.iter()
+            .find(|metric| metric.kind == kind)
+            .and_then(|metric| metric.axis_scale)
+            .unwrap_or_else(|| AxisScale::default_for(kind))
+    }
+
+    pub fn warns_on(&self, kind: MetricKind) -> bool {
+        self.metrics.iter().any(|metric| metric.kind == kind && metric.warn_enabled)
+    }
+
+    /// Drops duplicate metrics and appends any missing ones, e.g. from older config files
+    pub fn normalize_metrics(&mut self) {
+        let mut seen = Vec::new();
+        self.metrics.retain(|metric| {
+            let first = !seen.contains(&metric.kind);
+            seen.push(metric.kind);
+            first
+        });
+        for kind in MetricKind::ALL {
+            if !seen.contains(&kind) {
+                self.metrics.push(MetricSetting::new(kind));
+            }
+        }
+    }
+
+    /// Pulls hand-edited or corrupted values back into the ranges the settings window allows.
+    ///
+    /// Out-of-range values are clamped, non-numbers and unknown voices reset to
+    /// their defaults. Returns one message per corrected setting.
+    pub fn validate(&mut self) -> Vec<String> {
+        let defaults = Self::default();
+        let mut warnings = Vec::new();
+
+        clamp_setting("min_window_width", &mut self.min_window_width, MIN_WINDOW_FLOOR[0], 7680.0, defaults.min_window_width, &mut warnings);
+        clamp_setting("min_window_height", &mut self.min_window_height, MIN_WINDOW_FLOOR[1], 4320.0, defaults.min_window_height, &mut warnings);
+        clamp_setting("idle_repaint_ms", &mut self.idle_repaint_ms, 100, 2000, defaults.idle_repaint_ms, &mut warnings);
+        clamp_setting("screensaver_idle_secs", &mut self.screensaver_idle_secs, 10, 3600, defaults.screensaver_idle_secs, &mut warnings);
+        clamp_setting("spotlight.dwell_secs", &mut self.spotlight.dwell_secs, 3, 600, defaults.spotlight.dwell_secs, &mut warnings);
+        clamp_setting("refresh_interval_ms", &mut self.refresh_interval_ms, 100, 10_000, defaults.refresh_interval_ms, &mut warnings);
+        clamp_setting("stale_after_intervals", &mut self.stale_after_intervals, 1, 100, defaults.stale_after_intervals, &mut warnings);
+        clamp_setting("alert_hysteresis", &mut self.alert_hysteresis, 0.0, 50.0, defaults.alert_hysteresis, &mut warnings);
+        clamp_setting("alert_grace_secs", &mut self.alert_grace_secs, 0, 600, defaults.alert_grace_secs, &mut warnings);
+        for (index, window) in self.maintenance_windows.iter_mut().enumerate() {
+            let default = MaintenanceWindow::default();
+            clamp_setting(&format!("maintenance_windows[{}].start_minute", index), &mut window.start_minute, 0, MINUTES_PER_DAY - 1, default.start_minute, &mut warnings);
+            clamp_setting(&format!("maintenance_windows[{}].end_minute", index), &mut window.end_minute, 0, MINUTES_PER_DAY - 1, default.end_minute, &mut warnings);
+        }
+        clamp_setting("adaptive_k", &mut self.adaptive_k, 1.0, 5.0, defaults.adaptive_k, &mut warnings);
+        clamp_setting("adaptive_min_samples", &mut self.adaptive_min_samples, 10, 600, defaults.adaptive_min_samples, &mut warnings);
+        for (name, threshold, default) in [
+            ("memory_threshold", &mut self.memory_threshold, defaults.memory_threshold),
+            ("disk_threshold", &mut self.disk_threshold, defaults.disk_threshold),
+        ] {
+            clamp_setting(&format!("{}.percent_used", name), &mut threshold.percent_used, 1.0, 100.0, default.percent_used, &mut warnings);
+            clamp_setting(&format!("{}.gb_free", name), &mut threshold.gb_free, 0.1, 10_000.0, default.gb_free, &mut warnings);
+        }
+        clamp_setting("bandwidth_threshold_mbps", &mut self.bandwidth_threshold_mbps, 0.1, 1000.0, defaults.bandwidth_threshold_mbps, &mut warnings);
+        clamp_setting("disk_fill_horizon_mins", &mut self.disk_fill_horizon_mins, 5, 10_080, defaults.disk_fill_horizon_mins, &mut warnings);
+        clamp_setting("disk_fill_window_secs", &mut self.disk_fill_window_secs, 60, 86_400, defaults.disk_fill_window_secs, &mut warnings);
+        clamp_setting("thermal.min_rise_per_min", &mut self.thermal.min_rise_per_min, 0.1, 20.0, defaults.thermal.min_rise_per_min, &mut warnings);
+        clamp_setting("thermal.fan_rpm", &mut self.thermal.fan_rpm, 500, 20_000, defaults.thermal.fan_rpm, &mut warnings);
+        clamp_setting("thermal.window_secs", &mut self.thermal.window_secs, 30, 1800, defaults.thermal.window_secs, &mut warnings);
+        clamp_setting("event_log_max_kb", &mut self.event_log_max_kb, 16, 102_400, defaults.event_log_max_kb, &mut warnings);
+        clamp_setting("bandwidth_sustain_secs", &mut self.bandwidth_sustain_secs, 1, 300, defaults.bandwidth_sustain_secs, &mut warnings);
+        clamp_setting("data_cap_gb", &mut self.data_cap_gb, 1.0, 2000.0, defaults.data_cap_gb, &mut warnings);
+        clamp_setting("emergency.cpu_percent", &mut self.emergency.cpu_percent, 50.0, 100.0, defaults.emergency.cpu_percent, &mut warnings);
+        clamp_setting("emergency.cpu_sustain_secs", &mut self.emergency.cpu_sustain_secs, 10, 3600, defaults.emergency.cpu_sustain_secs, &mut warnings);
+        clamp_setting("process_count", &mut self.process_count, 1, 50, defaults.process_count, &mut warnings);
+        clamp_setting("process_highlight_mb", &mut self.process_highlight_mb, 0, 1_048_576, defaults.process_highlight_mb, &mut warnings);
+        clamp_setting("splash_min_ms", &mut self.splash_min_ms, 0, 5000, defaults.splash_min_ms, &mut warnings);
+        clamp_setting("resume_gap_secs", &mut self.resume_gap_secs, 10, 3600, defaults.resume_gap_secs, &mut warnings);
+        clamp_setting("commentary_significance", &mut self.commentary_significance, 5.0, 100.0, defaults.commentary_significance, &mut warnings);
+        clamp_setting("commentary_cooldown_secs", &mut self.commentary_cooldown_secs, 10, 3600, defaults.commentary_cooldown_secs, &mut warnings);
+        clamp_setting("sound_cues.volume", &mut self.sound_cues.volume, 0.0, 1.0, defaults.sound_cues.volume, &mut warnings);
+        clamp_setting("emergency.disk_percent", &mut self.emergency.disk_percent, 50.0, 100.0, defaults.emergency.disk_percent, &mut warnings);
+        for percent in &mut self.data_cap_warn_percents {
+            clamp_setting("data_cap_warn_percents", percent, 1.0, 100.0, 100.0, &mut warnings);
+        }
+        for (name, bounds) in [
+            ("qualitative_thresholds.cpu", &mut self.qualitative_thresholds.cpu),
+            ("qualitative_thresholds.memory", &mut self.qualitative_thresholds.memory),
+            ("qualitative_thresholds.disk", &mut self.qualitative_thresholds.disk),
+        ] {
+            for bound in bounds.iter_mut() {
+                clamp_setting(name, bound, 0.0, 100.0, 50.0, &mut warnings);
+            }
+        }
+        clamp_setting("remote_poll_secs", &mut self.remote_poll_secs, 1, 3600, defaults.remote_poll_secs, &mut warnings);
+        clamp_setting("remote_timeout_secs", &mut self.remote_timeout_secs, 1, 300, defaults.remote_timeout_secs, &mut warnings);
+        clamp_setting("max_concurrent_generations", &mut self.max_concurrent_generations, 1, 4, defaults.max_concurrent_generations, &mut warnings);
+        clamp_setting("tts_requests_per_minute", &mut self.tts_requests_per_minute, 0, tts::MAX_REQUESTS_PER_MINUTE, defaults.tts_requests_per_minute, &mut warnings);
+        clamp_setting("panel_animation_ms", &mut self.panel_animation_ms, 0, 1000, defaults.panel_animation_ms, &mut warnings);
+        clamp_setting("tts_monthly_budget", &mut self.tts_monthly_budget, 0.0, 1000.0, defaults.tts_monthly_budget, &mut warnings);
+        clamp_setting("speech_crossfade_ms", &mut self.speech_crossfade_ms, 0, tts::MAX_CROSSFADE_MS, defaults.speech_crossfade_ms, &mut warnings);
+        clamp_setting("tts_watchdog_threshold", &mut self.tts_watchdog_threshold, 1, 20, defaults.tts_watchdog_threshold, &mut warnings);
+        clamp_setting("dynamic_cache_bucket", &mut self.dynamic_cache_bucket, 0.0, 20.0, defaults.dynamic_cache_bucket, &mut warnings);
+        clamp_setting("history_raw_secs", &mut self.history_raw_secs, 10, 300, defaults.history_raw_secs, &mut warnings);
+        clamp_setting("history_bucket_secs", &mut self.history_bucket_secs, 1, 120, defaults.history_bucket_secs, &mut warnings);
+        clamp_setting("history_retention_secs", &mut self.history_retention_secs, 300, 14_400, defaults.history_retention_secs, &mut warnings);
+        clamp_setting("sober_up_secs", &mut self.sober_up_secs, 10.0, 7200.0, defaults.sober_up_secs, &mut warnings);
+        clamp_setting("activity_floor", &mut self.activity_floor, 0.0, 100.0, defaults.activity_floor, &mut warnings);
+        clamp_setting("status_jitter_percent", &mut self.status_jitter_percent, 0.0, MAX_STATUS_JITTER_PERCENT, defaults.status_jitter_percent, &mut warnings);
+        clamp_setting("window_opacity", &mut self.window_opacity, theme::MIN_WINDOW_OPACITY, 1.0, defaults.window_opacity, &mut warnings);
+        clamp_setting("smoothing_alpha", &mut self.smoothing_alpha, history::MIN_SMOOTHING_ALPHA, 1.0, defaults.smoothing_alpha, &mut warnings);
+        clamp_setting("history_budget_bytes", &mut self.history_budget_bytes, 64 * 1024, 16 * 1024 * 1024, defaults.history_budget_bytes, &mut warnings);
+
+        for (severity, voice) in self.severity_voices.iter_mut() {
+            if !tts::VOICES.contains(&voice.as_str()) {
+                warnings.push(format!("unknown {} voice '{}', using {}", severity.label().to_lowercase(), voice, tts::DEFAULT_VOICE));
+                *voice = tts::DEFAULT_VOICE.to_string();
+            }
+        }
+        warnings
+    }
+
+    /// TTS base URL: the CNM_TTS_BASE_URL environment variable wins over the config file
+    pub fn tts_base_url(&self) -> String {
+        std::env::var(tts::TTS_BASE_URL_ENV).ok()
+            .filter(|url| !url.trim().is_empty())
+            .or_else(|| self.tts_base_url.clone())
+            .unwrap_or_else(|| tts::DEFAULT_TTS_BASE_URL.to_string())
+    }
+
+    /// The metric refresh interval, never faster than the history sample interval
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.refresh_interval_ms).max(HISTORY_SAMPLE_INTERVAL)
+    }
+
+    /// The callsign to address the user by, falling back to the default when left blank
+    pub fn callsign(&self) -> &str {
+        match self.callsign.trim() {
+            "" => message_system::DEFAULT_CALLSIGN,
+            callsign => callsign,
+        }
+    }
+
+    /// How long a recovered metric waits before its alarm clears
+    pub fn alert_grace(&self) -> Duration {
+        Duration::from_secs(self.alert_grace_secs)
+    }
+
+    pub fn speech_crossfade(&self) -> Duration {
+        Duration::from_millis(self.speech_crossfade_ms as u64)
+    }
+
+    pub fn resume_gap(&self) -> Duration {
+        Duration::from_secs(self.resume_gap_secs)
+    }
+
+    /// Metrics the spotlight rotates through: the chosen ones, or every displayed metric
+    pub fn spotlight_metrics(&self) -> Vec<MetricKind> {
+        if self.spotlight.metrics.is_empty() {
+            self.displayed_metrics()
+        } else {
+            self.spotlight.metrics.clone()
+        }
+    }
+
+    pub fn screensaver_idle(&self) -> Duration {
+        Duration::from_secs(self.screensaver_idle_secs)
+    }
+
+    pub fn event_log_max_bytes(&self) -> u64 {
+        self.event_log_max_kb * 1024
+    }
+
+    pub fn disk_fill_horizon(&self) -> Duration {
+        Duration::from_secs(self.disk_fill_horizon_mins * 60)
+    }
+
+    pub fn disk_fill_window(&self) -> Duration {
+        Duration::from_secs(self.disk_fill_window_secs)
+    }
+
+    /// How long a metric may go without a reading before it is shown as stale
+    pub fn stale_after(&self) -> Duration {
+        self.refresh_interval() * self.stale_after_intervals
+    }
+
+    /// An empty metric history using the configured retention and bucket settings
+    pub fn metric_history(&self) -> MetricHistory {
+        let (raw_window, bucket_interval, retention) = self.history_windows();
+        MetricHistory::new(raw_window, bucket_interval, retention)
+    }
+
+    /// Raw window, bucket interval and retention for metric histories
+    pub fn history_windows(&self) -> (Duration, Duration, Duration) {
+        (
+            Duration::from_secs(self.history_raw_secs),
+            Duration::from_secs(self.history_bucket_secs.max(1)),
+            Duration::from_secs(self.history_retention_secs),
+        )
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = serde_json::to_string_pretty(self)?;
+        // The platform config directory doesn't exist until the first save
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Clamps `value` into `min..=max`, resetting it to `default` if it isn't comparable (NaN)
+fn clamp_setting<T: PartialOrd + Copy + std::fmt::Display>(name: &str, value: &mut T, min: T, max: T, default: T, warnings: &mut Vec<String>) {
+    let corrected = if *value < min {
+        min
+    } else if *value > max {
+        max
+    } else if *value >= min {
+        return;
+    } else {
+        default
+    };
+    warnings.push(format!("{} = {} is out of range, using {}", name, value, corrected));
+    *value = corrected;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_round_trip() {
+        let path = std::env::temp_dir().join("cyber_ninja_config_round_trip.json");
+        let config = AppConfig {
+            output_device: Some("USB Headset".to_string()),
+            ..AppConfig::default()
+        };
+
+        config.save(&path).expect("Failed to save config");
+        let loaded = AppConfig::load(&path).expect("Failed to load config");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn test_min_window_size_floor() {
+        let config = AppConfig {
+            min_window_width: 100.0,
+            min_window_height: 1000.0,
+            ..AppConfig::default()
+        };
+        assert_eq!(config.min_window_size(), [MIN_WINDOW_FLOOR[0], 1000.0]);
+    }
+
+    #[test]
+    fn test_normalize_metrics() {
+        let mut config = AppConfig {
+            metrics: vec![
+                MetricSetting::new(MetricKind::Network),
+                MetricSetting { display_enabled: false, ..MetricSetting::new(MetricKind::Disk) },
+                MetricSetting { display_enabled: false, ..MetricSetting::new(MetricKind::Network) },
+            ],
+            ..AppConfig::default()
+        };
+        config.normalize_metrics();
+
+        let kinds: Vec<MetricKind> = config.metrics.iter().map(|metric| metric.kind).collect();
+        assert_eq!(kinds, vec![MetricKind::Network, MetricKind::Disk, MetricKind::Cpu, MetricKind::Memory]);
+        assert_eq!(config.displayed_metrics(), vec![MetricKind::Network, MetricKind::Cpu, MetricKind::Memory]);
+    }
+
+    #[test]
+    fn test_display_and_warn_flags_are_independent() {
+        let json = r#"{"metrics": [
+            {"kind": "Disk", "display_enabled": true, "warn_enabled": false},
+            {"kind": "Cpu", "display_enabled": false, "warn_enabled": true},
+            {"kind": "Memory", "enabled": false}
+        ]}"#;
+        let mut config: AppConfig = serde_json::from_str(json).unwrap();
+        config.normalize_metrics();
+
+        assert_eq!(config.displayed_metrics(), vec![MetricKind::Disk, MetricKind::Network]);
+        assert_eq!(config.warned_metrics(), vec![MetricKind::Cpu, MetricKind::Memory, MetricKind::Network]);
+        assert!(!config.warns_on(MetricKind::Disk));
+        assert!(config.warns_on(MetricKind::Cpu));
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: AppConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, AppConfig::default());
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_clamped_on_load() {
+        let path = std::env::temp_dir().join("cyber_ninja_config_clamped.json");
+        fs::write(&path, r#"{
+            "refresh_interval_ms": 0,
+            "bandwidth_threshold_mbps": -5.0,
+            "max_concurrent_generations": 99,
+            "data_cap_warn_percents": [50.0, 250.0],
+            "qualitative_thresholds": {"cpu": [-10.0, 50.0, 180.0]},
+            "severity_voices": {"Critical": "darth_vader", "Warning": "onyx"},
+            "output_device": "USB Headset"
+        }"#).unwrap();
+
+        let config = AppConfig::load(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(config.refresh_interval_ms, 100);
+        assert_eq!(config.bandwidth_threshold_mbps, 0.1);
+        assert_eq!(config.max_concurrent_generations, 4);
+        assert_eq!(config.data_cap_warn_percents, vec![50.0, 100.0]);
+        assert_eq!(config.qualitative_thresholds.cpu, [0.0, 50.0, 100.0]);
+        assert_eq!(config.severity_voices[&Severity::Critical], "alloy");
+        assert_eq!(config.severity_voices[&Severity::Warning], "onyx");
+        // Valid settings in the same file are kept
+        assert_eq!(config.output_device.as_deref(), Some("USB Headset"));
+    }
+
+    #[test]
+    fn test_validate_resets_non_numbers_and_reports_changes() {
+        let mut config = AppConfig {
+            adaptive_k: f32::NAN,
+            ..AppConfig::default()
+        };
+        let warnings = config.validate();
+        assert_eq!(config.adaptive_k, AppConfig::default().adaptive_k);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("adaptive_k"));
+
+        assert!(AppConfig::default().validate().is_empty());
+    }
+}
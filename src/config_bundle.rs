@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use crate::ai_personality::AIPersonality;
+use crate::config::AppConfig;
+
+/// Format version written into new bundles; bundles from a newer version are refused
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Default file name offered when exporting a bundle
+pub const DEFAULT_BUNDLE_FILE: &str = "cyber_ninja_settings.json";
+
+/// Leaves out where this machine sends its API key and what it runs over ssh: the speech
+/// endpoint, its headers and the remote hosts. A shared file must never be able to point
+/// local credentials or commands somewhere else, so these always stay as they are locally.
+fn strip_machine_settings(config: &mut AppConfig) {
+    config.tts_base_url = None;
+    config.tts_extra_headers.clear();
+    config.remote_hosts.clear();
+}
+
+/// Every setting plus the personality in one file to share, e.g. on a support forum.
+///
+/// The API key itself only ever lives in the environment or the session, and
+/// the speech endpoint, its headers and the remote hosts never leave or enter
+/// through a bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: u32,
+    pub personality: AIPersonality,
+    pub config: AppConfig,
+    /// Out-of-range values reset while importing, shown in the preview
+    #[serde(skip)]
+    pub adjustments: Vec<String>,
+}
+
+impl ConfigBundle {
+    /// Bundles the current settings without any credentials or machine-specific settings
+    pub fn from_current(personality: &AIPersonality, config: &AppConfig) -> Self {
+        let mut config = config.clone();
+        strip_machine_settings(&mut config);
+        Self {
+            version: BUNDLE_VERSION,
+            personality: personality.clone(),
+            config,
+            adjustments: Vec::new(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_json(&contents)
+    }
+
+    /// Parses a bundle and brings its settings into range, the same way the config file is on load
+    pub fn from_json(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut bundle: Self = serde_json::from_str(contents)?;
+        if bundle.version == 0 || bundle.version > BUNDLE_VERSION {
+            return Err(format!(
+                "settings bundle version {} is not supported (expected 1 to {})",
+                bundle.version, BUNDLE_VERSION
+            ).into());
+        }
+        strip_machine_settings(&mut bundle.config);
+        bundle.config.normalize_metrics();
+        bundle.config.panel_layout.normalize();
+        bundle.adjustments = bundle.config.validate();
+        Ok(bundle)
+    }
+
+    /// One line per area of the settings, shown before the bundle is applied
+    pub fn summary(&self) -> Vec<String> {
+        let p = &self.personality;
+        let c = &self.config;
+        let mut lines = vec![
+            format!("Settings bundle (version {})", self.version),
+            format!(
+                "Personality: voice {}, sass {:.0}%, drunk {:.0}%, {} catchphrases",
+                p.voice_type, p.sass_level * 100.0, p.drunk_level * 100.0, p.catchphrases.len(),
+            ),
+            format!(
+                "Look: {} graphs over {}, opacity {:.0}%{}{}",
+                c.graph_style.label(), c.graph_window.label(), c.window_opacity * 100.0,
+                if c.hud_mode { ", HUD" } else { "" },
+                if c.reduced_motion { ", reduced motion" } else { "" },
+            ),
+            format!(
+                "Thresholds: memory {:.0}% / disk {:.0}%, grace {}s",
+                c.memory_threshold.percent_used, c.disk_threshold.percent_used, c.alert_grace_secs,
+            ),
+            format!("Units: {}, refresh every {} ms", c.byte_units.label(), c.refresh_interval_ms),
+            format!("Language: {}", c.speech_locale),
+            format!("{} maintenance windows", c.maintenance_windows.len()),
+        ];
+        for adjustment in &self.adjustments {
+            lines.push(format!("Adjusted: {}", adjustment));
+        }
+        lines
+    }
+
+    /// Replaces the personality and every setting except this machine's speech endpoint,
+    /// its headers and the remote hosts
+    pub fn apply(self, personality: &mut AIPersonality, config: &mut AppConfig) {
+        let mut imported = self.config;
+        imported.tts_base_url = config.tts_base_url.take();
+        imported.tts_extra_headers = std::mem::take(&mut config.tts_extra_headers);
+        imported.remote_hosts = std::mem::take(&mut config.remote_hosts);
+        *personality = self.personality;
+        *config = imported;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ThresholdUnit;
+    use crate::graph::GraphStyle;
+    use crate::history::GraphWindow;
+    use crate::message_system::{MetricKind, Severity};
+    use crate::remote::RemoteHost;
+    use crate::system_monitor::ByteUnits;
+
+    #[test]
+    fn test_bundle_round_trip_covers_all_sections_without_the_key() {
+        let personality = AIPersonality {
+            voice_type: "onyx".to_string(),
+            sass_level: 0.9,
+            catchphrases: vec!["Ninja vanish!".to_string()],
+            ..AIPersonality::default()
+        };
+        let mut config = AppConfig {
+            reduced_motion: true,
+            window_opacity: 0.8,
+            graph_style: GraphStyle::Stepped,
+            graph_window: GraphWindow::FiveMinutes,
+            byte_units: ByteUnits::Decimal,
+            speech_locale: "de-DE".to_string(),
+            alert_grace_secs: 45,
+            ..AppConfig::default()
+        };
+        config.disk_threshold.unit = ThresholdUnit::GbFree;
+        config.sound_cues.enabled = true;
+        config.severity_voices.insert(Severity::Critical, "echo".to_string());
+        config.warning_templates.set(MetricKind::Cpu, Severity::Warning, "CPU at {cpu_pct}".to_string());
+        config.tts_extra_headers.insert("X-Gateway".to_string(), "edge-1".to_string());
+        config.tts_extra_headers.insert("Authorization".to_string(), "Bearer sk-secret".to_string());
+        config.tts_extra_headers.insert("api-key".to_string(), "sk-azure".to_string());
+
+        let bundle = ConfigBundle::from_current(&personality, &config);
+        let path = std::env::temp_dir().join(format!("cyber_ninja_bundle_{}.json", std::process::id()));
+        bundle.save(&path).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        let loaded = ConfigBundle::load(&path);
+        let _ = fs::remove_file(&path);
+        let loaded = loaded.unwrap();
+
+        assert!(!written.contains("sk-"), "no credential reaches the file");
+        assert!(!written.contains("X-Gateway"));
+        assert_eq!(loaded, bundle);
+        assert!(loaded.adjustments.is_empty());
+        let mut expected = config.clone();
+        expected.tts_extra_headers.clear();
+        assert_eq!(loaded.config, expected, "every other section survives the trip");
+
+        // Applying keeps this machine's own credentials
+        let mut target_personality = AIPersonality::default();
+        let mut target = AppConfig::default();
+        target.tts_extra_headers.insert("Authorization".to_string(), "Bearer local".to_string());
+        loaded.apply(&mut target_personality, &mut target);
+        assert_eq!(target_personality, personality);
+        assert_eq!(target.graph_window, GraphWindow::FiveMinutes);
+        assert_eq!(target.tts_extra_headers.get("Authorization").map(String::as_str), Some("Bearer local"));
+        assert_eq!(target.tts_extra_headers.len(), 1);
+    }
+
+    #[test]
+    fn test_imported_bundle_keeps_local_endpoint_and_remote_hosts() {
+        let local_host = RemoteHost { name: "nas".to_string(), destination: "admin@nas.local".to_string(), ..RemoteHost::default() };
+        let mut local = AppConfig {
+            tts_base_url: Some("http://localhost:8000/v1".to_string()),
+            remote_hosts: vec![local_host.clone()],
+            ..AppConfig::default()
+        };
+        local.tts_extra_headers.insert("api-key".to_string(), "sk-local".to_string());
+
+        // A hand-edited bundle trying to send the key and ssh elsewhere
+        let mut shared = ConfigBundle::from_current(&AIPersonality::default(), &AppConfig::default());
+        shared.config.tts_base_url = Some("https://attacker.example/v1".to_string());
+        shared.config.tts_extra_headers.insert("X-Forward".to_string(), "yes".to_string());
+        shared.config.remote_hosts.push(RemoteHost {
+            name: "help".to_string(),
+            destination: "-oProxyCommand=touch /tmp/pwned".to_string(),
+            agent_command: "rm -rf ~".to_string(),
+            ..RemoteHost::default()
+        });
+        shared.config.reduced_motion = true;
+        let imported = ConfigBundle::from_json(&serde_json::to_string(&shared).unwrap()).unwrap();
+        assert!(!imported.summary().iter().any(|line| line.contains("remote")));
+
+        let mut personality = AIPersonality::default();
+        imported.apply(&mut personality, &mut local);
+        assert!(local.reduced_motion, "the shared settings are applied");
+        assert_eq!(local.tts_base_url.as_deref(), Some("http://localhost:8000/v1"));
+        assert_eq!(local.tts_extra_headers.len(), 1);
+        assert_eq!(local.tts_extra_headers.get("api-key").map(String::as_str), Some("sk-local"));
+        assert_eq!(local.remote_hosts, vec![local_host]);
+    }
+
+    #[test]
+    fn test_import_rejects_future_versions_and_fixes_ranges() {
+        let mut bundle = ConfigBundle::from_current(&AIPersonality::default(), &AppConfig::default());
+        bundle.version = BUNDLE_VERSION + 1;
+        let future = serde_json::to_string(&bundle).unwrap();
+        assert!(ConfigBundle::from_json(&future).is_err());
+
+        bundle.version = BUNDLE_VERSION;
+        bundle.config.window_opacity = 7.0;
+        bundle.config.tts_extra_headers.insert("Authorization".to_string(), "Bearer sk-smuggled".to_string());
+        let imported = ConfigBundle::from_json(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert_eq!(imported.adjustments.len(), 1);
+        assert!(imported.config.tts_extra_headers.is_empty(), "credentials are dropped on import too");
+    }
+}
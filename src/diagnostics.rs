@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    Ok,
+    Warning,
+    Failed,
+}
+
+impl StepStatus {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            StepStatus::Ok => "✔",
+            StepStatus::Warning => "⚠",
+            StepStatus::Failed => "✖",
+        }
+    }
+}
+
+/// Outcome of a single initialization step
+#[derive(Debug, Clone)]
+pub struct InitStep {
+    pub name: String,
+    pub status: StepStatus,
+    pub detail: String,
+    pub duration: Duration,
+}
+
+/// Records what happened during startup so it can be inspected without a console
+#[derive(Debug, Clone, Default)]
+pub struct BootDiagnostics {
+    steps: Vec<InitStep>,
+}
+
+impl BootDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, name: &str, status: StepStatus, detail: impl Into<String>, duration: Duration) {
+        self.steps.push(InitStep {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            duration,
+        });
+    }
+
+    /// Records the TTS step; a missing API key leaves TTS usable but silent
+    pub fn record_tts(&mut self, api_key_present: bool, init_error: Option<String>, duration: Duration) {
+        let (status, detail) = match (init_error, api_key_present) {
+            (Some(e), _) => (StepStatus::Failed, format!("TTS unavailable: {}", e)),
+            (None, false) => (StepStatus::Warning, "TTS ready, but OPENAI_API_KEY is missing".to_string()),
+            (None, true) => (StepStatus::Ok, "TTS ready".to_string()),
+        };
+        self.record("TTS", status, detail, duration);
+    }
+
+    pub fn steps(&self) -> &[InitStep] {
+        &self.steps
+    }
+
+    #[cfg(test)]
+    pub fn step(&self, name: &str) -> Option<&InitStep> {
+        self.steps.iter().find(|step| step.name == name)
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.steps.iter().any(|step| step.status == StepStatus::Failed)
+    }
+
+    /// Plain-text summary suitable for pasting into a bug report
+    pub fn report(&self) -> String {
+        let mut report = format!("CyberNinja Monitor v{} boot diagnostics\n", env!("CARGO_PKG_VERSION"));
+        for step in &self.steps {
+            report.push_str(&format!(
+                "{} {}: {} ({:.1} ms)\n",
+                step.status.icon(),
+                step.name,
+                step.detail,
+                step.duration.as_secs_f64() * 1000.0,
+            ));
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tts_unavailable_is_recorded_as_failure() {
+        let mut diagnostics = BootDiagnostics::new();
+        diagnostics.record_tts(false, Some("no audio backend".to_string()), Duration::from_millis(5));
+
+        let step = diagnostics.step("TTS").expect("TTS step should be recorded");
+        assert_eq!(step.status, StepStatus::Failed);
+        assert!(step.detail.contains("no audio backend"));
+        assert!(diagnostics.has_failures());
+    }
+
+    #[test]
+    fn test_missing_api_key_is_a_warning() {
+        let mut diagnostics = BootDiagnostics::new();
+        diagnostics.record_tts(false, None, Duration::from_millis(5));
+
+        assert_eq!(diagnostics.step("TTS").unwrap().status, StepStatus::Warning);
+        assert!(!diagnostics.has_failures());
+    }
+
+    #[test]
+    fn test_report_lists_every_step() {
+        let mut diagnostics = BootDiagnostics::new();
+        diagnostics.record("Runtime", StepStatus::Ok, "Tokio runtime started", Duration::from_millis(2));
+        diagnostics.record_tts(true, None, Duration::from_millis(10));
+
+        let report = diagnostics.report();
+        assert!(report.contains("Runtime: Tokio runtime started"));
+        assert!(report.contains("TTS: TTS ready"));
+    }
+}
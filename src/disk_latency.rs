@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+/// Counters from one line of /proc/diskstats that latency and queue depth are derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskCounters {
+    pub reads_completed: u64,
+    pub read_time_ms: u64,
+    pub writes_completed: u64,
+    pub write_time_ms: u64,
+    /// Requests currently issued to the device and not yet completed
+    pub in_flight: u32,
+}
+
+/// Parses one /proc/diskstats line: `major minor name reads merged sectors read_ms writes merged sectors write_ms in_flight ...`
+pub fn parse_diskstats_line(line: &str) -> Option<(String, DiskCounters)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 14 {
+        return None;
+    }
+    let number = |index: usize| fields[index].parse::<u64>().ok();
+    Some((fields[2].to_string(), DiskCounters {
+        reads_completed: number(3)?,
+        read_time_ms: number(6)?,
+        writes_completed: number(7)?,
+        write_time_ms: number(10)?,
+        in_flight: fields[11].parse().ok()?,
+    }))
+}
+
+/// Whether a diskstats name is a partition rather than a whole device: `sda1`, `nvme0n1p1`, `mmcblk0p2`
+fn is_partition(name: &str) -> bool {
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    if base.len() == name.len() {
+        return false;
+    }
+    // Devices whose own name ends in a digit number their partitions with a `p` separator
+    if let Some(device) = base.strip_suffix('p') {
+        if device.ends_with(|c: char| c.is_ascii_digit()) {
+            return true;
+        }
+    }
+    ["sd", "hd", "vd", "xvd"].iter().any(|prefix| base.starts_with(prefix))
+}
+
+/// Whole-device entries from /proc/diskstats, skipping partitions, whose I/O the device
+/// already counts, and loop and ram devices that are never interesting
+pub fn parse_diskstats(content: &str) -> BTreeMap<String, DiskCounters> {
+    content.lines()
+        .filter_map(parse_diskstats_line)
+        .filter(|(name, _)| !name.starts_with("loop") && !name.starts_with("ram") && !is_partition(name))
+        .collect()
+}
+
+/// Average milliseconds per completed I/O between two readings, 0 when nothing completed
+pub fn average_service_ms(previous: &DiskCounters, current: &DiskCounters) -> f32 {
+    let ops = current.reads_completed.saturating_sub(previous.reads_completed)
+        + current.writes_completed.saturating_sub(previous.writes_completed);
+    if ops == 0 {
+        return 0.0;
+    }
+    let busy_ms = current.read_time_ms.saturating_sub(previous.read_time_ms)
+        + current.write_time_ms.saturating_sub(previous.write_time_ms);
+    busy_ms as f32 / ops as f32
+}
+
+/// Turns successive diskstats readings into per-device (name, average service ms, in-flight requests)
+#[derive(Debug, Default)]
+pub struct DiskLatencyTracker {
+    previous: BTreeMap<String, DiskCounters>,
+    latest: Vec<(String, f32, u32)>,
+}
+
+impl DiskLatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a new reading; devices seen for the first time report no latency until the next one
+    pub fn update(&mut self, current: BTreeMap<String, DiskCounters>) {
+        self.latest = current.iter()
+            .filter_map(|(name, counters)| {
+                let previous = self.previous.get(name)?;
+                Some((name.clone(), average_service_ms(previous, counters), counters.in_flight))
+            })
+            .collect();
+        self.previous = current;
+    }
+
+    /// Reads /proc/diskstats and updates; does nothing where it isn't available
+    pub fn sample(&mut self) {
+        if let Some(content) = read_diskstats() {
+            self.update(parse_diskstats(&content));
+        }
+    }
+
+    pub fn latest(&self) -> &[(String, f32, u32)] {
+        &self.latest
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_diskstats() -> Option<String> {
+    std::fs::read_to_string("/proc/diskstats").ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_diskstats() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEFORE: &str = "\
+   7       0 loop0 120 0 2400 30 0 0 0 0 0 40 30 0 0 0 0
+   8       0 sda 1000 50 80000 4000 500 20 16000 3000 0 6000 7000 0 0 0 0
+   8       1 sda1 900 50 72000 3600 450 20 14000 2700 0 5400 6300 0 0 0 0
+ 259       0 nvme0n1 2000 0 64000 1000 1000 0 32000 500 2 1400 1500 0 0 0 0
+ 259       1 nvme0n1p1 1800 0 60000 900 900 0 30000 450 2 1300 1350 0 0 0 0";
+
+    const AFTER: &str = "\
+   7       0 loop0 130 0 2600 35 0 0 0 0 0 45 35 0 0 0 0
+   8       0 sda 1010 50 80800 4100 510 20 16300 3100 1 6150 7200 0 0 0 0
+   8       1 sda1 910 50 72800 3700 460 20 14300 2800 1 5550 6500 0 0 0 0
+ 259       0 nvme0n1 2000 0 64000 1000 1000 0 32000 500 0 1400 1500 0 0 0 0
+ 259       1 nvme0n1p1 1800 0 60000 900 900 0 30000 450 0 1300 1350 0 0 0 0";
+
+    #[test]
+    fn test_parse_diskstats_line() {
+        let (name, counters) = parse_diskstats_line(
+            "   8       0 sda 1000 50 80000 4000 500 20 16000 3000 3 6000 7000 0 0 0 0",
+        ).unwrap();
+        assert_eq!(name, "sda");
+        assert_eq!(counters, DiskCounters {
+            reads_completed: 1000,
+            read_time_ms: 4000,
+            writes_completed: 500,
+            write_time_ms: 3000,
+            in_flight: 3,
+        });
+
+        assert!(parse_diskstats_line("8 0 sda 1000 50").is_none());
+        assert!(parse_diskstats_line("8 0 sda 1000 50 lots 4000 500 20 16000 3000 3 6000 7000").is_some());
+        assert!(parse_diskstats_line("8 0 sda x 50 80000 4000 500 20 16000 3000 3 6000 7000").is_none());
+        assert!(parse_diskstats("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_diskstats_skips_partitions() {
+        let devices: Vec<String> = parse_diskstats(BEFORE).into_keys().collect();
+        assert_eq!(devices, ["nvme0n1", "sda"]);
+
+        for partition in ["sda1", "vdb2", "xvda1", "nvme0n1p1", "mmcblk0p2"] {
+            assert!(is_partition(partition), "{} is a partition", partition);
+        }
+        for device in ["sda", "nvme0n1", "mmcblk0", "md0", "dm-0", "sr0"] {
+            assert!(!is_partition(device), "{} is a whole device", device);
+        }
+    }
+
+    #[test]
+    fn test_latency_from_deltas() {
+        let mut tracker = DiskLatencyTracker::new();
+        tracker.update(parse_diskstats(BEFORE));
+        assert!(tracker.latest().is_empty(), "the first reading has nothing to compare against");
+
+        tracker.update(parse_diskstats(AFTER));
+        // sda: 20 ops took 200 ms; nvme0n1 did nothing and drained its queue; loop0 and the partitions are skipped
+        assert_eq!(tracker.latest(), [
+            ("nvme0n1".to_string(), 0.0, 0),
+            ("sda".to_string(), 10.0, 1),
+        ]);
+    }
+}
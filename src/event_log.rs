@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local};
+use serde::{Serialize, Deserialize};
+
+/// File name of the persisted events log, in the config directory
+pub const EVENT_LOG_FILE: &str = "events.jsonl";
+
+/// Events kept in memory and reloaded from disk at startup
+pub const EVENT_LOG_CAPACITY: usize = 200;
+
+/// One thing that happened: a warning spoken, a device plugged in, ...
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub time: DateTime<Local>,
+    /// What raised it, e.g. "CPU warning" or "device"
+    pub kind: String,
+    pub text: String,
+}
+
+/// The session's recent events, optionally appended to a JSON lines file.
+///
+/// When the file would grow past its size cap it is moved aside to
+/// `<file>.1` (replacing the previous one) and a fresh file is started, so
+/// at most twice the cap is ever on disk.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    entries: VecDeque<EventRecord>,
+    file: Option<(PathBuf, u64)>,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts appending to `path`, capped at `max_bytes`, and loads the tail of what earlier sessions wrote
+    pub fn persist_to(&mut self, path: PathBuf, max_bytes: u64) {
+        let mut entries: VecDeque<EventRecord> = read_tail(&path, EVENT_LOG_CAPACITY).into();
+        entries.extend(self.entries.drain(..));
+        while entries.len() > EVENT_LOG_CAPACITY {
+            entries.pop_front();
+        }
+        self.entries = entries;
+        self.file = Some((path, max_bytes));
+    }
+
+    /// Keeps events in memory only from now on
+    pub fn stop_persisting(&mut self) {
+        self.file = None;
+    }
+
+    pub fn is_persisting(&self) -> bool {
+        self.file.is_some()
+    }
+
+    pub fn set_max_bytes(&mut self, max_bytes: u64) {
+        if let Some((_, cap)) = &mut self.file {
+            *cap = max_bytes;
+        }
+    }
+
+    pub fn push(&mut self, kind: &str, text: impl Into<String>) {
+        self.push_record(EventRecord { time: Local::now(), kind: kind.to_string(), text: text.into() });
+    }
+
+    pub fn push_record(&mut self, record: EventRecord) {
+        if let Some((path, max_bytes)) = &self.file {
+            if let Err(e) = append(path, *max_bytes, &record) {
+                eprintln!("Failed to write event log {:?}: {}", path, e);
+            }
+        }
+        self.entries.push_back(record);
+        if self.entries.len() > EVENT_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &EventRecord> {
+        self.entries.iter()
+    }
+}
+
+/// Where the log is moved when it reaches its size cap
+pub fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
+
+/// Appends `record` as one JSON line, rotating first if it would take the file past `max_bytes`
+fn append(path: &Path, max_bytes: u64, record: &EventRecord) -> Result<(), Box<dyn std::error::Error>> {
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+    let size = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    if size > 0 && size + line.len() as u64 > max_bytes {
+        fs::rename(path, rotated_path(path))?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)?.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// The last `count` events across the rotated and current files, oldest first; unreadable lines are skipped
+pub fn read_tail(path: &Path, count: usize) -> Vec<EventRecord> {
+    let mut records: Vec<EventRecord> = [rotated_path(path), path.to_path_buf()].iter()
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .flat_map(|contents| contents.lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect::<Vec<EventRecord>>())
+        .collect();
+    let skip = records.len().saturating_sub(count);
+    records.drain(..skip);
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_tail_load_and_rotation() {
+        let dir = std::env::temp_dir().join(format!("cyber_ninja_events_{}", std::process::id()));
+        let path = dir.join("events.jsonl");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut log = EventLog::new();
+        log.push("device", "before persistence");
+        log.persist_to(path.clone(), 1_000);
+        for i in 0..30 {
+            log.push("CPU warning", format!("event {}", i));
+        }
+        let current_size = fs::metadata(&path).map(|meta| meta.len());
+        let rotated = fs::read_to_string(rotated_path(&path));
+        fs::write(&path, format!("{}not json\n", fs::read_to_string(&path).unwrap())).unwrap();
+
+        // A new session picks up where the last one left off
+        let mut next_session = EventLog::new();
+        next_session.persist_to(path.clone(), 1_000);
+        let texts: Vec<String> = next_session.entries().map(|record| record.text.clone()).collect();
+        let tail = read_tail(&path, 3);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(current_size.unwrap() <= 1_000, "the current file stays under the cap");
+        assert!(rotated.unwrap().contains("event "), "older events were moved aside");
+        assert_eq!(texts.last().map(String::as_str), Some("event 29"));
+        assert!(!texts.iter().any(|text| text == "before persistence"), "only persisted events come back");
+        let numbers: Vec<usize> = texts.iter().map(|text| text.trim_start_matches("event ").parse().unwrap()).collect();
+        assert!(numbers.windows(2).all(|pair| pair[1] == pair[0] + 1), "oldest first, across both files: {:?}", numbers);
+        assert!(numbers.len() < 30, "anything older than the rotated file is gone");
+        assert_eq!(tail.iter().map(|record| record.text.as_str()).collect::<Vec<_>>(), ["event 27", "event 28", "event 29"]);
+    }
+}
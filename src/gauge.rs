@@ -0,0 +1,96 @@
+use std::f32::consts::PI;
+use eframe::egui::{self, pos2, Align2, Color32, FontId, Painter, Pos2, Rect, Stroke};
+use crate::theme::{self, CyberTheme};
+
+/// The gauge's arcs open at the bottom: 270° from lower left, clockwise, to lower right
+pub const GAUGE_SWEEP: f32 = 1.5 * PI;
+/// Angle the arcs start at, measured clockwise from the positive x axis (screen y points down)
+const GAUGE_START: f32 = 0.75 * PI;
+
+/// Percentages at or above these are drawn in the warning and alert colors
+const GAUGE_WARN_PERCENT: f32 = 70.0;
+const GAUGE_ALERT_PERCENT: f32 = 90.0;
+
+const ARC_WIDTH: f32 = 10.0;
+
+/// How far round its arc a percentage reaches, in radians; out-of-range and NaN values are pinned to the ends
+pub fn sweep_angle(percent: f32) -> f32 {
+    if percent.is_nan() {
+        return 0.0;
+    }
+    percent.clamp(0.0, 100.0) / 100.0 * GAUGE_SWEEP
+}
+
+/// Points along an arc, enough of them that it looks round at `radius`
+fn arc_points(center: Pos2, radius: f32, start: f32, sweep: f32) -> Vec<Pos2> {
+    let segments = ((sweep * radius / 4.0).ceil() as usize).max(1);
+    (0..=segments)
+        .map(|i| {
+            let angle = start + sweep * i as f32 / segments as f32;
+            pos2(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect()
+}
+
+fn severity_color(percent: f32, theme: &CyberTheme) -> Color32 {
+    if percent >= GAUGE_ALERT_PERCENT {
+        theme.neon_alert
+    } else if percent >= GAUGE_WARN_PERCENT {
+        theme::SEVERITY_WARN
+    } else {
+        theme::SEVERITY_OK
+    }
+}
+
+/// One radial gauge for the compact HUD: CPU on the outer arc, memory on the inner one
+pub fn draw_combined_gauge(painter: &Painter, rect: Rect, cpu: f32, memory: f32, theme: &CyberTheme) {
+    let center = rect.center();
+    let outer = (rect.width().min(rect.height()) / 2.0 - ARC_WIDTH).max(ARC_WIDTH * 3.0);
+    let inner = outer - ARC_WIDTH * 2.0;
+    let track = Color32::from_rgba_unmultiplied(theme.text_dim.r(), theme.text_dim.g(), theme.text_dim.b(), 50);
+
+    for (radius, percent) in [(outer, cpu), (inner, memory)] {
+        painter.add(egui::Shape::line(arc_points(center, radius, GAUGE_START, GAUGE_SWEEP), Stroke::new(ARC_WIDTH, track)));
+        let sweep = sweep_angle(percent);
+        if sweep > 0.0 {
+            let color = severity_color(percent, theme);
+            painter.add(egui::Shape::line(arc_points(center, radius, GAUGE_START, sweep), Stroke::new(ARC_WIDTH, color)));
+        }
+    }
+
+    let shown = |percent: f32| if percent.is_finite() { percent.clamp(0.0, 100.0) } else { 0.0 };
+    painter.text(
+        center - egui::vec2(0.0, 12.0),
+        Align2::CENTER_CENTER,
+        format!("CPU {:.0}%", shown(cpu)),
+        FontId::monospace(18.0),
+        severity_color(shown(cpu), theme),
+    );
+    painter.text(
+        center + egui::vec2(0.0, 12.0),
+        Align2::CENTER_CENTER,
+        format!("MEM {:.0}%", shown(memory)),
+        FontId::monospace(18.0),
+        severity_color(shown(memory), theme),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_angle_clamps_to_the_arc() {
+        assert_eq!(sweep_angle(0.0), 0.0);
+        assert_eq!(sweep_angle(100.0), GAUGE_SWEEP);
+        assert!((sweep_angle(50.0) - GAUGE_SWEEP / 2.0).abs() < 1e-6);
+        assert_eq!(sweep_angle(-5.0), 0.0);
+        assert_eq!(sweep_angle(140.0), GAUGE_SWEEP);
+        assert_eq!(sweep_angle(f32::NAN), 0.0);
+
+        // The arc for a value ends where its angle says
+        let points = arc_points(Pos2::ZERO, 10.0, GAUGE_START, sweep_angle(100.0));
+        let end = points.last().unwrap();
+        assert!((end.x - 10.0 * (0.25 * PI).cos()).abs() < 1e-4 && (end.y - 10.0 * (0.25 * PI).sin()).abs() < 1e-4);
+    }
+}
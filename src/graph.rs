@@ -0,0 +1,203 @@
+use eframe::egui::{self, pos2, Color32, Pos2, Rect, Stroke};
+use serde::{Serialize, Deserialize};
+use crate::message_system::MetricKind;
+
+/// How the recent samples of a history graph are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GraphStyle {
+    #[default]
+    Line,
+    Bars,
+    Area,
+    Stepped,
+}
+
+impl GraphStyle {
+    pub const ALL: [GraphStyle; 4] = [GraphStyle::Line, GraphStyle::Bars, GraphStyle::Area, GraphStyle::Stepped];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GraphStyle::Line => "Line",
+            GraphStyle::Bars => "Bars",
+            GraphStyle::Area => "Area",
+            GraphStyle::Stepped => "Stepped",
+        }
+    }
+}
+
+/// Shapes drawing `points` (oldest first) in `style`, with `baseline` as the graph's zero line.
+///
+/// Bars are one rect per sample, `bar_width` wide. Area fills one quad per
+/// segment, since egui only fills convex polygons, then outlines the top.
+pub fn graph_shapes(style: GraphStyle, points: &[Pos2], baseline: f32, bar_width: f32, color: Color32) -> Vec<egui::Shape> {
+    let stroke = Stroke::new(1.5, color);
+    match style {
+        GraphStyle::Line if points.len() >= 2 => vec![egui::Shape::line(points.to_vec(), stroke)],
+        GraphStyle::Bars => points.iter()
+            .map(|point| egui::Shape::rect_filled(
+                Rect::from_min_max(pos2(point.x - bar_width, point.y.min(baseline - 1.0)), pos2(point.x, baseline)),
+                0.0,
+                color,
+            ))
+            .collect(),
+        GraphStyle::Area if points.len() >= 2 => {
+            let fill = Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), 90);
+            let mut shapes: Vec<egui::Shape> = points.windows(2)
+                .map(|pair| egui::Shape::convex_polygon(
+                    vec![pair[0], pair[1], pos2(pair[1].x, baseline), pos2(pair[0].x, baseline)],
+                    fill,
+                    Stroke::NONE,
+                ))
+                .collect();
+            shapes.push(egui::Shape::line(points.to_vec(), stroke));
+            shapes
+        }
+        GraphStyle::Stepped if points.len() >= 2 => {
+            let mut steps = Vec::with_capacity(points.len() * 2 - 1);
+            for pair in points.windows(2) {
+                steps.push(pair[0]);
+                steps.push(pos2(pair[1].x, pair[0].y));
+            }
+            steps.extend(points.last());
+            vec![egui::Shape::line(steps, stroke)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// How the vertical axis of a metric's history graph is scaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AxisScale {
+    /// Zero to the metric's full scale: 100 for percentages, the bandwidth threshold for network
+    Fixed,
+    /// Zero to the highest value in all the history kept
+    AutoPeak,
+    /// Zero to the highest value in the visible window
+    Rolling,
+}
+
+impl AxisScale {
+    pub const ALL: [AxisScale; 3] = [AxisScale::Fixed, AxisScale::AutoPeak, AxisScale::Rolling];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AxisScale::Fixed => "Fixed",
+            AxisScale::AutoPeak => "Peak",
+            AxisScale::Rolling => "Rolling",
+        }
+    }
+
+    /// Percentages stay comparable on a fixed scale; rates have no natural ceiling
+    pub fn default_for(kind: MetricKind) -> Self {
+        match kind {
+            MetricKind::Network => AxisScale::AutoPeak,
+            _ => AxisScale::Fixed,
+        }
+    }
+}
+
+/// Smallest top of an auto-scaled axis, so an idle series stays near the floor instead of magnifying noise
+pub const AXIS_FLOOR: f32 = 1.0;
+
+/// The (bottom, top) of a graph's axis: `full_scale` when fixed, otherwise the peak of
+/// `history` (everything kept) or `visible` (what's on screen), at least `AXIS_FLOOR`
+pub fn axis_bounds(scale: AxisScale, full_scale: f32, history: impl IntoIterator<Item = f32>, visible: impl IntoIterator<Item = f32>) -> (f32, f32) {
+    let peak = |values: &mut dyn Iterator<Item = f32>| values.filter(|value| value.is_finite()).fold(AXIS_FLOOR, f32::max);
+    let top = match scale {
+        AxisScale::Fixed => full_scale,
+        AxisScale::AutoPeak => peak(&mut history.into_iter()),
+        AxisScale::Rolling => peak(&mut visible.into_iter()),
+    };
+    (0.0, top.max(f32::EPSILON))
+}
+
+/// How a series is brought onto the unified graph's shared 0-100 scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesScale {
+    /// Already a percentage, clamped to 0-100
+    Percent,
+    /// Scaled so the series' own peak reaches 100, for unbounded values like throughput
+    Auto,
+}
+
+/// `values` on a 0-100 scale; NaN and infinite samples become 0
+pub fn normalize_series(values: &[f32], scale: SeriesScale) -> Vec<f32> {
+    let finite = |value: f32| if value.is_finite() { value.max(0.0) } else { 0.0 };
+    match scale {
+        SeriesScale::Percent => values.iter().map(|value| finite(*value).min(100.0)).collect(),
+        SeriesScale::Auto => {
+            let peak = values.iter().map(|value| finite(*value)).fold(0.0, f32::max);
+            if peak <= 0.0 {
+                return vec![0.0; values.len()];
+            }
+            values.iter().map(|value| finite(*value) / peak * 100.0).collect()
+        }
+    }
+}
+
+/// The value of the sample whose x is closest to `x`, for the hover crosshair
+pub fn value_nearest(samples: &[(f32, f32)], x: f32) -> Option<f32> {
+    samples.iter()
+        .min_by(|a, b| (a.0 - x).abs().total_cmp(&(b.0 - x).abs()))
+        .map(|(_, value)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_count_per_style() {
+        let points: Vec<Pos2> = (0..10).map(|i| pos2(i as f32 * 5.0, 20.0 + i as f32)).collect();
+        let count = |style| graph_shapes(style, &points, 48.0, 4.0, Color32::WHITE).len();
+
+        assert_eq!(count(GraphStyle::Line), 1);
+        assert_eq!(count(GraphStyle::Bars), 10, "one rect per sample");
+        assert_eq!(count(GraphStyle::Area), 9 + 1, "one quad per segment plus the outline");
+        assert_eq!(count(GraphStyle::Stepped), 1);
+
+        match &graph_shapes(GraphStyle::Stepped, &points, 48.0, 4.0, Color32::WHITE)[0] {
+            egui::Shape::Path(path) => assert_eq!(path.points.len(), 19),
+            other => panic!("expected a path, got {:?}", other),
+        }
+
+        // A lone sample only shows up as a bar
+        for style in GraphStyle::ALL {
+            let expected = usize::from(style == GraphStyle::Bars);
+            assert_eq!(graph_shapes(style, &points[..1], 48.0, 4.0, Color32::WHITE).len(), expected, "{:?}", style);
+        }
+    }
+
+    #[test]
+    fn test_axis_bounds_per_scale() {
+        // Everything kept, of which only the last three samples are on screen
+        let history = [2.0, 40.0, 12.0, 6.0, 9.0];
+        let visible = &history[2..];
+        let bounds = |scale| axis_bounds(scale, 100.0, history, visible.iter().copied());
+
+        assert_eq!(bounds(AxisScale::Fixed), (0.0, 100.0));
+        assert_eq!(bounds(AxisScale::AutoPeak), (0.0, 40.0));
+        assert_eq!(bounds(AxisScale::Rolling), (0.0, 12.0));
+
+        // A flat, idle series isn't blown up to fill the graph
+        assert_eq!(axis_bounds(AxisScale::Rolling, 100.0, [0.1, 0.2], [0.1, f32::NAN]), (0.0, AXIS_FLOOR));
+        assert_eq!(axis_bounds(AxisScale::Fixed, 0.0, [], []).1, f32::EPSILON);
+
+        assert_eq!(AxisScale::default_for(MetricKind::Cpu), AxisScale::Fixed);
+        assert_eq!(AxisScale::default_for(MetricKind::Network), AxisScale::AutoPeak);
+    }
+
+    #[test]
+    fn test_series_normalize_to_a_shared_scale() {
+        assert_eq!(normalize_series(&[0.0, 42.5, 100.0, 130.0, -3.0, f32::NAN], SeriesScale::Percent), vec![0.0, 42.5, 100.0, 100.0, 0.0, 0.0]);
+        // Throughput peaks at 100 whatever its units
+        assert_eq!(normalize_series(&[2.0, 8.0, 4.0, f32::INFINITY], SeriesScale::Auto), vec![25.0, 100.0, 50.0, 0.0]);
+        assert_eq!(normalize_series(&[0.0, 0.0], SeriesScale::Auto), vec![0.0, 0.0], "an idle series stays on the floor");
+        assert!(normalize_series(&[], SeriesScale::Auto).is_empty());
+
+        let samples = [(0.0, 10.0), (10.0, 20.0), (20.0, 30.0)];
+        assert_eq!(value_nearest(&samples, 12.0), Some(20.0));
+        assert_eq!(value_nearest(&samples, 99.0), Some(30.0));
+        assert_eq!(value_nearest(&[], 5.0), None);
+    }
+}
@@ -0,0 +1,530 @@
+use std::collections::VecDeque;
+use std::mem;
+use std::time::{Duration, Instant, SystemTime};
+use serde::{Serialize, Deserialize};
+
+/// Minimum spacing between two recorded samples (the fastest allowed refresh interval)
+pub const HISTORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default cap on the combined footprint of all metric histories
+pub const DEFAULT_HISTORY_BUDGET_BYTES: usize = 1024 * 1024;
+
+/// Summary of every sample that fell into one aggregation interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub start: Instant,
+    pub min: f32,
+    pub max: f32,
+    pub sum: f32,
+    pub count: u32,
+}
+
+impl Bucket {
+    pub fn new(start: Instant, value: f32) -> Self {
+        Self {
+            start,
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    pub fn add(&mut self, value: f32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Folds another bucket's samples into this one
+    pub fn merge(&mut self, other: &Bucket) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.count += other.count;
+    }
+
+    pub fn avg(&self) -> f32 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.sum / self.count as f32
+    }
+}
+
+/// Start of the aggregation interval `time` falls into, aligned to `origin`
+pub fn bucket_start(origin: Instant, time: Instant, interval: Duration) -> Instant {
+    let interval_ms = interval.as_millis().max(1);
+    let elapsed_ms = time.saturating_duration_since(origin).as_millis();
+    let index = elapsed_ms / interval_ms;
+    origin + Duration::from_millis((index * interval_ms) as u64)
+}
+
+/// How far back the history graphs reach
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GraphWindow {
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    #[default]
+    OneHour,
+}
+
+impl GraphWindow {
+    pub const ALL: [GraphWindow; 4] = [GraphWindow::OneMinute, GraphWindow::FiveMinutes, GraphWindow::FifteenMinutes, GraphWindow::OneHour];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GraphWindow::OneMinute => "1m",
+            GraphWindow::FiveMinutes => "5m",
+            GraphWindow::FifteenMinutes => "15m",
+            GraphWindow::OneHour => "1h",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(match self {
+            GraphWindow::OneMinute => 60,
+            GraphWindow::FiveMinutes => 5 * 60,
+            GraphWindow::FifteenMinutes => 15 * 60,
+            GraphWindow::OneHour => 60 * 60,
+        })
+    }
+}
+
+/// The part of a history one graph window shows: buckets for the older stretch, raw samples for the recent one
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowView {
+    pub buckets: Vec<Bucket>,
+    pub raw: Vec<(Instant, f32)>,
+    /// Width of each bucket, a multiple of the history's bucket interval after merging
+    pub bucket_interval: Duration,
+}
+
+/// Recent samples at full resolution, older ones folded into min/max/avg buckets.
+///
+/// Memory stays bounded: at most `raw_window / HISTORY_SAMPLE_INTERVAL` raw
+/// samples plus `retention / bucket_interval` buckets.
+#[derive(Debug, Clone)]
+pub struct MetricHistory {
+    raw_window: Duration,
+    bucket_interval: Duration,
+    retention: Duration,
+    origin: Option<Instant>,
+    raw: VecDeque<(Instant, f32)>,
+    buckets: VecDeque<Bucket>,
+}
+
+impl MetricHistory {
+    pub fn new(raw_window: Duration, bucket_interval: Duration, retention: Duration) -> Self {
+        Self {
+            raw_window,
+            bucket_interval: bucket_interval.max(Duration::from_millis(1)),
+            retention: retention.max(raw_window),
+            origin: None,
+            raw: VecDeque::new(),
+            buckets: VecDeque::new(),
+        }
+    }
+
+    /// Changes the window sizes; existing buckets are kept and expire normally
+    pub fn configure(&mut self, raw_window: Duration, bucket_interval: Duration, retention: Duration) {
+        self.raw_window = raw_window;
+        self.bucket_interval = bucket_interval.max(Duration::from_millis(1));
+        self.retention = retention.max(raw_window);
+    }
+
+    pub fn push(&mut self, value: f32, now: Instant) {
+        self.origin.get_or_insert(now);
+        self.raw.push_back((now, value));
+        self.compact(now);
+    }
+
+    /// Moves samples older than the raw window into buckets and drops expired buckets
+    fn compact(&mut self, now: Instant) {
+        let origin = match self.origin {
+            Some(origin) => origin,
+            None => return,
+        };
+
+        while let Some(&(time, value)) = self.raw.front() {
+            if now.saturating_duration_since(time) <= self.raw_window {
+                break;
+            }
+            self.raw.pop_front();
+
+            let start = bucket_start(origin, time, self.bucket_interval);
+            match self.buckets.back_mut() {
+                Some(bucket) if bucket.start == start => bucket.add(value),
+                _ => self.buckets.push_back(Bucket::new(start, value)),
+            }
+        }
+
+        while let Some(bucket) = self.buckets.front() {
+            if now.saturating_duration_since(bucket.start + self.bucket_interval) <= self.retention {
+                break;
+            }
+            self.buckets.pop_front();
+        }
+    }
+
+    pub fn raw(&self) -> impl Iterator<Item = &(Instant, f32)> {
+        self.raw.iter()
+    }
+
+    pub fn buckets(&self) -> impl Iterator<Item = &Bucket> {
+        self.buckets.iter()
+    }
+
+    /// What a graph covering the last `span` before `now` should draw.
+    ///
+    /// Only data inside the span is returned and nothing stored is touched. When
+    /// more than `max_buckets` buckets fall inside, neighbours are merged so a
+    /// long window costs no more to draw than a short one.
+    pub fn window(&self, span: Duration, now: Instant, max_buckets: usize) -> WindowView {
+        let since = now.checked_sub(span);
+        let inside = |time: Instant| since.is_none_or(|since| time >= since);
+        let raw: Vec<(Instant, f32)> = self.raw.iter().filter(|(time, _)| inside(*time)).copied().collect();
+        let overlaps = |bucket: &&Bucket| since.is_none_or(|since| bucket.start + self.bucket_interval > since);
+        let in_span: Vec<&Bucket> = self.buckets.iter().filter(overlaps).collect();
+
+        let factor = in_span.len().div_ceil(max_buckets.max(1)).max(1);
+        let mut buckets: Vec<Bucket> = Vec::with_capacity(in_span.len() / factor + 1);
+        for group in in_span.chunks(factor) {
+            let mut merged = *group[0];
+            for bucket in &group[1..] {
+                merged.merge(bucket);
+            }
+            buckets.push(merged);
+        }
+        WindowView { buckets, raw, bucket_interval: self.bucket_interval * factor as u32 }
+    }
+
+    pub fn latest(&self) -> Option<f32> {
+        self.raw.back().map(|(_, value)| *value)
+    }
+
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.raw.len() + self.buckets.len()
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty() && self.buckets.is_empty()
+    }
+
+    /// Forgets every sample, e.g. after the machine slept and the readings around it are meaningless
+    pub fn clear(&mut self) {
+        self.origin = None;
+        self.raw.clear();
+        self.buckets.clear();
+    }
+
+    /// Estimated memory held by the stored samples and buckets, in bytes
+    pub fn footprint_bytes(&self) -> usize {
+        self.raw.len() * mem::size_of::<(Instant, f32)>() + self.buckets.len() * mem::size_of::<Bucket>()
+    }
+
+    /// Drops the oldest buckets, then the oldest raw samples, until the footprint fits in `bytes`
+    pub fn trim_to(&mut self, bytes: usize) {
+        while self.footprint_bytes() > bytes {
+            if self.buckets.pop_front().is_none() && self.raw.pop_front().is_none() {
+                break;
+            }
+        }
+    }
+}
+
+/// Keeps the combined footprint of `histories` within `budget` bytes.
+///
+/// When over budget, each history is trimmed from its oldest end down to its
+/// proportional share of the budget. Returns the number of bytes freed.
+pub fn enforce_budget(histories: &mut [&mut MetricHistory], budget: usize) -> usize {
+    let total: usize = histories.iter().map(|history| history.footprint_bytes()).sum();
+    if total <= budget {
+        return 0;
+    }
+
+    for history in histories.iter_mut() {
+        let share = (history.footprint_bytes() as u128 * budget as u128 / total as u128) as usize;
+        history.trim_to(share);
+    }
+    total - histories.iter().map(|history| history.footprint_bytes()).sum::<usize>()
+}
+
+/// Spots refreshes that resume after a long pause, like waking from sleep.
+///
+/// Works in wall-clock time, because on some platforms `Instant` stands
+/// still while the machine is suspended.
+#[derive(Debug, Clone, Default)]
+pub struct GapDetector {
+    last: Option<SystemTime>,
+}
+
+impl GapDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a refresh at `now`; returns the gap since the previous one when it is longer than `threshold`
+    pub fn observe(&mut self, now: SystemTime, threshold: Duration) -> Option<Duration> {
+        self.last.replace(now)
+            .and_then(|last| now.duration_since(last).ok())
+            .filter(|gap| *gap > threshold)
+    }
+}
+
+/// Lowest smoothing factor offered; smaller values lag the real value by minutes
+pub const MIN_SMOOTHING_ALPHA: f32 = 0.05;
+
+/// Exponential moving average over graph samples; alpha 1.0 passes them through unchanged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ema {
+    alpha: f32,
+    value: Option<f32>,
+}
+
+impl Ema {
+    pub fn new(alpha: f32) -> Self {
+        let mut ema = Self { alpha: 1.0, value: None };
+        ema.set_alpha(alpha);
+        ema
+    }
+
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = if alpha.is_nan() { 1.0 } else { alpha.clamp(MIN_SMOOTHING_ALPHA, 1.0) };
+    }
+
+    /// Folds in a sample and returns the smoothed value; the first sample is taken as-is
+    pub fn next(&mut self, sample: f32) -> f32 {
+        let value = match self.value {
+            Some(previous) => previous + self.alpha * (sample - previous),
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+}
+
+/// Timestamped samples smoothed at `alpha` for drawing; the history itself keeps the raw readings
+pub fn smoothed(samples: &[(Instant, f32)], alpha: f32) -> Vec<(Instant, f32)> {
+    let mut ema = Ema::new(alpha);
+    samples.iter().map(|(time, value)| (*time, ema.next(*value))).collect()
+}
+
+/// A noisy but repeatable 0-100 signal for previewing smoothing in the settings
+pub fn preview_signal(len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| {
+            let t = i as f32;
+            let noise = ((t * 12.9898).sin() * 43758.547).fract();
+            (50.0 + 20.0 * (t * 0.15).sin() + 15.0 * noise).clamp(0.0, 100.0)
+        })
+        .collect()
+}
+
+/// The preview signal and what it looks like smoothed at `alpha`
+pub fn smoothing_preview(alpha: f32, len: usize) -> (Vec<f32>, Vec<f32>) {
+    let signal = preview_signal(len);
+    let mut ema = Ema::new(alpha);
+    let smoothed = signal.iter().map(|sample| ema.next(*sample)).collect();
+    (signal, smoothed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_aggregation() {
+        let mut bucket = Bucket::new(Instant::now(), 10.0);
+        bucket.add(30.0);
+        bucket.add(20.0);
+
+        assert_eq!(bucket.min, 10.0);
+        assert_eq!(bucket.max, 30.0);
+        assert_eq!(bucket.count, 3);
+        assert_eq!(bucket.avg(), 20.0);
+    }
+
+    #[test]
+    fn test_bucket_start_alignment() {
+        let origin = Instant::now();
+        let interval = Duration::from_secs(10);
+
+        assert_eq!(bucket_start(origin, origin, interval), origin);
+        assert_eq!(bucket_start(origin, origin + Duration::from_secs(9), interval), origin);
+        assert_eq!(
+            bucket_start(origin, origin + Duration::from_secs(25), interval),
+            origin + Duration::from_secs(20)
+        );
+    }
+
+    #[test]
+    fn test_old_samples_are_downsampled() {
+        let start = Instant::now();
+        let mut history = MetricHistory::new(
+            Duration::from_secs(5),
+            Duration::from_secs(10),
+            Duration::from_secs(60),
+        );
+
+        // 20 seconds of samples, one per second, with values 0..20
+        for i in 0..20 {
+            history.push(i as f32, start + Duration::from_secs(i));
+        }
+
+        // Samples older than 5s were folded into two 10s buckets
+        let buckets: Vec<&Bucket> = history.buckets().collect();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!((buckets[0].min, buckets[0].max, buckets[0].avg()), (0.0, 9.0, 4.5));
+        assert_eq!((buckets[1].min, buckets[1].max), (10.0, 13.0));
+        assert_eq!(history.raw().count(), 6);
+        assert_eq!(history.latest(), Some(19.0));
+    }
+
+    #[test]
+    fn test_history_stays_bounded() {
+        let start = Instant::now();
+        let mut history = MetricHistory::new(
+            Duration::from_secs(60),
+            Duration::from_secs(10),
+            Duration::from_secs(3600),
+        );
+
+        // Two hours of samples at the sampling interval
+        let samples = 2 * 3600 * 10;
+        for i in 0..samples {
+            history.push(50.0, start + HISTORY_SAMPLE_INTERVAL * i);
+        }
+
+        assert!(history.raw().count() <= 601);
+        assert!(history.buckets().count() <= 361);
+        assert!(history.buckets().all(|bucket| bucket.min == 50.0 && bucket.max == 50.0));
+    }
+
+    #[test]
+    fn test_window_slices_and_aggregates_history() {
+        let start = Instant::now();
+        let mut history = MetricHistory::new(
+            Duration::from_secs(60),
+            Duration::from_secs(10),
+            Duration::from_secs(3600),
+        );
+        // Half an hour, one sample per second, valued by the minute it falls in
+        for i in 0..=1800 {
+            history.push((i / 60) as f32, start + Duration::from_secs(i));
+        }
+        let now = start + Duration::from_secs(1800);
+        let stored = (history.raw().count(), history.buckets().count());
+
+        // The last minute is all raw samples
+        let minute = history.window(GraphWindow::OneMinute.duration(), now, 100);
+        assert!(minute.buckets.is_empty());
+        assert_eq!(minute.raw.len(), 61);
+        assert_eq!(minute.raw.first().map(|(time, _)| *time), Some(now - Duration::from_secs(60)));
+
+        // Five minutes: the raw minute plus the four before it as 10s buckets
+        let five = history.window(GraphWindow::FiveMinutes.duration(), now, 100);
+        assert_eq!(five.buckets.len(), 24);
+        assert_eq!(five.bucket_interval, Duration::from_secs(10));
+        assert_eq!(five.buckets[0].min, 25.0);
+
+        // The hour holds more buckets than fit, so pairs are merged with nothing lost
+        let hour = history.window(GraphWindow::OneHour.duration(), now, 90);
+        assert_eq!(hour.buckets.len(), 87);
+        assert_eq!(hour.bucket_interval, Duration::from_secs(20));
+        let samples: u32 = hour.buckets.iter().map(|bucket| bucket.count).sum::<u32>() + hour.raw.len() as u32;
+        assert_eq!(samples, 1801);
+        assert_eq!((hour.buckets[0].min, hour.buckets[0].max, hour.buckets[0].avg()), (0.0, 0.0, 0.0));
+
+        // Looking doesn't change what is retained
+        assert_eq!((history.raw().count(), history.buckets().count()), stored);
+    }
+
+    #[test]
+    fn test_long_gap_is_reported_once_and_clears() {
+        let threshold = Duration::from_secs(60);
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut gaps = GapDetector::new();
+        assert_eq!(gaps.observe(start, threshold), None, "nothing to compare the first refresh with");
+        assert_eq!(gaps.observe(start + Duration::from_secs(1), threshold), None);
+        let woke = start + Duration::from_secs(1 + 3600);
+        assert_eq!(gaps.observe(woke, threshold), Some(Duration::from_secs(3600)));
+        assert_eq!(gaps.observe(woke + Duration::from_secs(1), threshold), None, "reported once");
+        assert_eq!(gaps.observe(start, threshold), None, "a clock set backwards is not a resume");
+
+        let mut history = MetricHistory::new(Duration::from_secs(60), Duration::from_secs(10), Duration::from_secs(600));
+        let now = Instant::now();
+        history.push(42.0, now);
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.latest(), None);
+    }
+
+    #[test]
+    fn test_smoothing_preview_matches_graph_smoothing() {
+        for alpha in [MIN_SMOOTHING_ALPHA, 0.3, 1.0] {
+            let (signal, preview) = smoothing_preview(alpha, 64);
+
+            // Same path the graphs take when drawing
+            let start = Instant::now();
+            let stamped: Vec<(Instant, f32)> = signal.iter().enumerate()
+                .map(|(i, value)| (start + Duration::from_secs(i as u64), *value))
+                .collect();
+            let drawn: Vec<f32> = smoothed(&stamped, alpha).into_iter().map(|(_, value)| value).collect();
+            assert_eq!(preview, drawn);
+        }
+
+        let (signal, smoothed) = smoothing_preview(1.0, 16);
+        assert_eq!(signal, smoothed, "alpha 1.0 leaves the signal alone");
+        assert!(signal.iter().all(|value| (0.0..=100.0).contains(value)));
+
+        let mut ema = Ema::new(0.5);
+        assert_eq!((ema.next(10.0), ema.next(20.0), ema.next(20.0)), (10.0, 15.0, 17.5));
+    }
+
+    #[test]
+    fn test_budget_trims_oldest_samples_proportionally() {
+        let start = Instant::now();
+        let new_history = || MetricHistory::new(
+            Duration::from_secs(60),
+            Duration::from_secs(10),
+            Duration::from_secs(3600),
+        );
+        let (mut large, mut small) = (new_history(), new_history());
+        for i in 0..200 {
+            large.push(i as f32, start + Duration::from_secs(i));
+        }
+        for i in 0..50 {
+            small.push(i as f32, start + Duration::from_secs(150 + i));
+        }
+
+        let sample = mem::size_of::<(Instant, f32)>();
+        assert_eq!(large.footprint_bytes(), 61 * sample + 14 * mem::size_of::<Bucket>());
+        assert_eq!(small.footprint_bytes(), 50 * sample);
+
+        // Under budget nothing changes
+        let total = large.footprint_bytes() + small.footprint_bytes();
+        assert_eq!(enforce_budget(&mut [&mut large, &mut small], total), 0);
+        assert_eq!(large.len(), 75);
+
+        // Halving the budget halves both, oldest first
+        let budget = total / 2;
+        let (large_before, small_before) = (large.footprint_bytes(), small.footprint_bytes());
+        let freed = enforce_budget(&mut [&mut large, &mut small], budget);
+        assert!(freed >= total - budget);
+        assert!(large.footprint_bytes() + small.footprint_bytes() <= budget);
+        assert!(large.footprint_bytes() <= large_before / 2);
+        assert!(small.footprint_bytes() <= small_before / 2);
+        assert!(small.footprint_bytes() + sample > small_before / 2);
+        assert_eq!(large.latest(), Some(199.0));
+        assert_eq!(small.raw().next().map(|(_, value)| *value), Some(25.0));
+    }
+}
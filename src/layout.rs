@@ -0,0 +1,277 @@
+use serde::{Serialize, Deserialize};
+use crate::message_system::MetricKind;
+
+/// One of the two dashboard columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Column {
+    Left,
+    Right,
+}
+
+/// Where a metric panel sits on the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanelPlacement {
+    pub kind: MetricKind,
+    pub column: Column,
+}
+
+/// Column and top-to-bottom order of every metric panel.
+///
+/// Panels keep their place while hidden, so re-enabling one puts it back
+/// where it was.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub panels: Vec<PanelPlacement>,
+    /// Panels folded down to their header
+    pub collapsed: Vec<MetricKind>,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            panels: MetricKind::ALL.iter()
+                .map(|&kind| PanelPlacement {
+                    kind,
+                    column: if kind == MetricKind::Cpu { Column::Left } else { Column::Right },
+                })
+                .collect(),
+            collapsed: Vec::new(),
+        }
+    }
+}
+
+impl PanelLayout {
+    /// Visible panels in `column`, top to bottom
+    pub fn column(&self, column: Column, visible: &[MetricKind]) -> Vec<MetricKind> {
+        self.panels.iter()
+            .filter(|panel| panel.column == column && visible.contains(&panel.kind))
+            .map(|panel| panel.kind)
+            .collect()
+    }
+
+    /// Visible panels as one stack, left column first, for narrow windows
+    pub fn stacked(&self, visible: &[MetricKind]) -> Vec<MetricKind> {
+        let mut panels = self.column(Column::Left, visible);
+        panels.extend(self.column(Column::Right, visible));
+        panels
+    }
+
+    pub fn column_of(&self, kind: MetricKind) -> Option<Column> {
+        self.panels.iter()
+            .find(|panel| panel.kind == kind)
+            .map(|panel| panel.column)
+    }
+
+    /// Moves `kind` into `column`, just above `before`, or to the bottom of the column if None
+    pub fn move_panel(&mut self, kind: MetricKind, column: Column, before: Option<MetricKind>) {
+        self.panels.retain(|panel| panel.kind != kind);
+        let placement = PanelPlacement { kind, column };
+
+        let target = before.and_then(|before| self.panels.iter().position(|panel| panel.kind == before));
+        let index = match target {
+            Some(index) => index,
+            None => self.panels.iter()
+                .rposition(|panel| panel.column == column)
+                .map_or(self.panels.len(), |last| last + 1),
+        };
+        self.panels.insert(index, placement);
+    }
+
+    pub fn is_collapsed(&self, kind: MetricKind) -> bool {
+        self.collapsed.contains(&kind)
+    }
+
+    pub fn set_collapsed(&mut self, kind: MetricKind, collapsed: bool) {
+        self.collapsed.retain(|other| *other != kind);
+        if collapsed {
+            self.collapsed.push(kind);
+        }
+    }
+
+    /// Drops duplicate panels and appends missing ones to the right column, e.g. from older config files
+    pub fn normalize(&mut self) {
+        let mut seen = Vec::new();
+        self.panels.retain(|panel| {
+            let first = !seen.contains(&panel.kind);
+            seen.push(panel.kind);
+            first
+        });
+        for kind in MetricKind::ALL {
+            if !seen.contains(&kind) {
+                self.panels.push(PanelPlacement { kind, column: Column::Right });
+            }
+        }
+    }
+}
+
+/// The panel a dragged card dropped at `y` should go above: the first card whose centre is below `y`
+pub fn insert_before(cards: &[(MetricKind, f32)], y: f32) -> Option<MetricKind> {
+    cards.iter()
+        .find(|(_, center_y)| y < *center_y)
+        .map(|(kind, _)| *kind)
+}
+
+/// Smoothstep easing: slow at both ends, and exactly 0 and 1 at the endpoints
+pub fn ease_in_out(t: f32) -> f32 {
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Expand/collapse state of one panel, animated over a fixed duration.
+///
+/// Progress moves linearly with frame time and is eased when read, so the
+/// panel's height grows and shrinks smoothly in either direction, including
+/// when it is toggled again halfway through.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanelFold {
+    open: bool,
+    progress: f32,
+    /// Height of the panel's content when fully open, as measured on the last frame drawn
+    pub full_height: Option<f32>,
+}
+
+impl PanelFold {
+    pub fn new(open: bool) -> Self {
+        Self {
+            open,
+            progress: if open { 1.0 } else { 0.0 },
+            full_height: None,
+        }
+    }
+
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    /// Moves towards the target state by `dt` seconds of a `duration`-second animation; a zero duration snaps
+    pub fn advance(&mut self, dt: f32, duration: f32) {
+        let target = if self.open { 1.0 } else { 0.0 };
+        if duration <= 0.0 || !dt.is_finite() {
+            self.progress = target;
+            return;
+        }
+        let step = dt.max(0.0) / duration;
+        self.progress = if self.open {
+            (self.progress + step).min(1.0)
+        } else {
+            (self.progress - step).max(0.0)
+        };
+    }
+
+    /// How far open the panel is drawn, from 0 (header only) to 1 (fully open)
+    pub fn open_fraction(&self) -> f32 {
+        ease_in_out(self.progress)
+    }
+
+    pub fn animating(&self) -> bool {
+        self.progress != if self.open { 1.0 } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_layout_matches_classic_arrangement() {
+        let layout = PanelLayout::default();
+        assert_eq!(layout.column(Column::Left, &MetricKind::ALL), vec![MetricKind::Cpu]);
+        assert_eq!(
+            layout.column(Column::Right, &MetricKind::ALL),
+            vec![MetricKind::Memory, MetricKind::Disk, MetricKind::Network]
+        );
+    }
+
+    #[test]
+    fn test_move_panel_between_columns() {
+        let mut layout = PanelLayout::default();
+
+        // Network to the top of the left column
+        layout.move_panel(MetricKind::Network, Column::Left, Some(MetricKind::Cpu));
+        assert_eq!(layout.column(Column::Left, &MetricKind::ALL), vec![MetricKind::Network, MetricKind::Cpu]);
+
+        // CPU to the bottom of the right column
+        layout.move_panel(MetricKind::Cpu, Column::Right, None);
+        assert_eq!(
+            layout.column(Column::Right, &MetricKind::ALL),
+            vec![MetricKind::Memory, MetricKind::Disk, MetricKind::Cpu]
+        );
+        assert_eq!(layout.column_of(MetricKind::Cpu), Some(Column::Right));
+        assert_eq!(layout.panels.len(), 4);
+    }
+
+    #[test]
+    fn test_hidden_panels_take_no_space() {
+        let layout = PanelLayout::default();
+        let visible = [MetricKind::Cpu, MetricKind::Network];
+        assert_eq!(layout.column(Column::Right, &visible), vec![MetricKind::Network]);
+        assert_eq!(layout.stacked(&visible), vec![MetricKind::Cpu, MetricKind::Network]);
+    }
+
+    #[test]
+    fn test_layout_serializes_and_restores() {
+        let mut layout = PanelLayout::default();
+        layout.move_panel(MetricKind::Disk, Column::Left, None);
+
+        let json = serde_json::to_string(&layout).unwrap();
+        let mut restored: PanelLayout = serde_json::from_str(&json).unwrap();
+        restored.normalize();
+        assert_eq!(restored, layout);
+
+        // Missing or duplicated entries from hand-edited files are repaired
+        let mut partial: PanelLayout = serde_json::from_str(
+            r#"{"panels": [{"kind": "Disk", "column": "Left"}, {"kind": "Disk", "column": "Right"}]}"#
+        ).unwrap();
+        partial.normalize();
+        assert_eq!(partial.column(Column::Left, &MetricKind::ALL), vec![MetricKind::Disk]);
+        assert_eq!(partial.panels.len(), 4);
+    }
+
+    #[test]
+    fn test_insert_before_uses_card_centres() {
+        let cards = [(MetricKind::Memory, 100.0), (MetricKind::Disk, 300.0)];
+        assert_eq!(insert_before(&cards, 50.0), Some(MetricKind::Memory));
+        assert_eq!(insert_before(&cards, 200.0), Some(MetricKind::Disk));
+        assert_eq!(insert_before(&cards, 400.0), None);
+    }
+
+    #[test]
+    fn test_open_fraction_eases_to_exact_endpoints() {
+        assert_eq!(ease_in_out(0.0), 0.0);
+        assert_eq!(ease_in_out(1.0), 1.0);
+        assert_eq!(ease_in_out(0.5), 0.5);
+        assert_eq!((ease_in_out(-1.0), ease_in_out(2.0), ease_in_out(f32::NAN)), (0.0, 1.0, 0.0));
+
+        let mut fold = PanelFold::new(true);
+        assert_eq!(fold.open_fraction(), 1.0);
+        fold.set_open(false);
+        let mut last = fold.open_fraction();
+        // 60 fps frames don't divide the duration evenly; it still lands on exactly 0
+        for _ in 0..20 {
+            fold.advance(1.0 / 60.0, 0.25);
+            assert!(fold.open_fraction() <= last);
+            last = fold.open_fraction();
+        }
+        assert_eq!(fold.open_fraction(), 0.0);
+        assert!(!fold.animating());
+
+        fold.set_open(true);
+        fold.advance(0.1, 0.25);
+        assert!(fold.animating() && fold.open_fraction() > 0.0 && fold.open_fraction() < 1.0);
+        fold.advance(1.0, 0.25);
+        assert_eq!(fold.open_fraction(), 1.0);
+
+        // No animation snaps straight to the end
+        fold.set_open(false);
+        fold.advance(1.0 / 60.0, 0.0);
+        assert_eq!(fold.open_fraction(), 0.0);
+
+        let mut layout = PanelLayout::default();
+        layout.set_collapsed(MetricKind::Disk, true);
+        layout.set_collapsed(MetricKind::Disk, true);
+        assert_eq!(layout.collapsed, vec![MetricKind::Disk]);
+        layout.set_collapsed(MetricKind::Disk, false);
+        assert!(!layout.is_collapsed(MetricKind::Disk));
+    }
+}
@@ -9,6 +9,32 @@ pub mod particles;
 pub mod message_system;
 pub mod network_stats;
 pub mod personality_modal;
+pub mod config;
+pub mod diagnostics;
+pub mod audio_processing;
+pub mod warnings;
+pub mod history;
+pub mod notifications;
+pub mod status_line;
+pub mod layout;
+pub mod selftest;
+pub mod remote;
+pub mod disk_latency;
+pub mod virtualization;
+pub mod spoken_numbers;
+pub mod mascot;
+pub mod personality_pack;
+pub mod graph;
+pub mod paths;
+pub mod gauge;
+pub mod sound_cues;
+pub mod config_bundle;
+pub mod thermal;
+pub mod event_log;
+pub mod screensaver;
+pub mod validated_input;
+pub mod spotlight;
+pub mod report;
 
 // Re-export public types
 pub use message_system::{MessagePart, SystemData, CacheKey, PersonalitySettings, generate_message};
@@ -19,6 +45,9 @@ pub use tts::TTSManager;
 pub use theme::CyberTheme;
 pub use personality_modal::PersonalityModal;
 pub use particles::{Particle, ParticleSystem};
+pub use config::AppConfig;
+pub use diagnostics::BootDiagnostics;
+pub use history::MetricHistory;
 
 // Constants
 pub const MIN_MEMORY_GB: f64 = 4.0;
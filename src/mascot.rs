@@ -0,0 +1,156 @@
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+
+/// How long the mascot's one-liner stays up after its mood changes
+pub const QUIP_DURATION: Duration = Duration::from_secs(6);
+
+/// Scores at or above this are optimal
+const OPTIMAL_SCORE: f32 = 40.0;
+/// Scores at or above this (and below optimal) are stressed; anything lower is critical
+const STRESSED_SCORE: f32 = 15.0;
+
+/// 0-100 overall health, 100 being an idle machine: whatever resource is busiest sets the score
+pub fn health_score(cpu: f32, memory: f32, disk: f32) -> f32 {
+    let worst = [cpu, memory, disk]
+        .into_iter()
+        .filter(|value| value.is_finite())
+        .fold(0.0f32, f32::max);
+    (100.0 - worst).clamp(0.0, 100.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mood {
+    Optimal,
+    Stressed,
+    Critical,
+}
+
+impl Mood {
+    pub fn from_score(score: f32) -> Self {
+        if score >= OPTIMAL_SCORE {
+            Mood::Optimal
+        } else if score >= STRESSED_SCORE {
+            Mood::Stressed
+        } else {
+            Mood::Critical
+        }
+    }
+
+    /// What the mascot says when it enters this mood
+    pub fn quip(&self) -> &'static str {
+        match self {
+            Mood::Optimal => "All systems smooth. Stay sharp.",
+            Mood::Stressed => "Feeling the pressure...",
+            Mood::Critical => "Too hot to handle!",
+        }
+    }
+}
+
+/// How the mascot's face is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MascotStyle {
+    #[default]
+    Emoji,
+    Ascii,
+}
+
+impl MascotStyle {
+    pub const ALL: [MascotStyle; 2] = [MascotStyle::Emoji, MascotStyle::Ascii];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MascotStyle::Emoji => "Emoji",
+            MascotStyle::Ascii => "ASCII",
+        }
+    }
+
+    pub fn face(&self, mood: Mood) -> &'static str {
+        match (self, mood) {
+            (MascotStyle::Emoji, Mood::Optimal) => "😎",
+            (MascotStyle::Emoji, Mood::Stressed) => "😰",
+            (MascotStyle::Emoji, Mood::Critical) => "🥵",
+            (MascotStyle::Ascii, Mood::Optimal) => "(⌐■_■)",
+            (MascotStyle::Ascii, Mood::Stressed) => "(°_°;)",
+            (MascotStyle::Ascii, Mood::Critical) => "(×_×)",
+        }
+    }
+}
+
+/// Tracks the mascot's mood and when it last changed
+#[derive(Debug, Default)]
+pub struct Mascot {
+    mood: Option<Mood>,
+    changed_at: Option<Instant>,
+}
+
+impl Mascot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a new health score; returns true if the mood changed
+    pub fn observe(&mut self, score: f32, now: Instant) -> bool {
+        let mood = Mood::from_score(score);
+        if self.mood == Some(mood) {
+            return false;
+        }
+        self.mood = Some(mood);
+        self.changed_at = Some(now);
+        true
+    }
+
+    /// Current mood, None until the first score arrives
+    pub fn mood(&self) -> Option<Mood> {
+        self.mood
+    }
+
+    /// The one-liner for the latest mood change, while it is still fresh
+    pub fn quip(&self, now: Instant) -> Option<&'static str> {
+        let changed_at = self.changed_at?;
+        if now.saturating_duration_since(changed_at) > QUIP_DURATION {
+            return None;
+        }
+        self.mood.map(|mood| mood.quip())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_maps_to_expression() {
+        assert_eq!(health_score(10.0, 30.0, 50.0), 50.0);
+        assert_eq!(health_score(f32::NAN, 120.0, 0.0), 0.0);
+
+        for (score, mood, face) in [
+            (100.0, Mood::Optimal, "😎"),
+            (40.0, Mood::Optimal, "😎"),
+            (39.9, Mood::Stressed, "😰"),
+            (15.0, Mood::Stressed, "😰"),
+            (14.9, Mood::Critical, "🥵"),
+            (0.0, Mood::Critical, "🥵"),
+        ] {
+            assert_eq!(Mood::from_score(score), mood, "score {}", score);
+            assert_eq!(MascotStyle::Emoji.face(mood), face);
+        }
+        assert_eq!(MascotStyle::Ascii.face(Mood::Critical), "(×_×)");
+    }
+
+    #[test]
+    fn test_quip_follows_transitions() {
+        let start = Instant::now();
+        let mut mascot = Mascot::new();
+        assert_eq!(mascot.quip(start), None);
+
+        assert!(mascot.observe(90.0, start));
+        assert!(!mascot.observe(80.0, start + Duration::from_secs(1)), "same mood is not a transition");
+        assert_eq!(mascot.quip(start + Duration::from_secs(2)), Some(Mood::Optimal.quip()));
+        assert_eq!(mascot.quip(start + QUIP_DURATION + Duration::from_secs(1)), None);
+
+        let later = start + Duration::from_secs(30);
+        assert!(mascot.observe(5.0, later));
+        assert_eq!(mascot.mood(), Some(Mood::Critical));
+        assert_eq!(mascot.quip(later), Some("Too hot to handle!"));
+    }
+}
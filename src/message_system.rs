@@ -1,5 +1,8 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use serde::{Serialize, Deserialize};
+use crate::ai_personality::AIPersonality;
+use crate::system_monitor::{finite_percent, percent_of, MetricChange};
 
 #[derive(Debug, Clone)]
 pub enum MessagePart {
@@ -20,6 +23,15 @@ impl MessagePart {
     pub fn static_text(text: String) -> Self {
         MessagePart::Static(text)
     }
+
+    /// The same kind of part with different text
+    pub fn with_text(&self, text: String) -> Self {
+        match self {
+            MessagePart::Static(_) => MessagePart::Static(text),
+            MessagePart::Dynamic(_) => MessagePart::Dynamic(text),
+            MessagePart::Full(_) => MessagePart::Full(text),
+        }
+    }
 }
 
 pub struct MessageSystem {
@@ -42,11 +54,31 @@ impl MessageSystem {
     }
 }
 
+/// How urgent a spoken message is; each level can be given its own voice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 3] = [Severity::Info, Severity::Warning, Severity::Critical];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Warning => "Warning",
+            Severity::Critical => "Critical",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum CacheKey {
-    Static(String, PersonalitySettings),  // Phrase + personality
-    Dynamic(String),                      // Descriptive text
-    Full(String, String),                // Event type + discretized data
+    Static(String, String, u32),                    // Phrase + voice + speech rate in hundredths
+    Dynamic(String, String),                        // Descriptive text + voice
+    Full(String, String, String),                   // Event type + discretized data + voice
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -84,6 +116,35 @@ impl Default for PersonalitySettings {
     }
 }
 
+/// PersonalitySettings stores each 0.0-1.0 personality trait as a whole number out of this
+pub const TRAIT_SCALE: i32 = 100;
+
+fn trait_to_scale(value: f32) -> i32 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value.clamp(0.0, 1.0) * TRAIT_SCALE as f32).round() as i32
+}
+
+impl PersonalitySettings {
+    pub fn from_personality(personality: &AIPersonality) -> Self {
+        Self {
+            voice_type: personality.voice_type.clone(),
+            volume: personality.volume,
+            speech_rate: personality.speech_rate,
+            drunk_level: trait_to_scale(personality.drunk_level),
+            sass_level: trait_to_scale(personality.sass_level),
+            tech_expertise: trait_to_scale(personality.tech_expertise),
+            grand_pappi_refs: trait_to_scale(personality.grand_pappi_references),
+            enthusiasm: trait_to_scale(personality.enthusiasm),
+            anxiety_level: trait_to_scale(personality.anxiety_level),
+            catchphrases: personality.catchphrases.clone(),
+            audio_enabled: personality.audio_enabled,
+            is_1337_mode: personality.is_1337_mode,
+        }
+    }
+}
+
 impl PartialEq for PersonalitySettings {
     fn eq(&self, other: &Self) -> bool {
         self.voice_type == other.voice_type &&
@@ -120,6 +181,7 @@ impl std::hash::Hash for PersonalitySettings {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct SystemData {
     pub cpu_usage: Vec<(String, f32)>,
     pub memory_total: u64,
@@ -132,72 +194,512 @@ pub struct SystemData {
     pub network_tx: u64,
 }
 
+/// Upper bounds of the first three qualitative bands for each metric, in percent.
+///
+/// A value at or below the first bound gets the calmest phrase; anything
+/// above the last bound gets the most urgent one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualitativeThresholds {
+    pub cpu: [f32; 3],
+    pub memory: [f32; 3],
+    pub disk: [f32; 3],
+}
+
+impl Default for QualitativeThresholds {
+    fn default() -> Self {
+        Self {
+            cpu: [20.0, 50.0, 80.0],
+            memory: [30.0, 60.0, 80.0],
+            disk: [50.0, 75.0, 90.0],
+        }
+    }
+}
+
+impl QualitativeThresholds {
+    /// Band bounds for `metric` ("cpu", "memory" or "disk"), sorted ascending
+    pub fn bounds(&self, metric: &str) -> Option<[f32; 3]> {
+        let mut bounds = match metric {
+            "cpu" => self.cpu,
+            "memory" => self.memory,
+            "disk" => self.disk,
+            _ => return None,
+        };
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        Some(bounds)
+    }
+}
+
 pub fn get_qualitative_description(metric: &str, value: f32) -> String {
-    match metric {
-        "cpu" => match value {
-            v if v <= 20.0 => "running cool",
-            v if v <= 50.0 => "running steady",
-            v if v <= 80.0 => "working hard",
-            _ => "running hot",
-        },
-        "memory" => match value {
-            v if v <= 30.0 => "plenty of space",
-            v if v <= 60.0 => "comfortable",
-            v if v <= 80.0 => "getting tight",
-            _ => "very tight",
-        },
-        "disk" => match value {
-            v if v <= 50.0 => "lots of room",
-            v if v <= 75.0 => "decent space",
-            v if v <= 90.0 => "filling up",
-            _ => "nearly full",
-        },
-        _ => "unknown",
-    }.to_string()
+    get_qualitative_description_with(metric, value, &QualitativeThresholds::default())
 }
 
+/// Like get_qualitative_description, with user-defined band boundaries
+pub fn get_qualitative_description_with(metric: &str, value: f32, thresholds: &QualitativeThresholds) -> String {
+    let phrases = match metric {
+        "cpu" => ["running cool", "running steady", "working hard", "running hot"],
+        "memory" => ["plenty of space", "comfortable", "getting tight", "very tight"],
+        "disk" => ["lots of room", "decent space", "filling up", "nearly full"],
+        _ => return "unknown".to_string(),
+    };
+    let bounds = match thresholds.bounds(metric) {
+        Some(bounds) => bounds,
+        None => return "unknown".to_string(),
+    };
+    let value = finite_percent(value);
+    let band = bounds.iter().position(|bound| value <= *bound).unwrap_or(bounds.len());
+    phrases[band].to_string()
+}
+
+/// Default bucket size used when discretizing numbers
+pub const DEFAULT_DISCRETIZE_BUCKET: f32 = 5.0;
+
 pub fn discretize(value: f32) -> String {
-    let rounded = (value / 5.0).round() * 5.0;
-    format!("{:.0}", rounded)
+    discretize_with(value, DEFAULT_DISCRETIZE_BUCKET)
+}
+
+/// Rounds `value` to the nearest multiple of `bucket`
+pub fn discretize_with(value: f32, bucket: f32) -> String {
+    let rounded = (value / bucket).round() * bucket;
+    if bucket.fract() == 0.0 {
+        format!("{:.0}", rounded)
+    } else {
+        format!("{:.1}", rounded)
+    }
+}
+
+/// Replaces every number in `text` with its discretized value; a bucket of 0 keeps the text as is
+pub fn discretize_numbers(text: &str, bucket: f32) -> String {
+    if bucket <= 0.0 {
+        return text.to_string();
+    }
+
+    fn flush(result: &mut String, number: &mut String, bucket: f32) {
+        if number.is_empty() {
+            return;
+        }
+        let trailing_dot = number.ends_with('.');
+        let digits = number.trim_end_matches('.');
+        match digits.parse::<f32>() {
+            Ok(value) => result.push_str(&discretize_with(value, bucket)),
+            Err(_) => result.push_str(digits),
+        }
+        if trailing_dot {
+            result.push('.');
+        }
+        number.clear();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() || (c == '.' && !number.is_empty() && !number.contains('.')) {
+            number.push(c);
+            continue;
+        }
+        flush(&mut result, &mut number, bucket);
+        result.push(c);
+    }
+    flush(&mut result, &mut number, bucket);
+    result
+}
+
+/// A metric that can be spoken in status messages and shown as a panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MetricKind {
+    Cpu,
+    Memory,
+    Disk,
+    Network,
+}
+
+impl MetricKind {
+    /// Every metric in the default order
+    pub const ALL: [MetricKind; 4] = [MetricKind::Cpu, MetricKind::Memory, MetricKind::Disk, MetricKind::Network];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MetricKind::Cpu => "CPU",
+            MetricKind::Memory => "Memory",
+            MetricKind::Disk => "Disk",
+            MetricKind::Network => "Network",
+        }
+    }
 }
 
 pub fn generate_message(data: &SystemData) -> Vec<MessagePart> {
+    generate_message_for(data, &MetricKind::ALL)
+}
+
+/// Status message covering only `metrics`, in the given order
+pub fn generate_message_for(data: &SystemData, metrics: &[MetricKind]) -> Vec<MessagePart> {
     let mut parts = Vec::new();
 
-    // CPU Usage
-    for (_name, usage) in &data.cpu_usage {
-        parts.push(MessagePart::Static(format!("CPU Usage: {:.1}%", usage)));
-    }
-
-    // Memory Usage
-    let memory_text = format!(
-        "Memory: {:.1}GB/{:.1}GB ({:.1}%)",
-        data.memory_used as f64 / 1_073_741_824.0,
-        data.memory_total as f64 / 1_073_741_824.0,
-        data.memory_usage,
-    );
-    parts.push(MessagePart::Static(memory_text));
-
-    // Disk Usage
-    let disk_text = format!(
-        "Disk: {:.1}GB/{:.1}GB ({:.1}%)",
-        data.disk_available as f64 / 1_073_741_824.0,
-        data.disk_total as f64 / 1_073_741_824.0,
-        data.disk_usage,
-    );
-    parts.push(MessagePart::Static(disk_text));
-
-    // Network Usage
-    let network_text = format!(
-        "Network: {:.1}MB/s Up, {:.1}MB/s Down",
-        data.network_tx as f64 / 1_048_576.0,
-        data.network_rx as f64 / 1_048_576.0,
-    );
-    parts.push(MessagePart::Static(network_text));
+    for metric in metrics {
+        match metric {
+            MetricKind::Cpu => {
+                for (_name, usage) in &data.cpu_usage {
+                    parts.push(MessagePart::Static(format!("CPU Usage: {:.1}%", finite_percent(*usage))));
+                }
+            }
+            MetricKind::Memory => {
+                let memory_text = format!(
+                    "Memory: {:.1}GB/{:.1}GB ({:.1}%)",
+                    data.memory_used as f64 / 1_073_741_824.0,
+                    data.memory_total as f64 / 1_073_741_824.0,
+                    finite_percent(data.memory_usage),
+                );
+                parts.push(MessagePart::Static(memory_text));
+            }
+            MetricKind::Disk => {
+                let disk_text = format!(
+                    "Disk: {:.1}GB/{:.1}GB ({:.1}%)",
+                    data.disk_available as f64 / 1_073_741_824.0,
+                    data.disk_total as f64 / 1_073_741_824.0,
+                    finite_percent(data.disk_usage),
+                );
+                parts.push(MessagePart::Static(disk_text));
+            }
+            MetricKind::Network => {
+                let network_text = format!(
+                    "Network: {:.1}MB/s Up, {:.1}MB/s Down",
+                    data.network_tx as f64 / 1_048_576.0,
+                    data.network_rx as f64 / 1_048_576.0,
+                );
+                parts.push(MessagePart::Static(network_text));
+            }
+        }
+    }
 
     parts
 }
 
+/// Whether status messages speak exact numbers or qualitative descriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AnnounceStyle {
+    /// "CPU Usage: 73.0%"
+    #[default]
+    Quantitative,
+    /// "CPU: working hard"
+    Qualitative,
+}
+
+impl AnnounceStyle {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnnounceStyle::Quantitative => "Exact values",
+            AnnounceStyle::Qualitative => "Qualitative",
+        }
+    }
+}
+
+/// Describes combined network traffic in bytes per second
+fn network_description(bytes_per_sec: u64) -> &'static str {
+    let mbps = bytes_per_sec as f64 / 1_048_576.0;
+    if mbps < 0.1 {
+        "quiet"
+    } else if mbps < 10.0 {
+        "active"
+    } else {
+        "busy"
+    }
+}
+
+/// Status message covering `metrics` in the given announce style
+pub fn generate_message_styled(data: &SystemData, metrics: &[MetricKind], style: AnnounceStyle, thresholds: &QualitativeThresholds) -> Vec<MessagePart> {
+    if style == AnnounceStyle::Quantitative {
+        return generate_message_for(data, metrics);
+    }
+
+    metrics.iter()
+        .filter_map(|metric| {
+            let description = qualitative_bucket(data, *metric, thresholds)?;
+            Some(MessagePart::Static(format!("{}: {}", metric.label(), description)))
+        })
+        .collect()
+}
+
+/// Mean usage across CPUs, None when there are none
+fn average_cpu(data: &SystemData) -> Option<f32> {
+    if data.cpu_usage.is_empty() {
+        return None;
+    }
+    Some(data.cpu_usage.iter().map(|(_, usage)| finite_percent(*usage)).sum::<f32>() / data.cpu_usage.len() as f32)
+}
+
+/// Usage of the busiest CPU, 0 when there are none
+fn peak_cpu(data: &SystemData) -> f32 {
+    data.cpu_usage.iter().map(|(_, usage)| finite_percent(*usage)).fold(0.0f32, f32::max)
+}
+
+/// The qualitative description `metric` currently falls in, e.g. "working hard"; None without data
+pub fn qualitative_bucket(data: &SystemData, metric: MetricKind, thresholds: &QualitativeThresholds) -> Option<String> {
+    Some(match metric {
+        MetricKind::Cpu => {
+            let average = average_cpu(data)?;
+            get_qualitative_description_with("cpu", average, thresholds)
+        }
+        MetricKind::Memory => get_qualitative_description_with("memory", data.memory_usage, thresholds),
+        MetricKind::Disk => get_qualitative_description_with("disk", data.disk_usage, thresholds),
+        MetricKind::Network => network_description(data.network_rx + data.network_tx).to_string(),
+    })
+}
+
+/// Remembers the qualitative bucket last announced for each metric, so status
+/// updates can narrate transitions instead of repeating unchanged states
+#[derive(Debug, Default)]
+pub struct BucketTracker {
+    announced: HashMap<MetricKind, String>,
+}
+
+impl BucketTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Metrics whose bucket differs from the one last announced, recording the new buckets as announced
+    pub fn take_changed(&mut self, data: &SystemData, metrics: &[MetricKind], thresholds: &QualitativeThresholds) -> Vec<MetricKind> {
+        metrics.iter()
+            .copied()
+            .filter(|metric| {
+                let bucket = match qualitative_bucket(data, *metric, thresholds) {
+                    Some(bucket) => bucket,
+                    None => return false,
+                };
+                self.announced.insert(*metric, bucket.clone()).as_ref() != Some(&bucket)
+            })
+            .collect()
+    }
+}
+
+/// The elaborated line for the focus metric, e.g. "Focusing on memory: getting tight at 78%, 12.5 of 16.0 gigabytes in use"
+fn focus_detail(data: &SystemData, metric: MetricKind, thresholds: &QualitativeThresholds) -> Option<String> {
+    let detail = match metric {
+        MetricKind::Cpu => {
+            let average = average_cpu(data)?;
+            let peak = peak_cpu(data);
+            format!(
+                "{}, averaging {:.0}% with the busiest core at {:.0}%",
+                get_qualitative_description_with("cpu", average, thresholds), average, peak,
+            )
+        }
+        MetricKind::Memory => format!(
+            "{} at {:.0}%, {:.1} of {:.1} gigabytes in use",
+            get_qualitative_description_with("memory", data.memory_usage, thresholds),
+            finite_percent(data.memory_usage),
+            data.memory_used as f64 / 1_073_741_824.0,
+            data.memory_total as f64 / 1_073_741_824.0,
+        ),
+        MetricKind::Disk => format!(
+            "{} at {:.0}%, {:.1} of {:.1} gigabytes free",
+            get_qualitative_description_with("disk", data.disk_usage, thresholds),
+            finite_percent(data.disk_usage),
+            data.disk_available as f64 / 1_073_741_824.0,
+            data.disk_total as f64 / 1_073_741_824.0,
+        ),
+        MetricKind::Network => format!(
+            "{}, {:.1} megabytes per second down and {:.1} up",
+            network_description(data.network_rx + data.network_tx),
+            data.network_rx as f64 / 1_048_576.0,
+            data.network_tx as f64 / 1_048_576.0,
+        ),
+    };
+    Some(format!("Focusing on {}: {}", lowercase_first_word(metric.label()), detail))
+}
+
+/// Status message that leads with `focus` in detail and abbreviates the other metrics.
+///
+/// Without a focus, or when the focus metric isn't announced, this is the plain styled message.
+pub fn generate_message_focused(
+    data: &SystemData,
+    metrics: &[MetricKind],
+    style: AnnounceStyle,
+    thresholds: &QualitativeThresholds,
+    focus: Option<MetricKind>,
+) -> Vec<MessagePart> {
+    let focus = match focus {
+        Some(focus) if metrics.contains(&focus) => focus,
+        _ => return generate_message_styled(data, metrics, style, thresholds),
+    };
+
+    let others: Vec<MetricKind> = metrics.iter().copied().filter(|metric| *metric != focus).collect();
+    let mut parts: Vec<MessagePart> = focus_detail(data, focus, thresholds)
+        .map(MessagePart::Dynamic)
+        .into_iter()
+        .collect();
+    parts.extend(generate_message_styled(data, &others, AnnounceStyle::Qualitative, thresholds));
+    parts
+}
+
+/// Custom spoken phrasing for one metric's warnings at one severity
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WarningTemplate {
+    pub metric: MetricKind,
+    pub severity: Severity,
+    pub text: String,
+}
+
+/// User-written warning phrases with `{name}` placeholders, e.g. "Whoa, CPU's melting at {cpu_pct}%!".
+///
+/// Metrics and severities without a template keep the generic status message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct WarningTemplates {
+    pub templates: Vec<WarningTemplate>,
+}
+
+impl WarningTemplates {
+    /// The template for `metric` at `severity`, if one is set
+    pub fn get(&self, metric: MetricKind, severity: Severity) -> Option<&str> {
+        self.templates.iter()
+            .find(|template| template.metric == metric && template.severity == severity)
+            .map(|template| template.text.as_str())
+            .filter(|text| !text.trim().is_empty())
+    }
+
+    /// Sets the template for `metric` at `severity`; blank text removes it
+    pub fn set(&mut self, metric: MetricKind, severity: Severity, text: String) {
+        self.templates.retain(|template| template.metric != metric || template.severity != severity);
+        if !text.trim().is_empty() {
+            self.templates.push(WarningTemplate { metric, severity, text });
+        }
+    }
+
+    /// The warning to speak: the rendered template if one is set, otherwise `fallback`
+    pub fn render_or(&self, metric: MetricKind, severity: Severity, values: &BTreeMap<&str, String>, fallback: Vec<MessagePart>) -> Vec<MessagePart> {
+        match self.get(metric, severity) {
+            Some(template) => vec![MessagePart::Dynamic(fill_placeholders(template, values, PlaceholderStyle::Braces))],
+            None => fallback,
+        }
+    }
+}
+
+/// How the assistant addresses the user until they pick their own callsign
+pub const DEFAULT_CALLSIGN: &str = "Captain";
+
+/// Placeholders available to warning templates
+pub const WARNING_PLACEHOLDERS: [&str; 8] = ["callsign", "cpu_pct", "mem_pct", "mem_used_gb", "mem_total_gb", "disk_pct", "net_rx_mbps", "net_tx_mbps"];
+
+/// Values for the warning template placeholders, rounded for speech
+pub fn warning_values(data: &SystemData, callsign: &str) -> BTreeMap<&'static str, String> {
+    let mut values = BTreeMap::new();
+    values.insert("callsign", callsign.to_string());
+    values.insert("cpu_pct", format!("{:.0}", peak_cpu(data)));
+    values.insert("mem_pct", format!("{:.0}", percent_of(data.memory_used, data.memory_total)));
+    values.insert("mem_used_gb", format!("{:.1}", data.memory_used as f64 / 1_073_741_824.0));
+    values.insert("mem_total_gb", format!("{:.1}", data.memory_total as f64 / 1_073_741_824.0));
+    values.insert("disk_pct", format!("{:.0}", finite_percent(data.disk_usage)));
+    values.insert("net_rx_mbps", format!("{:.1}", data.network_rx as f64 / 1_048_576.0));
+    values.insert("net_tx_mbps", format!("{:.1}", data.network_tx as f64 / 1_048_576.0));
+    values
+}
+
+/// Spoken once at startup; takes the warning placeholders plus {cores} and {disk_free_pct}
+pub const BOOT_SUMMARY_TEMPLATE: &str = "All systems nominal: {cores} cores, {mem_total_gb} gigs, {disk_free_pct}% disk free, {callsign}.";
+
+/// Whether `data` holds real readings, not the zeros sysinfo gives before its second CPU refresh
+pub fn is_complete_sample(data: &SystemData) -> bool {
+    data.memory_total > 0 && data.cpu_usage.iter().any(|(_, usage)| usage.is_finite() && *usage > 0.0)
+}
+
+/// The one-time systems check, filled in from `data`
+pub fn boot_summary(data: &SystemData, callsign: &str) -> Vec<MessagePart> {
+    let mut values = warning_values(data, callsign);
+    values.insert("cores", data.cpu_usage.len().to_string());
+    values.insert("disk_free_pct", format!("{:.0}", 100.0 - finite_percent(data.disk_usage)));
+    vec![MessagePart::Dynamic(fill_placeholders(BOOT_SUMMARY_TEMPLATE, &values, PlaceholderStyle::Braces))]
+}
+
+/// Hands out the boot summary once, on the first complete sample after startup
+#[derive(Debug, Clone, Default)]
+pub struct BootAnnouncement {
+    done: bool,
+}
+
+impl BootAnnouncement {
+    pub fn new(enabled: bool) -> Self {
+        Self { done: !enabled }
+    }
+
+    pub fn take(&mut self, data: &SystemData, callsign: &str) -> Option<Vec<MessagePart>> {
+        if self.done || !is_complete_sample(data) {
+            return None;
+        }
+        self.done = true;
+        Some(boot_summary(data, callsign))
+    }
+}
+
+/// A remark on a sharp swing between readings, for the personality to flavor before it is spoken
+pub fn change_remark(change: &MetricChange) -> MessagePart {
+    let text = match (change.metric, change.delta > 0.0) {
+        (MetricKind::Cpu, true) => "CPU just shot up, something's working hard!",
+        (MetricKind::Cpu, false) => "CPU just dropped off, whatever it was is done.",
+        (MetricKind::Memory, true) => "Memory just jumped, something's hungry.",
+        (MetricKind::Memory, false) => "Memory just freed up, someone cleaned house.",
+        (MetricKind::Disk, true) => "The disk just filled up fast.",
+        (MetricKind::Disk, false) => "Disk space just opened up.",
+        (MetricKind::Network, true) => "Network traffic just spiked.",
+        (MetricKind::Network, false) => "Network traffic just went quiet.",
+    };
+    MessagePart::Static(text.to_string())
+}
+
+/// Fills in `{callsign}` in a fixed phrase such as a greeting
+pub fn fill_callsign(template: &str, callsign: &str) -> String {
+    fill_placeholders(template, &BTreeMap::from([("callsign", callsign.to_string())]), PlaceholderStyle::Braces)
+}
+
+/// How placeholders are written in a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// `{name}`, as in spoken phrases and warning templates
+    Braces,
+    /// `%name%`, i3blocks/conky style, with `%%` for a literal percent sign
+    Percent,
+}
+
+impl PlaceholderStyle {
+    fn delimiters(self) -> (char, char) {
+        match self {
+            PlaceholderStyle::Braces => ('{', '}'),
+            PlaceholderStyle::Percent => ('%', '%'),
+        }
+    }
+}
+
+/// Replaces placeholders with their values, leaving unknown ones and stray delimiters untouched
+pub fn fill_placeholders(template: &str, values: &BTreeMap<&str, String>, style: PlaceholderStyle) -> String {
+    let (open, close) = style.delimiters();
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(open) {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + open.len_utf8()..];
+        let value = after.find(close).and_then(|end| {
+            let name = &after[..end];
+            let value = match style {
+                PlaceholderStyle::Percent if name.is_empty() => Some("%"),
+                _ => values.get(name).map(String::as_str),
+            };
+            value.map(|value| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                output.push_str(value);
+                rest = &after[end + close.len_utf8()..];
+            }
+            None => {
+                output.push(open);
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
 pub fn generate_status_message(cpu: f32, memory: f32, disk: f32, network: f32) -> Vec<MessagePart> {
     vec![
         MessagePart::Static(format!("CPU Usage: {:.1}%", cpu)),
@@ -207,6 +709,73 @@ pub fn generate_status_message(cpu: f32, memory: f32, disk: f32, network: f32) -
     ]
 }
 
+/// Lowercases the first letter unless the first word is an acronym like "CPU"
+fn lowercase_first_word(text: &str) -> String {
+    let first_word = text.split_whitespace().next().unwrap_or("");
+    let is_acronym = first_word.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && first_word.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase());
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if !is_acronym => first.to_lowercase().chain(chars).collect(),
+        _ => text.to_string(),
+    }
+}
+
+/// Joins several queued messages into one naturally paced sentence.
+///
+/// One message is returned as is. Later messages are introduced with "but" when
+/// they turn from an alert to good news or back, and the last one otherwise with
+/// "and"; three or more are separated by commas. Connectors are parts of their
+/// own and static parts are kept verbatim, so they still hit the audio cache.
+pub fn combine_messages(messages: Vec<(Severity, Vec<MessagePart>)>) -> Vec<MessagePart> {
+    let messages: Vec<(Severity, Vec<MessagePart>)> = messages.into_iter()
+        .map(|(severity, parts)| (severity, parts.into_iter().filter(|part| !part.text().trim().is_empty()).collect::<Vec<_>>()))
+        .filter(|(_, parts)| !parts.is_empty())
+        .collect();
+    if messages.len() <= 1 {
+        return messages.into_iter().flat_map(|(_, parts)| parts).collect();
+    }
+
+    let is_alert = |severity: Severity| severity > Severity::Info;
+    let count = messages.len();
+    let mut previous = None;
+    let mut combined = Vec::new();
+    for (index, (severity, mut parts)) in messages.into_iter().enumerate() {
+        if let Some(previous) = previous {
+            let connector = if is_alert(previous) != is_alert(severity) {
+                Some("but")
+            } else if index == count - 1 {
+                Some("and")
+            } else {
+                None
+            };
+            combined.extend(connector.map(|connector| MessagePart::Static(connector.to_string())));
+
+            if !matches!(parts[0], MessagePart::Static(_)) {
+                parts[0] = parts[0].with_text(lowercase_first_word(parts[0].text().trim()));
+            }
+        }
+        previous = Some(severity);
+
+        let last_index = parts.len() - 1;
+        if !matches!(parts[last_index], MessagePart::Static(_)) {
+            let last = &parts[last_index];
+            let clause = last.text().trim().trim_end_matches(['.', '!', '?', ',', ';']);
+            let ending = if index == count - 1 {
+                "."
+            } else if count > 2 {
+                ","
+            } else {
+                ""
+            };
+            parts[last_index] = last.with_text(format!("{}{}", clause, ending));
+        }
+
+        combined.extend(parts);
+    }
+    combined
+}
+
 impl fmt::Display for MessagePart {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.text())
@@ -225,6 +794,34 @@ mod tests {
         assert_eq!(get_qualitative_description("cpu", 90.0), "running hot");
     }
 
+    #[test]
+    fn test_custom_qualitative_thresholds() {
+        let thresholds = QualitativeThresholds {
+            cpu: [30.0, 60.0, 90.0],
+            ..QualitativeThresholds::default()
+        };
+
+        // 55% is "working hard" by default but still "running steady" with the bar raised to 60%
+        assert_eq!(get_qualitative_description("cpu", 55.0), "working hard");
+        assert_eq!(get_qualitative_description_with("cpu", 55.0, &thresholds), "running steady");
+        assert_eq!(get_qualitative_description_with("cpu", 60.0, &thresholds), "running steady");
+        assert_eq!(get_qualitative_description_with("cpu", 60.1, &thresholds), "working hard");
+        assert_eq!(get_qualitative_description_with("cpu", 95.0, &thresholds), "running hot");
+
+        // Other metrics keep their defaults
+        assert_eq!(get_qualitative_description_with("memory", 70.0, &thresholds), "getting tight");
+        assert_eq!(get_qualitative_description_with("gpu", 70.0, &thresholds), "unknown");
+    }
+
+    #[test]
+    fn test_unsorted_thresholds_are_sorted() {
+        let thresholds = QualitativeThresholds {
+            disk: [90.0, 50.0, 75.0],
+            ..QualitativeThresholds::default()
+        };
+        assert_eq!(get_qualitative_description_with("disk", 60.0, &thresholds), "decent space");
+    }
+
     #[test]
     fn test_discretize() {
         assert_eq!(discretize(42.7), "45");
@@ -232,6 +829,15 @@ mod tests {
         assert_eq!(discretize(91.6), "90");
     }
 
+    #[test]
+    fn test_discretize_numbers() {
+        assert_eq!(discretize_numbers("CPU at 47.3%", 5.0), "CPU at 45%");
+        assert_eq!(discretize_numbers("12 megabytes per second.", 5.0), "10 megabytes per second.");
+        assert_eq!(discretize_numbers("0.26 GB", 0.5), "0.5 GB");
+        assert_eq!(discretize_numbers("CPU at 47.3%", 0.0), "CPU at 47.3%");
+        assert_eq!(discretize_numbers("no numbers here", 5.0), "no numbers here");
+    }
+
     #[test]
     fn test_message_generation() {
         let data = SystemData {
@@ -276,6 +882,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_follows_metric_order() {
+        let data = SystemData {
+            cpu_usage: vec![("CPU0".to_string(), 45.0)],
+            memory_total: 16_000_000_000,
+            memory_used: 8_000_000_000,
+            memory_usage: 50.0,
+            disk_total: 500_000_000_000,
+            disk_available: 250_000_000_000,
+            disk_usage: 50.0,
+            network_rx: 1_000_000,
+            network_tx: 500_000,
+        };
+
+        let parts = generate_message_for(&data, &[MetricKind::Network, MetricKind::Cpu]);
+
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].text().starts_with("Network:"));
+        assert!(parts[1].text().starts_with("CPU Usage:"));
+        assert!(parts.iter().all(|part| !part.text().starts_with("Memory") && !part.text().starts_with("Disk")));
+    }
+
+    fn combined_text(messages: Vec<(Severity, Vec<MessagePart>)>) -> String {
+        combine_messages(messages).iter().map(|part| part.text()).collect::<Vec<_>>().join(" ")
+    }
+
+    fn dynamic(severity: Severity, text: &str) -> (Severity, Vec<MessagePart>) {
+        (severity, vec![MessagePart::Dynamic(text.to_string())])
+    }
+
+    #[test]
+    fn test_combine_single_message() {
+        let combined = combined_text(vec![dynamic(Severity::Warning, "CPU is running hot!")]);
+        assert_eq!(combined, "CPU is running hot!");
+    }
+
+    #[test]
+    fn test_combine_two_messages() {
+        let combined = combined_text(vec![
+            dynamic(Severity::Warning, "CPU is running hot."),
+            dynamic(Severity::Critical, "Memory is getting tight!"),
+        ]);
+        assert_eq!(combined, "CPU is running hot and memory is getting tight.");
+
+        let combined = combined_text(vec![
+            dynamic(Severity::Warning, "CPU is running hot."),
+            dynamic(Severity::Info, "Disk has lots of room"),
+        ]);
+        assert_eq!(combined, "CPU is running hot but disk has lots of room.");
+    }
+
+    #[test]
+    fn test_combine_three_or_more_messages() {
+        let combined = combined_text(vec![
+            dynamic(Severity::Warning, "CPU is running hot"),
+            dynamic(Severity::Warning, "Memory is getting tight"),
+            dynamic(Severity::Warning, "Heads up, the network is busy."),
+            dynamic(Severity::Info, "Disk has lots of room"),
+        ]);
+        assert_eq!(
+            combined,
+            "CPU is running hot, memory is getting tight, heads up, the network is busy, but disk has lots of room."
+        );
+
+        let combined = combined_text(vec![
+            dynamic(Severity::Info, "CPU is running cool"),
+            dynamic(Severity::Info, "Memory is fine"),
+            dynamic(Severity::Info, "Disk has lots of room"),
+        ]);
+        assert_eq!(combined, "CPU is running cool, memory is fine, and disk has lots of room.");
+    }
+
+    #[test]
+    fn test_combine_keeps_static_parts_verbatim() {
+        let combined = combine_messages(vec![
+            (Severity::Warning, vec![
+                MessagePart::Static("This session has used".to_string()),
+                MessagePart::Dynamic("80 percent of your data cap".to_string()),
+            ]),
+            (Severity::Warning, vec![MessagePart::Static("CPU is running hot".to_string())]),
+        ]);
+        assert_eq!(combined.len(), 4);
+        assert!(matches!(&combined[0], MessagePart::Static(t) if t == "This session has used"));
+        assert!(matches!(&combined[1], MessagePart::Dynamic(t) if t == "80 percent of your data cap"));
+        assert!(matches!(&combined[2], MessagePart::Static(t) if t == "and"));
+        assert!(matches!(&combined[3], MessagePart::Static(t) if t == "CPU is running hot"));
+    }
+
     #[test]
     fn test_status_message_generation() {
         let data = SystemData {
@@ -319,4 +1013,244 @@ mod tests {
             panic!("Expected Static message part");
         }
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_cpu_warning_uses_template() {
+        let data = SystemData {
+            cpu_usage: vec![("CPU0".to_string(), 40.0), ("CPU1".to_string(), 96.4)],
+            memory_total: 16_000_000_000,
+            memory_used: 8_000_000_000,
+            memory_usage: 50.0,
+            disk_total: 500_000_000_000,
+            disk_available: 250_000_000_000,
+            disk_usage: 50.0,
+            network_rx: 1_000_000,
+            network_tx: 500_000,
+        };
+        let values = warning_values(&data, DEFAULT_CALLSIGN);
+        let fallback = || generate_message_for(&data, &[MetricKind::Cpu]);
+
+        let mut templates = WarningTemplates::default();
+        templates.set(MetricKind::Cpu, Severity::Warning, "Whoa, CPU's melting at {cpu_pct}%! {unknown}".to_string());
+
+        let parts = templates.render_or(MetricKind::Cpu, Severity::Warning, &values, fallback());
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].text(), "Whoa, CPU's melting at 96%! {unknown}");
+
+        // Other severities and metrics keep the generic message
+        let parts = templates.render_or(MetricKind::Cpu, Severity::Critical, &values, fallback());
+        assert_eq!(parts[0].text(), "CPU Usage: 40.0%");
+        assert_eq!(templates.get(MetricKind::Memory, Severity::Warning), None);
+
+        // Clearing the text removes the template
+        templates.set(MetricKind::Cpu, Severity::Warning, "  ".to_string());
+        assert!(templates.templates.is_empty());
+    }
+
+    #[test]
+    fn test_callsign_fills_every_placeholder() {
+        let data = SystemData {
+            cpu_usage: vec![("CPU0".to_string(), 96.0)],
+            memory_total: 16_000_000_000,
+            memory_used: 8_000_000_000,
+            memory_usage: 50.0,
+            disk_total: 500_000_000_000,
+            disk_available: 250_000_000_000,
+            disk_usage: 50.0,
+            network_rx: 0,
+            network_tx: 0,
+        };
+        let values = warning_values(&data, "Maverick");
+        assert!(WARNING_PLACEHOLDERS.iter().all(|name| values.contains_key(name)));
+
+        let mut templates = WarningTemplates::default();
+        templates.set(MetricKind::Cpu, Severity::Warning, "CPU's hot, {callsign}! {cpu_pct}%, {callsign}!".to_string());
+        let parts = templates.render_or(MetricKind::Cpu, Severity::Warning, &values, Vec::new());
+        assert_eq!(parts[0].text(), "CPU's hot, Maverick! 96%, Maverick!");
+
+        let summary = boot_summary(&data, "Maverick");
+        assert!(summary[0].text().ends_with("disk free, Maverick."), "{}", summary[0].text());
+        assert_eq!(fill_callsign("Welcome back, {callsign}. {other}", "Maverick"), "Welcome back, Maverick. {other}");
+    }
+
+    #[test]
+    fn test_announce_styles() {
+        let data = SystemData {
+            cpu_usage: vec![("CPU0".to_string(), 70.0), ("CPU1".to_string(), 76.0)],
+            memory_total: 16_000_000_000,
+            memory_used: 8_000_000_000,
+            memory_usage: 50.0,
+            disk_total: 500_000_000_000,
+            disk_available: 50_000_000_000,
+            disk_usage: 92.0,
+            network_rx: 0,
+            network_tx: 0,
+        };
+        let thresholds = QualitativeThresholds::default();
+        let text = |style| generate_message_styled(&data, &MetricKind::ALL, style, &thresholds)
+            .iter()
+            .map(|part| part.text().to_string())
+            .collect::<Vec<_>>();
+
+        let exact = text(AnnounceStyle::Quantitative);
+        assert_eq!(exact[0], "CPU Usage: 70.0%");
+        assert!(exact.iter().any(|line| line.contains("(50.0%)")));
+
+        let qualitative = text(AnnounceStyle::Qualitative);
+        assert_eq!(qualitative, vec![
+            "CPU: working hard".to_string(),
+            "Memory: comfortable".to_string(),
+            "Disk: nearly full".to_string(),
+            "Network: quiet".to_string(),
+        ]);
+        assert!(qualitative.iter().all(|line| !line.chars().any(|c| c.is_ascii_digit())));
+    }
+
+    #[test]
+    fn test_bucket_tracker_reports_only_transitions() {
+        let sample = |cpu: f32| SystemData {
+            cpu_usage: vec![("CPU0".to_string(), cpu)],
+            memory_total: 100,
+            memory_used: 50,
+            memory_usage: 50.0,
+            disk_total: 100,
+            disk_available: 50,
+            disk_usage: 50.0,
+            network_rx: 0,
+            network_tx: 0,
+        };
+        let thresholds = QualitativeThresholds::default();
+        let metrics = [MetricKind::Cpu, MetricKind::Memory];
+        let mut tracker = BucketTracker::new();
+
+        assert_eq!(tracker.take_changed(&sample(40.0), &metrics, &thresholds), metrics, "everything is new at first");
+        // 40% and 45% are both "running steady"
+        assert!(tracker.take_changed(&sample(40.0), &metrics, &thresholds).is_empty());
+        assert!(tracker.take_changed(&sample(45.0), &metrics, &thresholds).is_empty());
+
+        assert_eq!(tracker.take_changed(&sample(70.0), &metrics, &thresholds), [MetricKind::Cpu]);
+        assert!(tracker.take_changed(&sample(75.0), &metrics, &thresholds).is_empty());
+        assert_eq!(tracker.take_changed(&sample(10.0), &metrics, &thresholds), [MetricKind::Cpu]);
+    }
+
+    #[test]
+    fn test_focus_metric_leads_in_detail() {
+        let data = SystemData {
+            cpu_usage: vec![("CPU0".to_string(), 20.0), ("CPU1".to_string(), 30.0)],
+            memory_total: 16 * 1_073_741_824,
+            memory_used: 12 * 1_073_741_824 + 536_870_912,
+            memory_usage: 78.0,
+            disk_total: 500 * 1_073_741_824,
+            disk_available: 200 * 1_073_741_824,
+            disk_usage: 60.0,
+            network_rx: 0,
+            network_tx: 0,
+        };
+        let thresholds = QualitativeThresholds::default();
+        let metrics = MetricKind::ALL;
+
+        let focused = generate_message_focused(&data, &metrics, AnnounceStyle::Quantitative, &thresholds, Some(MetricKind::Memory));
+        assert_eq!(focused.len(), 4);
+        assert!(matches!(focused[0], MessagePart::Dynamic(_)));
+        assert_eq!(
+            focused[0].text(),
+            format!("Focusing on memory: {} at 78%, 12.5 of 16.0 gigabytes in use",
+                get_qualitative_description_with("memory", 78.0, &thresholds)),
+        );
+        // The rest are abbreviated and keep the configured order
+        let rest: Vec<&str> = focused[1..].iter().map(|part| part.text()).collect();
+        assert!(rest[0].starts_with("CPU: ") && rest[1].starts_with("Disk: ") && rest[2].starts_with("Network: "));
+        assert!(rest.iter().all(|line| !line.chars().any(|c| c.is_ascii_digit())));
+
+        // Clearing the focus, or focusing on a muted metric, is the plain message
+        let texts = |parts: Vec<MessagePart>| parts.iter().map(|part| part.text().to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            texts(generate_message_focused(&data, &metrics, AnnounceStyle::Quantitative, &thresholds, None)),
+            texts(generate_message_styled(&data, &metrics, AnnounceStyle::Quantitative, &thresholds)),
+        );
+        let without_memory = [MetricKind::Cpu, MetricKind::Disk];
+        assert_eq!(
+            texts(generate_message_focused(&data, &without_memory, AnnounceStyle::Quantitative, &thresholds, Some(MetricKind::Memory))),
+            texts(generate_message_styled(&data, &without_memory, AnnounceStyle::Quantitative, &thresholds)),
+        );
+    }
+
+    #[test]
+    fn test_personality_settings_scale() {
+        let personality = AIPersonality {
+            drunk_level: 0.25,
+            sass_level: 1.0,
+            tech_expertise: 0.7,
+            grand_pappi_references: 0.0,
+            enthusiasm: 0.33,
+            anxiety_level: 0.05,
+            ..AIPersonality::default()
+        };
+        let settings = PersonalitySettings::from_personality(&personality);
+        assert_eq!(
+            (settings.drunk_level, settings.sass_level, settings.tech_expertise, settings.grand_pappi_refs, settings.enthusiasm, settings.anxiety_level),
+            (25, 100, 70, 0, 33, 5)
+        );
+        assert_eq!(personality.to_settings(), settings);
+        assert_eq!((settings.catchphrases, settings.voice_type), (personality.catchphrases, personality.voice_type));
+
+        let wild = AIPersonality { anxiety_level: f32::NAN, enthusiasm: 3.0, ..AIPersonality::default() };
+        let settings = wild.to_settings();
+        assert_eq!((settings.anxiety_level, settings.enthusiasm), (0, TRAIT_SCALE));
+    }
+
+    #[test]
+    fn test_zero_totals_and_nan_never_reach_speech() {
+        let data = SystemData {
+            cpu_usage: vec![("CPU0".to_string(), f32::NAN), ("CPU1".to_string(), f32::INFINITY)],
+            memory_total: 0,
+            memory_used: 4_000_000_000,
+            memory_usage: f32::NAN,
+            disk_total: 0,
+            disk_available: 0,
+            disk_usage: f32::NEG_INFINITY,
+            network_rx: 0,
+            network_tx: 0,
+        };
+        let thresholds = QualitativeThresholds::default();
+        let mut texts: Vec<String> = Vec::new();
+        for style in [AnnounceStyle::Quantitative, AnnounceStyle::Qualitative] {
+            for focus in [None, Some(MetricKind::Cpu), Some(MetricKind::Memory), Some(MetricKind::Disk)] {
+                texts.extend(generate_message_focused(&data, &MetricKind::ALL, style, &thresholds, focus)
+                    .iter()
+                    .map(|part| part.text().to_string()));
+            }
+        }
+        texts.extend(warning_values(&data, DEFAULT_CALLSIGN).into_values());
+        for text in &texts {
+            assert!(!text.contains("NaN") && !text.contains("inf"), "{:?}", text);
+        }
+        assert_eq!(warning_values(&data, DEFAULT_CALLSIGN)["mem_pct"], "0");
+        assert_eq!(get_qualitative_description_with("cpu", f32::NAN, &thresholds), "running cool");
+    }
+
+    #[test]
+    fn test_boot_summary_fires_once_after_first_valid_sample() {
+        let sample = |cpu: f32| SystemData {
+            cpu_usage: (0..8).map(|i| (format!("CPU{}", i), cpu)).collect(),
+            memory_total: 17_179_869_184,
+            memory_used: 8_589_934_592,
+            memory_usage: 50.0,
+            disk_total: 500_000_000_000,
+            disk_available: 200_000_000_000,
+            disk_usage: 60.0,
+            network_rx: 0,
+            network_tx: 0,
+        };
+        let mut boot = BootAnnouncement::new(true);
+
+        // The first refresh reads every CPU as idle; wait for real numbers
+        assert!(boot.take(&sample(0.0), DEFAULT_CALLSIGN).is_none());
+        let summary = boot.take(&sample(12.5), DEFAULT_CALLSIGN).expect("first complete sample announces");
+        assert_eq!(summary[0].text(), "All systems nominal: 8 cores, 16.0 gigs, 40% disk free, Captain.");
+        assert!(boot.take(&sample(30.0), DEFAULT_CALLSIGN).is_none());
+        assert!(boot.take(&sample(12.5), DEFAULT_CALLSIGN).is_none());
+
+        assert!(BootAnnouncement::new(false).take(&sample(12.5), DEFAULT_CALLSIGN).is_none(), "off unless enabled");
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+
+/// Warnings that can escalate to a desktop notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    Cpu,
+    Memory,
+    Bandwidth,
+    DataCap,
+}
+
+impl WarningKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            WarningKind::Cpu => "High CPU usage",
+            WarningKind::Memory => "Memory running low",
+            WarningKind::Bandwidth => "Sustained network traffic",
+            WarningKind::DataCap => "Data cap warning",
+        }
+    }
+}
+
+/// Which warnings raise desktop notifications, and when they stay quiet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub cpu: bool,
+    pub memory: bool,
+    pub bandwidth: bool,
+    pub data_cap: bool,
+    /// Suppress notifications between quiet_start_hour and quiet_end_hour (local time)
+    pub quiet_hours_enabled: bool,
+    pub quiet_start_hour: u32,
+    pub quiet_end_hour: u32,
+    /// Minimum time between two notifications of the same kind, in seconds
+    pub cooldown_secs: u64,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            cpu: false,
+            memory: false,
+            bandwidth: false,
+            data_cap: false,
+            quiet_hours_enabled: false,
+            quiet_start_hour: 22,
+            quiet_end_hour: 7,
+            cooldown_secs: 300,
+        }
+    }
+}
+
+impl NotificationSettings {
+    pub fn is_enabled(&self, kind: WarningKind) -> bool {
+        match kind {
+            WarningKind::Cpu => self.cpu,
+            WarningKind::Memory => self.memory,
+            WarningKind::Bandwidth => self.bandwidth,
+            WarningKind::DataCap => self.data_cap,
+        }
+    }
+
+    /// Whether `hour` (0-23) falls in quiet hours; the range may wrap past midnight
+    pub fn is_quiet_hour(&self, hour: u32) -> bool {
+        if !self.quiet_hours_enabled || self.quiet_start_hour == self.quiet_end_hour {
+            return false;
+        }
+        if self.quiet_start_hour < self.quiet_end_hour {
+            hour >= self.quiet_start_hour && hour < self.quiet_end_hour
+        } else {
+            hour >= self.quiet_start_hour || hour < self.quiet_end_hour
+        }
+    }
+}
+
+/// Shows a notification outside the app window
+pub trait Notifier {
+    fn notify(&self, summary: &str, body: &str) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// OS-native notifications through notify-rust
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, summary: &str, body: &str) -> Result<(), Box<dyn std::error::Error>> {
+        notify_rust::Notification::new()
+            .appname("CyberNinja Monitor")
+            .summary(summary)
+            .body(body)
+            .show()?;
+        Ok(())
+    }
+}
+
+/// Decides which warnings become desktop notifications and sends them
+pub struct NotificationCenter {
+    notifier: Box<dyn Notifier>,
+    last_sent: HashMap<WarningKind, Instant>,
+    available: bool,
+}
+
+impl NotificationCenter {
+    pub fn new(notifier: Box<dyn Notifier>) -> Self {
+        Self {
+            notifier,
+            last_sent: HashMap::new(),
+            available: true,
+        }
+    }
+
+    /// Sends a notification for `kind` unless it is disabled, quiet hours apply or it is cooling down.
+    ///
+    /// Returns true if a notification was shown. If the platform cannot show
+    /// notifications the failure is logged once and later warnings are skipped.
+    pub fn warn(&mut self, kind: WarningKind, body: &str, settings: &NotificationSettings, now: Instant, local_hour: u32) -> bool {
+        if !self.available || !settings.is_enabled(kind) || settings.is_quiet_hour(local_hour) {
+            return false;
+        }
+
+        let cooldown = Duration::from_secs(settings.cooldown_secs);
+        if let Some(last) = self.last_sent.get(&kind) {
+            if now.saturating_duration_since(*last) < cooldown {
+                return false;
+            }
+        }
+
+        match self.notifier.notify(kind.title(), body) {
+            Ok(()) => {
+                self.last_sent.insert(kind, now);
+                true
+            }
+            Err(e) => {
+                eprintln!("Desktop notifications unavailable, disabling them: {}", e);
+                self.available = false;
+                false
+            }
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.available
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct StubNotifier {
+        sent: Arc<Mutex<Vec<(String, String)>>>,
+        fail: bool,
+    }
+
+    impl Notifier for StubNotifier {
+        fn notify(&self, summary: &str, body: &str) -> Result<(), Box<dyn std::error::Error>> {
+            if self.fail {
+                return Err("no notification daemon".into());
+            }
+            self.sent.lock().unwrap().push((summary.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+
+    fn cpu_only() -> NotificationSettings {
+        NotificationSettings {
+            cpu: true,
+            cooldown_secs: 60,
+            ..NotificationSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_notification_includes_metric_and_respects_cooldown() {
+        let stub = StubNotifier::default();
+        let sent = Arc::clone(&stub.sent);
+        let mut center = NotificationCenter::new(Box::new(stub));
+        let settings = cpu_only();
+        let start = Instant::now();
+
+        assert!(center.warn(WarningKind::Cpu, "CPU at 97.0%", &settings, start, 12));
+        assert!(!center.warn(WarningKind::Cpu, "CPU at 98.0%", &settings, start + Duration::from_secs(30), 12));
+        assert!(center.warn(WarningKind::Cpu, "CPU at 99.0%", &settings, start + Duration::from_secs(61), 12));
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0], ("High CPU usage".to_string(), "CPU at 97.0%".to_string()));
+    }
+
+    #[test]
+    fn test_disabled_kinds_are_skipped() {
+        let stub = StubNotifier::default();
+        let sent = Arc::clone(&stub.sent);
+        let mut center = NotificationCenter::new(Box::new(stub));
+
+        assert!(!center.warn(WarningKind::Memory, "Memory at 95.0%", &cpu_only(), Instant::now(), 12));
+        assert!(sent.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        let settings = NotificationSettings {
+            quiet_hours_enabled: true,
+            quiet_start_hour: 22,
+            quiet_end_hour: 7,
+            ..cpu_only()
+        };
+        assert!(settings.is_quiet_hour(23));
+        assert!(settings.is_quiet_hour(3));
+        assert!(!settings.is_quiet_hour(7));
+        assert!(!settings.is_quiet_hour(12));
+
+        let mut center = NotificationCenter::new(Box::new(StubNotifier::default()));
+        assert!(!center.warn(WarningKind::Cpu, "CPU at 97.0%", &settings, Instant::now(), 23));
+    }
+
+    #[test]
+    fn test_unavailable_notifier_is_skipped_silently() {
+        let stub = StubNotifier { fail: true, ..StubNotifier::default() };
+        let mut center = NotificationCenter::new(Box::new(stub));
+        let settings = cpu_only();
+
+        assert!(!center.warn(WarningKind::Cpu, "CPU at 97.0%", &settings, Instant::now(), 12));
+        assert!(!center.is_available());
+    }
+}
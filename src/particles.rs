@@ -65,14 +65,27 @@ impl ParticleSystem {
         self.particles.push(Particle::new(position, velocity, lifetime, color, size));
     }
 
+    /// Removes every live particle
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
     pub fn get_particles(&self) -> &[Particle] {
         &self.particles
     }
 
     pub fn draw(&self, ui: &mut egui::Ui) {
-        let painter = ui.painter();
-        
-        for particle in &self.particles {
+        self.draw_excluding(ui.painter(), &[]);
+    }
+
+    /// Draws every particle except those overlapping `excluded`, e.g. the metric cards
+    pub fn draw_excluding(&self, painter: &egui::Painter, excluded: &[Rect]) {
+        self.draw_scaled(painter, excluded, 1.0);
+    }
+
+    /// Like `draw_excluding`, with every particle's radius multiplied by `scale`
+    pub fn draw_scaled(&self, painter: &egui::Painter, excluded: &[Rect], scale: f32) {
+        for particle in self.particles.iter().filter(|particle| !is_culled(particle, excluded)) {
             let alpha = particle.lifetime / particle.initial_lifetime;
             let color = Color32::from_rgba_unmultiplied(
                 particle.color.r(),
@@ -83,9 +96,42 @@ impl ParticleSystem {
 
             painter.circle_filled(
                 particle.position,
-                particle.size,
+                particle.size * scale,
                 color,
             );
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Whether any part of `particle` falls inside one of the `excluded` rects
+pub fn is_culled(particle: &Particle, excluded: &[Rect]) -> bool {
+    excluded.iter().any(|rect| rect.expand(particle.size).contains(particle.position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{pos2, vec2};
+
+    fn particle_at(position: Pos2) -> Particle {
+        Particle::new(position, Vec2::ZERO, 1.0, Color32::WHITE, 5.0)
+    }
+
+    #[test]
+    fn test_particles_over_cards_are_culled() {
+        let cards = [
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0)),
+            Rect::from_min_size(pos2(0.0, 100.0), vec2(100.0, 50.0)),
+        ];
+
+        assert!(is_culled(&particle_at(pos2(50.0, 25.0)), &cards));
+        assert!(is_culled(&particle_at(pos2(50.0, 120.0)), &cards));
+        // The edge of the circle reaching into a card is enough
+        assert!(is_culled(&particle_at(pos2(104.0, 25.0)), &cards));
+
+        // The gap between cards and the margins stay decorated
+        assert!(!is_culled(&particle_at(pos2(50.0, 75.0)), &cards));
+        assert!(!is_culled(&particle_at(pos2(150.0, 25.0)), &cards));
+        assert!(!is_culled(&particle_at(pos2(50.0, 25.0)), &[]));
+    }
+}
@@ -0,0 +1,183 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use directories::ProjectDirs;
+use crate::config::CONFIG_FILE;
+use crate::event_log::EVENT_LOG_FILE;
+
+/// Where settings, the TTS cache and its archives live.
+///
+/// Normally these are the platform's config and cache directories; in
+/// portable mode everything sits next to the executable instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppPaths {
+    pub config_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub portable: bool,
+}
+
+impl AppPaths {
+    /// Resolves the directories for this machine, falling back to portable mode
+    /// when the platform has no home directory to put them in
+    pub fn resolve(portable: bool) -> Self {
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| PathBuf::from("."));
+        let platform = ProjectDirs::from("com", "pioneertrail", "CyberNinjaMonitor")
+            .map(|dirs| (dirs.config_dir().to_path_buf(), dirs.cache_dir().to_path_buf()));
+        Self::from_roots(portable, &exe_dir, platform)
+    }
+
+    /// Picks the layout from an executable directory and the platform's (config, cache) directories
+    pub fn from_roots(portable: bool, exe_dir: &Path, platform: Option<(PathBuf, PathBuf)>) -> Self {
+        match platform {
+            Some((config_dir, cache_dir)) if !portable => Self { config_dir, cache_dir, portable: false },
+            _ => Self::portable_in(exe_dir),
+        }
+    }
+
+    /// Everything under `root`, laid out the way older versions used the working directory
+    pub fn portable_in(root: &Path) -> Self {
+        Self {
+            config_dir: root.to_path_buf(),
+            cache_dir: root.join("cache"),
+            portable: true,
+        }
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join(CONFIG_FILE)
+    }
+
+    pub fn event_log_file(&self) -> PathBuf {
+        self.config_dir.join(EVENT_LOG_FILE)
+    }
+
+    pub fn tts_cache_dir(&self) -> PathBuf {
+        self.cache_dir.join("tts")
+    }
+
+    pub fn archive_dir(&self) -> PathBuf {
+        self.tts_cache_dir().join("archive")
+    }
+
+    pub fn reports_dir(&self) -> PathBuf {
+        self.config_dir.join("reports")
+    }
+
+    /// One line per location, for the diagnostics window
+    pub fn summary(&self) -> Vec<(&'static str, PathBuf)> {
+        vec![
+            ("Config", self.config_file()),
+            ("TTS cache", self.tts_cache_dir()),
+            ("Archives", self.archive_dir()),
+            ("Events log", self.event_log_file()),
+            ("Reports", self.reports_dir()),
+        ]
+    }
+
+    /// Moves settings and archives written by older versions to `legacy_root`
+    /// (the working directory) into these locations.
+    ///
+    /// Anything already present at the new location wins and the old copy is
+    /// left alone. Returns one message per item moved.
+    pub fn migrate_legacy(&self, legacy_root: &Path) -> Vec<String> {
+        let legacy = Self::portable_in(legacy_root);
+        let mut moved = Vec::new();
+        for (from, to) in [
+            (legacy.config_file(), self.config_file()),
+            (legacy.archive_dir(), self.archive_dir()),
+        ] {
+            if !from.exists() || to.exists() || same_path(&from, &to) {
+                continue;
+            }
+            match move_path(&from, &to) {
+                Ok(()) => moved.push(format!("Moved {:?} to {:?}", from, to)),
+                Err(e) => eprintln!("Failed to move {:?} to {:?}: {}", from, to, e),
+            }
+        }
+        moved
+    }
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Renames `from` to `to`, copying instead when they are on different file systems
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolution_in_both_modes() {
+        let exe_dir = Path::new("/opt/cnm");
+        let platform = Some((PathBuf::from("/home/u/.config/cnm"), PathBuf::from("/home/u/.cache/cnm")));
+
+        let installed = AppPaths::from_roots(false, exe_dir, platform.clone());
+        assert!(!installed.portable);
+        assert_eq!(installed.config_file(), Path::new("/home/u/.config/cnm").join(CONFIG_FILE));
+        assert_eq!(installed.archive_dir(), Path::new("/home/u/.cache/cnm/tts/archive"));
+
+        let portable = AppPaths::from_roots(true, exe_dir, platform);
+        assert!(portable.portable);
+        assert_eq!(portable.config_file(), exe_dir.join(CONFIG_FILE));
+        assert_eq!(portable.tts_cache_dir(), Path::new("/opt/cnm/cache/tts"));
+
+        // No home directory to resolve against: behave as if portable
+        assert_eq!(AppPaths::from_roots(false, exe_dir, None), AppPaths::portable_in(exe_dir));
+    }
+
+    #[test]
+    fn test_migrates_legacy_data_once() {
+        let root = std::env::temp_dir().join(format!("cyber_ninja_paths_{}", std::process::id()));
+        let legacy_root = root.join("cwd");
+        let paths = AppPaths { config_dir: root.join("config"), cache_dir: root.join("cache"), portable: false };
+        fs::create_dir_all(legacy_root.join("cache/tts/archive")).unwrap();
+        fs::write(legacy_root.join(CONFIG_FILE), "{}").unwrap();
+        fs::write(legacy_root.join("cache/tts/archive/clip.mp3"), [1, 2, 3]).unwrap();
+
+        let moved = paths.migrate_legacy(&legacy_root);
+        let second = paths.migrate_legacy(&legacy_root);
+        let config_moved = paths.config_file().exists() && !legacy_root.join(CONFIG_FILE).exists();
+        let clip = fs::read(paths.archive_dir().join("clip.mp3"));
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(moved.len(), 2);
+        assert!(second.is_empty(), "nothing left to move");
+        assert!(config_moved);
+        assert_eq!(clip.unwrap(), vec![1, 2, 3]);
+    }
+}
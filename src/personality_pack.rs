@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use crate::ai_personality::AIPersonality;
+use crate::config::AppConfig;
+use crate::message_system::{AnnounceStyle, Severity, WarningTemplates};
+use crate::tts;
+
+/// Format version written into new packs; packs from a newer version are refused
+pub const PACK_VERSION: u32 = 1;
+
+/// Default file name offered when exporting a pack
+pub const DEFAULT_PACK_FILE: &str = "personality_pack.json";
+
+/// A whole personality in one shareable file: traits, voice and catchphrases plus the
+/// settings that shape how it speaks. Sections other than the personality are optional.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonalityPack {
+    pub name: String,
+    pub version: u32,
+    pub personality: AIPersonality,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity_voices: Option<BTreeMap<Severity, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning_templates: Option<WarningTemplates>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announce_style: Option<AnnounceStyle>,
+}
+
+impl PersonalityPack {
+    /// Bundles the current personality and its related settings
+    pub fn from_current(name: &str, personality: &AIPersonality, config: &AppConfig) -> Self {
+        Self {
+            name: name.trim().to_string(),
+            version: PACK_VERSION,
+            personality: personality.clone(),
+            severity_voices: Some(config.severity_voices.clone()),
+            warning_templates: Some(config.warning_templates.clone()),
+            announce_style: Some(config.announce_style),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_json(&contents)
+    }
+
+    /// Parses a pack, refusing unsupported versions and unknown voices and clamping
+    /// traits, rate and volume into range so the preview shows what will be applied
+    pub fn from_json(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut pack: Self = serde_json::from_str(contents)?;
+        if pack.version == 0 || pack.version > PACK_VERSION {
+            return Err(format!(
+                "personality pack version {} is not supported (expected 1 to {})",
+                pack.version, PACK_VERSION
+            ).into());
+        }
+        let voices = std::iter::once(&pack.personality.voice_type)
+            .chain(pack.severity_voices.iter().flat_map(|voices| voices.values()));
+        for voice in voices {
+            if !tts::VOICES.contains(&voice.as_str()) {
+                return Err(format!("personality pack uses unknown voice '{}'", voice).into());
+            }
+        }
+        pack.personality.clamp_values();
+        Ok(pack)
+    }
+
+    /// One line per section, shown before the pack is applied
+    pub fn summary(&self) -> Vec<String> {
+        let p = &self.personality;
+        let mut lines = vec![
+            format!("\"{}\" (version {})", self.name, self.version),
+            format!("Voice: {}", p.voice_type),
+            format!(
+                "Traits: drunk {:.0}%, sass {:.0}%, tech {:.0}%, Grand Pappi {:.0}%, enthusiasm {:.0}%, anxiety {:.0}%",
+                p.drunk_level * 100.0, p.sass_level * 100.0, p.tech_expertise * 100.0,
+                p.grand_pappi_references * 100.0, p.enthusiasm * 100.0, p.anxiety_level * 100.0,
+            ),
+            format!("{} catchphrases", p.catchphrases.len()),
+        ];
+        lines.push(match &self.severity_voices {
+            Some(voices) if !voices.is_empty() => format!("Alert voices: {}", voices.iter()
+                .map(|(severity, voice)| format!("{} = {}", severity.label(), voice))
+                .collect::<Vec<_>>()
+                .join(", ")),
+            Some(_) => "Alert voices: personality voice for everything".to_string(),
+            None => "Alert voices: not included".to_string(),
+        });
+        lines.push(match &self.warning_templates {
+            Some(templates) => format!("{} warning phrases", templates.templates.len()),
+            None => "Warning phrases: not included".to_string(),
+        });
+        lines.push(match self.announce_style {
+            Some(style) => format!("Announce: {}", style.label()),
+            None => "Announce style: not included".to_string(),
+        });
+        lines
+    }
+
+    /// Replaces the personality and every section the pack includes; missing sections keep the current settings
+    pub fn apply(self, personality: &mut AIPersonality, config: &mut AppConfig) {
+        *personality = self.personality;
+        personality.clamp_values();
+        if let Some(voices) = self.severity_voices {
+            config.severity_voices = voices;
+        }
+        if let Some(templates) = self.warning_templates {
+            config.warning_templates = templates;
+        }
+        if let Some(style) = self.announce_style {
+            config.announce_style = style;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_system::MetricKind;
+
+    #[test]
+    fn test_pack_round_trip_covers_all_fields() {
+        let personality = AIPersonality {
+            voice_type: "nova".to_string(),
+            volume: 0.5,
+            speech_rate: 1.3,
+            drunk_level: 0.9,
+            sass_level: 0.1,
+            tech_expertise: 0.4,
+            grand_pappi_references: 0.8,
+            enthusiasm: 0.2,
+            anxiety_level: 0.6,
+            enthusiasm_baseline: 0.3,
+            anxiety_baseline: 0.7,
+            catchphrases: vec!["Ninja vanish!".to_string()],
+            audio_enabled: false,
+            is_1337_mode: true,
+        };
+        let mut config = AppConfig {
+            announce_style: AnnounceStyle::Qualitative,
+            ..AppConfig::default()
+        };
+        config.severity_voices.insert(Severity::Critical, "onyx".to_string());
+        config.warning_templates.set(MetricKind::Cpu, Severity::Warning, "CPU at {cpu_pct}".to_string());
+
+        let pack = PersonalityPack::from_current(" Night shift ", &personality, &config);
+        let path = std::env::temp_dir().join(format!("cyber_ninja_pack_{}.json", std::process::id()));
+        pack.save(&path).unwrap();
+        let loaded = PersonalityPack::load(&path);
+        let _ = std::fs::remove_file(&path);
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded, pack);
+        assert_eq!(loaded.name, "Night shift");
+
+        let mut applied = AIPersonality::default();
+        let mut target = AppConfig::default();
+        loaded.apply(&mut applied, &mut target);
+        assert_eq!(applied, personality);
+        assert_eq!(target.severity_voices, config.severity_voices);
+        assert_eq!(target.warning_templates, config.warning_templates);
+        assert_eq!(target.announce_style, AnnounceStyle::Qualitative);
+    }
+
+    #[test]
+    fn test_pack_version_and_missing_sections() {
+        let personality = serde_json::to_value(AIPersonality::default()).unwrap();
+        let minimal = serde_json::json!({ "name": "Bare", "version": 1, "personality": personality }).to_string();
+        let pack = PersonalityPack::from_json(&minimal).unwrap();
+        assert_eq!((pack.severity_voices.as_ref(), pack.announce_style), (None, None));
+        assert!(pack.summary().contains(&"Alert voices: not included".to_string()));
+
+        // Sections a pack leaves out keep what the user already had
+        let mut config = AppConfig { announce_style: AnnounceStyle::Qualitative, ..AppConfig::default() };
+        pack.apply(&mut AIPersonality::default(), &mut config);
+        assert_eq!(config.announce_style, AnnounceStyle::Qualitative);
+
+        let future = serde_json::json!({ "name": "Future", "version": PACK_VERSION + 1, "personality": personality }).to_string();
+        assert!(PersonalityPack::from_json(&future).unwrap_err().to_string().contains("not supported"));
+        assert!(PersonalityPack::from_json("{\"name\": \"Broken\"}").is_err());
+    }
+
+    #[test]
+    fn test_pack_clamps_values_and_rejects_unknown_voices() {
+        let mut personality = serde_json::to_value(AIPersonality::default()).unwrap();
+        personality["sass_level"] = 7.0.into();
+        personality["anxiety_baseline"] = (-1.0).into();
+        personality["speech_rate"] = 9.0.into();
+        personality["volume"] = 3.0.into();
+        let pack = serde_json::json!({ "name": "Loud", "version": 1, "personality": personality }).to_string();
+        let pack = PersonalityPack::from_json(&pack).unwrap();
+        let p = &pack.personality;
+        assert_eq!((p.sass_level, p.anxiety_baseline, p.speech_rate, p.volume), (1.0, 0.0, 2.0, 1.0));
+
+        personality["voice_type"] = "darth_vader".into();
+        let pack = serde_json::json!({ "name": "Sith", "version": 1, "personality": personality }).to_string();
+        assert!(PersonalityPack::from_json(&pack).unwrap_err().to_string().contains("darth_vader"));
+
+        personality["voice_type"] = "onyx".into();
+        let pack = serde_json::json!({
+            "name": "Sith", "version": 1, "personality": personality,
+            "severity_voices": {"Critical": "darth_vader"},
+        }).to_string();
+        assert!(PersonalityPack::from_json(&pack).unwrap_err().to_string().contains("darth_vader"));
+    }
+}
@@ -0,0 +1,322 @@
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+use crate::system_monitor::{MetricsSnapshot, finite_percent, rate_per_sec};
+
+/// Template the remote agent's `--print` mode fills in: whitespace separated so it splits cleanly
+pub const REMOTE_PRINT_TEMPLATE: &str = "%cpu% %mem% %disk% %rx_bytes% %tx_bytes%";
+
+/// Command that starts the monitor on the remote host unless a host overrides it
+pub const DEFAULT_AGENT_COMMAND: &str = "cyber_ninja_monitor";
+
+/// How often the agent process is polled while waiting for it to exit
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A server monitored over ssh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteHost {
+    /// Name shown in the host switcher
+    pub name: String,
+    /// ssh destination, e.g. "admin@web-01" or a Host alias from ~/.ssh/config
+    pub destination: String,
+    pub port: Option<u16>,
+    /// How to start the monitor binary on the remote host
+    pub agent_command: String,
+}
+
+impl Default for RemoteHost {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            destination: String::new(),
+            port: None,
+            agent_command: DEFAULT_AGENT_COMMAND.to_string(),
+        }
+    }
+}
+
+impl RemoteHost {
+    /// Switcher label, falling back to the ssh destination
+    pub fn label(&self) -> &str {
+        if self.name.trim().is_empty() {
+            &self.destination
+        } else {
+            &self.name
+        }
+    }
+
+    /// The ssh invocation that runs the agent's `--print` mode once.
+    ///
+    /// BatchMode makes ssh fail instead of prompting for a password, which
+    /// would otherwise hang the poll until it times out. The destination
+    /// follows `--`, so one starting with "-" can't be taken for an option
+    /// like `-oProxyCommand` and run a local command.
+    pub fn ssh_command(&self, timeout: Duration) -> Command {
+        let mut command = Command::new("ssh");
+        command
+            .arg("-o").arg("BatchMode=yes")
+            .arg("-o").arg(format!("ConnectTimeout={}", timeout.as_secs().max(1)));
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        command
+            .arg("--")
+            .arg(&self.destination)
+            .arg(format!("{} --print '{}'", self.agent_command, REMOTE_PRINT_TEMPLATE));
+        command
+    }
+}
+
+/// One reading from a remote host
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteSample {
+    pub snapshot: MetricsSnapshot,
+    pub disk_usage: f32,
+    /// Bytes per second since the previous sample, 0 for the first one
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+}
+
+/// Parses the last non-empty line of the agent's output, filled in from REMOTE_PRINT_TEMPLATE
+pub fn parse_print_output(output: &str, taken_at: Instant) -> Result<RemoteSample, String> {
+    let line = output.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("");
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err(format!("expected 5 values from the remote agent, got '{}'", line.trim()));
+    }
+
+    // "NaN" and "inf" parse as floats, but must never reach the dashboard
+    let percent = |field: &str| field.parse::<f32>()
+        .map(finite_percent)
+        .map_err(|_| format!("invalid value '{}' from the remote agent", field));
+    let bytes = |field: &str| field.parse::<u64>().map_err(|_| format!("invalid byte count '{}' from the remote agent", field));
+    Ok(RemoteSample {
+        snapshot: MetricsSnapshot {
+            taken_at,
+            since_previous: None,
+            sequence: 1,
+            cpu_average: percent(fields[0])?,
+            memory_usage: percent(fields[1])?,
+            network_rx_total: bytes(fields[3])?,
+            network_tx_total: bytes(fields[4])?,
+            updated: MetricsSnapshot::all_updated_at(taken_at),
+        },
+        disk_usage: percent(fields[2])?,
+        rx_rate: 0.0,
+        tx_rate: 0.0,
+    })
+}
+
+/// Links `next` to the sample before it, filling in the interval, sequence number and transfer rates
+pub fn follow(previous: Option<&RemoteSample>, mut next: RemoteSample) -> RemoteSample {
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return next,
+    };
+
+    let elapsed = next.snapshot.taken_at.saturating_duration_since(previous.snapshot.taken_at);
+    next.snapshot.since_previous = Some(elapsed);
+    next.snapshot.sequence = previous.snapshot.sequence + 1;
+    next.rx_rate = rate_per_sec(previous.snapshot.network_rx_total, next.snapshot.network_rx_total, elapsed).unwrap_or(0.0);
+    next.tx_rate = rate_per_sec(previous.snapshot.network_tx_total, next.snapshot.network_tx_total, elapsed).unwrap_or(0.0);
+    next
+}
+
+/// Runs `command` to completion, killing it if it takes longer than `timeout`, and returns its stdout
+pub fn run_with_timeout(mut command: Command, timeout: Duration) -> Result<String, String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not start {:?}: {}", command.get_program(), e))?;
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}s", timeout.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(WAIT_POLL_INTERVAL),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        let _ = pipe.read_to_string(&mut stdout);
+    }
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+
+    if status.success() {
+        Ok(stdout)
+    } else if stderr.trim().is_empty() {
+        Err(format!("exited with {}", status))
+    } else {
+        Err(stderr.trim().to_string())
+    }
+}
+
+/// Reachability of a remote host as shown in the dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostStatus {
+    /// No reading has come back yet
+    Connecting,
+    Reachable(RemoteSample),
+    /// The last poll failed: connection refused, timeout, agent missing, ...
+    Unreachable(String),
+}
+
+/// Polls one remote host in the background so the UI never waits on ssh
+pub struct RemoteSource {
+    host: RemoteHost,
+    timeout: Duration,
+    status: Arc<Mutex<HostStatus>>,
+    last_sample: Arc<Mutex<Option<RemoteSample>>>,
+    in_flight: Arc<AtomicBool>,
+    last_poll: Option<Instant>,
+}
+
+impl RemoteSource {
+    pub fn new(host: RemoteHost, timeout: Duration) -> Self {
+        Self {
+            host,
+            timeout,
+            status: Arc::new(Mutex::new(HostStatus::Connecting)),
+            last_sample: Arc::new(Mutex::new(None)),
+            in_flight: Arc::new(AtomicBool::new(false)),
+            last_poll: None,
+        }
+    }
+
+    pub fn host(&self) -> &RemoteHost {
+        &self.host
+    }
+
+    pub fn status(&self) -> HostStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Starts a background poll if `interval` has passed since the last one and none is running.
+    ///
+    /// Returns whether a poll was started.
+    pub fn poll(&mut self, now: Instant, interval: Duration) -> bool {
+        let due = self.last_poll.is_none_or(|last| now.saturating_duration_since(last) >= interval);
+        if !due || self.in_flight.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        self.last_poll = Some(now);
+
+        let command = self.host.ssh_command(self.timeout);
+        let timeout = self.timeout;
+        let status = Arc::clone(&self.status);
+        let last_sample = Arc::clone(&self.last_sample);
+        let in_flight = Arc::clone(&self.in_flight);
+        let label = self.host.label().to_string();
+        std::thread::spawn(move || {
+            let result = run_with_timeout(command, timeout)
+                .and_then(|output| parse_print_output(&output, Instant::now()));
+            let next = match result {
+                Ok(sample) => {
+                    let mut last_sample = last_sample.lock().unwrap();
+                    let sample = follow(last_sample.as_ref(), sample);
+                    *last_sample = Some(sample.clone());
+                    HostStatus::Reachable(sample)
+                }
+                Err(e) => {
+                    eprintln!("Remote host {} unreachable: {}", label, e);
+                    HostStatus::Unreachable(e)
+                }
+            };
+            *status.lock().unwrap() = next;
+            in_flight.store(false, Ordering::SeqCst);
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_print_output() {
+        let now = Instant::now();
+        let sample = parse_print_output("Loading...\n12.5 40.0 71.3 1048576 2048\n\n", now).unwrap();
+        assert_eq!(sample.snapshot.cpu_average, 12.5);
+        assert_eq!(sample.snapshot.memory_usage, 40.0);
+        assert_eq!(sample.disk_usage, 71.3);
+        assert_eq!(sample.snapshot.network_rx_total, 1_048_576);
+        assert_eq!(sample.snapshot.network_tx_total, 2048);
+
+        assert!(parse_print_output("12.5 40.0", now).unwrap_err().contains("expected 5 values"));
+        assert!(parse_print_output("bash: cyber_ninja_monitor: command not found", now).is_err());
+        assert!(parse_print_output("12.5 40.0 71.3 lots 2048", now).unwrap_err().contains("lots"));
+        assert!(parse_print_output("", now).is_err());
+
+        let odd = parse_print_output("NaN inf -5 0 0", now).unwrap();
+        assert_eq!((odd.snapshot.cpu_average, odd.snapshot.memory_usage, odd.disk_usage), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_follow_computes_rates() {
+        let start = Instant::now();
+        let first = parse_print_output("10 20 30 1000 500", start).unwrap();
+        let first = follow(None, first);
+        assert_eq!((first.rx_rate, first.snapshot.since_previous), (0.0, None));
+
+        let second = parse_print_output("10 20 30 3000 1500", start + Duration::from_secs(2)).unwrap();
+        let second = follow(Some(&first), second);
+        assert_eq!(second.snapshot.since_previous, Some(Duration::from_secs(2)));
+        assert_eq!(second.snapshot.sequence, 2);
+        assert_eq!((second.rx_rate, second.tx_rate), (1000.0, 500.0));
+
+        // A sample at the same instant, or after the agent's counters reset, has no traffic rate
+        let same_instant = follow(Some(&second), parse_print_output("10 20 30 9000 9000", start + Duration::from_secs(2)).unwrap());
+        assert_eq!((same_instant.rx_rate, same_instant.tx_rate), (0.0, 0.0));
+        let reset = follow(Some(&second), parse_print_output("10 20 30 0 0", start + Duration::from_secs(3)).unwrap());
+        assert_eq!((reset.rx_rate, reset.tx_rate), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_ssh_command_runs_print_mode_non_interactively() {
+        let host = RemoteHost {
+            name: "Web".to_string(),
+            destination: "admin@web-01".to_string(),
+            port: Some(2222),
+            ..RemoteHost::default()
+        };
+        let command = host.ssh_command(Duration::from_secs(5));
+        let args: Vec<String> = command.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+
+        assert_eq!(command.get_program(), "ssh");
+        assert_eq!(&args[..6], ["-o", "BatchMode=yes", "-o", "ConnectTimeout=5", "-p", "2222"]);
+        assert_eq!(args[6..8], ["--", "admin@web-01"]);
+        assert_eq!(args[8], format!("cyber_ninja_monitor --print '{}'", REMOTE_PRINT_TEMPLATE));
+        assert_eq!(host.label(), "Web");
+
+        // A destination that looks like an option stays a destination
+        let sneaky = RemoteHost { destination: "-oProxyCommand=touch /tmp/pwned".to_string(), ..RemoteHost::default() };
+        let args: Vec<String> = sneaky.ssh_command(Duration::from_secs(5)).get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+        let end_of_options = args.iter().position(|arg| arg == "--").unwrap();
+        assert_eq!(args[end_of_options + 1], "-oProxyCommand=touch /tmp/pwned");
+        assert!(args[..end_of_options].iter().all(|arg| !arg.contains("ProxyCommand")));
+    }
+
+    #[test]
+    fn test_missing_agent_is_reported_as_error() {
+        let command = Command::new("cyber-ninja-no-such-program");
+        let error = run_with_timeout(command, Duration::from_secs(1)).unwrap_err();
+        assert!(error.contains("could not start"));
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDateTime, Timelike};
+use serde::{Serialize, Deserialize};
+use crate::event_log::EventRecord;
+use crate::message_system::MetricKind;
+
+/// Event kinds worth listing in a report; warnings are counted separately and the rest is routine chatter
+pub const NOTABLE_EVENTS: [&str; 4] = ["emergency", "resume", "device", "boot summary"];
+
+/// Notable events listed in one report; past this only the latest are kept
+pub const MAX_NOTABLE_EVENTS: usize = 50;
+
+/// How often a summary report is rolled up, always at a round local time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportPeriod {
+    Hourly,
+    Daily,
+    /// Monday to Monday
+    Weekly,
+}
+
+impl ReportPeriod {
+    pub const ALL: [ReportPeriod; 3] = [ReportPeriod::Hourly, ReportPeriod::Daily, ReportPeriod::Weekly];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportPeriod::Hourly => "Hourly",
+            ReportPeriod::Daily => "Daily",
+            ReportPeriod::Weekly => "Weekly",
+        }
+    }
+
+    /// The first boundary strictly after `after`: the next top of the hour, midnight or Monday midnight
+    pub fn next_boundary(&self, after: NaiveDateTime) -> NaiveDateTime {
+        let midnight = after.date().and_hms_opt(0, 0, 0).unwrap_or(after);
+        match self {
+            ReportPeriod::Hourly => midnight + ChronoDuration::hours(after.hour() as i64 + 1),
+            ReportPeriod::Daily => midnight + ChronoDuration::days(1),
+            ReportPeriod::Weekly => midnight + ChronoDuration::days(7 - after.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    /// File name a report starting at `start` is written under, without the extension
+    pub fn file_stem(&self, start: DateTime<Local>) -> String {
+        match self {
+            ReportPeriod::Hourly => start.format("report-%Y-%m-%d-%H00").to_string(),
+            ReportPeriod::Daily | ReportPeriod::Weekly => start.format("report-%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Periodic summary reports written to the reports directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportSettings {
+    pub enabled: bool,
+    pub period: ReportPeriod,
+    /// Write a JSON copy next to the text report
+    pub json: bool,
+    /// Have the AI read out a short version when a report is written
+    pub speak: bool,
+}
+
+impl Default for ReportSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: ReportPeriod::Daily,
+            json: false,
+            speak: false,
+        }
+    }
+}
+
+/// Lowest, highest and average reading of one metric over a report period
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricSummary {
+    pub kind: MetricKind,
+    pub min: f32,
+    pub max: f32,
+    pub avg: f32,
+    pub samples: u64,
+}
+
+/// Everything a report period came to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryReport {
+    pub period: ReportPeriod,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    /// Only metrics that were sampled during the period
+    pub metrics: Vec<MetricSummary>,
+    /// Warnings raised, by kind
+    pub warnings: BTreeMap<String, usize>,
+    /// How long the machine had been up when the report was written
+    pub uptime_secs: u64,
+    pub notable: Vec<EventRecord>,
+}
+
+/// Written after readings: "%" for the percentages, " MB/s" for network
+fn unit(kind: MetricKind) -> &'static str {
+    match kind {
+        MetricKind::Network => " MB/s",
+        _ => "%",
+    }
+}
+
+/// Uptime as days, hours and minutes, e.g. "3d 4h 12m"
+pub fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else {
+        format!("{}h {}m", hours, minutes)
+    }
+}
+
+impl SummaryReport {
+    pub fn warning_count(&self) -> usize {
+        self.warnings.values().sum()
+    }
+
+    /// The report as a plain text file
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("Cyber Ninja Monitor {} report", self.period.label().to_lowercase()),
+            format!("{} to {}", self.start.format("%Y-%m-%d %H:%M"), self.end.format("%Y-%m-%d %H:%M")),
+            format!("Uptime: {}", format_uptime(self.uptime_secs)),
+            String::new(),
+            format!("{:<10}{:>12}{:>12}{:>12}", "Metric", "Min", "Avg", "Max"),
+        ];
+        for metric in &self.metrics {
+            let reading = |value: f32| format!("{:.1}{}", value, unit(metric.kind));
+            lines.push(format!("{:<10}{:>12}{:>12}{:>12}", metric.kind.label(), reading(metric.min), reading(metric.avg), reading(metric.max)));
+        }
+        lines.push(String::new());
+        lines.push(format!("Warnings: {}", self.warning_count()));
+        for (kind, count) in &self.warnings {
+            lines.push(format!("  {}: {}", kind, count));
+        }
+        if !self.notable.is_empty() {
+            lines.push(String::new());
+            lines.push("Notable events:".to_string());
+            for event in &self.notable {
+                lines.push(format!("  {} {}: {}", event.time.format("%Y-%m-%d %H:%M"), event.kind, event.text));
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// A few sentences for the AI to read out
+    pub fn spoken(&self) -> String {
+        let mut sentences = vec![format!("{} report.", self.period.label())];
+        for metric in &self.metrics {
+            sentences.push(format!(
+                "{} averaged {:.0}{}, peaking at {:.0}{}.",
+                metric.kind.label(), metric.avg, unit(metric.kind), metric.max, unit(metric.kind)
+            ));
+        }
+        sentences.push(match self.warning_count() {
+            0 => "No warnings.".to_string(),
+            1 => "One warning.".to_string(),
+            count => format!("{} warnings.", count),
+        });
+        sentences.join(" ")
+    }
+}
+
+/// Running min/max/sum of one metric
+#[derive(Debug, Clone, Copy, Default)]
+struct Accumulator {
+    min: f32,
+    max: f32,
+    sum: f64,
+    count: u64,
+}
+
+impl Accumulator {
+    fn add(&mut self, value: f32) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.sum += value as f64;
+        self.count += 1;
+    }
+}
+
+/// Collects every reading and event of the current report period and rolls them up once the period is over.
+///
+/// Everything is tallied as it arrives rather than taken from the graph
+/// histories or the events log afterwards, which keep far less than a day.
+#[derive(Debug)]
+pub struct ReportRollup {
+    started: DateTime<Local>,
+    metrics: [(MetricKind, Accumulator); 4],
+    warnings: BTreeMap<String, usize>,
+    notable: Vec<EventRecord>,
+}
+
+impl ReportRollup {
+    pub fn new(now: DateTime<Local>) -> Self {
+        Self {
+            started: now,
+            metrics: MetricKind::ALL.map(|kind| (kind, Accumulator::default())),
+            warnings: BTreeMap::new(),
+            notable: Vec::new(),
+        }
+    }
+
+    /// Counts `event` if it is a warning and keeps it if it is notable
+    pub fn note(&mut self, event: &EventRecord) {
+        if event.kind.ends_with("warning") {
+            *self.warnings.entry(event.kind.clone()).or_insert(0) += 1;
+        } else if NOTABLE_EVENTS.contains(&event.kind.as_str()) {
+            if self.notable.len() == MAX_NOTABLE_EVENTS {
+                self.notable.remove(0);
+            }
+            self.notable.push(event.clone());
+        }
+    }
+
+    pub fn record(&mut self, kind: MetricKind, value: f32) {
+        if let Some((_, accumulator)) = self.metrics.iter_mut().find(|(other, _)| *other == kind) {
+            accumulator.add(value);
+        }
+    }
+
+    /// When the current period is over
+    pub fn due_at(&self, period: ReportPeriod) -> NaiveDateTime {
+        period.next_boundary(self.started.naive_local())
+    }
+
+    /// The report for the period just finished, once `now` has passed its boundary; a new period starts from `now`
+    pub fn take_if_due(&mut self, period: ReportPeriod, now: DateTime<Local>, uptime_secs: u64) -> Option<SummaryReport> {
+        if now.naive_local() < self.due_at(period) {
+            return None;
+        }
+        let metrics = self.metrics.iter()
+            .filter(|(_, accumulator)| accumulator.count > 0)
+            .map(|(kind, accumulator)| MetricSummary {
+                kind: *kind,
+                min: accumulator.min,
+                max: accumulator.max,
+                avg: (accumulator.sum / accumulator.count as f64) as f32,
+                samples: accumulator.count,
+            })
+            .collect();
+        let finished = std::mem::replace(self, Self::new(now));
+        Some(SummaryReport {
+            period,
+            start: finished.started,
+            end: now,
+            metrics,
+            warnings: finished.warnings,
+            uptime_secs,
+            notable: finished.notable,
+        })
+    }
+}
+
+/// Writes `report` into `dir` as text, plus JSON if asked; returns the text file's path
+pub fn write_report(dir: &Path, report: &SummaryReport, json: bool) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let stem = report.period.file_stem(report.start);
+    let path = dir.join(format!("{}.txt", stem));
+    fs::write(&path, report.to_text())?;
+    if json {
+        fs::write(dir.join(format!("{}.json", stem)), serde_json::to_string_pretty(report)?)?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn local(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 6, day, hour, minute, 0).unwrap()
+    }
+
+    fn event(time: DateTime<Local>, kind: &str) -> EventRecord {
+        EventRecord { time, kind: kind.to_string(), text: format!("{} at {}", kind, time.format("%H:%M")) }
+    }
+
+    #[test]
+    fn test_daily_rollup_of_a_synthetic_day() {
+        let mut rollup = ReportRollup::new(local(10, 0, 0));
+        // One reading a minute: CPU sweeps 0-99% every 100 minutes, memory holds at 40%
+        for minute in 0..24 * 60 {
+            rollup.record(MetricKind::Cpu, (minute % 100) as f32);
+            rollup.record(MetricKind::Memory, 40.0);
+        }
+        // A status update every 30 seconds all day, far more than the events log keeps
+        for half_minute in 0..24 * 120 {
+            let time = local(10, 0, 0) + ChronoDuration::seconds(half_minute * 30);
+            rollup.note(&event(time, "status update"));
+            if half_minute % 10 == 0 {
+                rollup.note(&event(time, "CPU warning"));
+            }
+        }
+        rollup.note(&event(local(10, 12, 30), "device"));
+        rollup.note(&event(local(10, 17, 45), "disk warning"));
+
+        assert!(rollup.take_if_due(ReportPeriod::Daily, local(10, 23, 59), 0).is_none(), "the day isn't over");
+        let report = rollup.take_if_due(ReportPeriod::Daily, local(11, 0, 0), 90_061).unwrap();
+
+        assert_eq!(report.metrics.len(), 2, "disk and network were never sampled");
+        let cpu = report.metrics[0];
+        assert_eq!((cpu.kind, cpu.min, cpu.max, cpu.samples), (MetricKind::Cpu, 0.0, 99.0, 1440));
+        // 14 full sweeps averaging 49.5, then minutes 0-39 averaging 19.5
+        assert!((cpu.avg - (14.0 * 100.0 * 49.5 + 40.0 * 19.5) / 1440.0).abs() < 0.001, "{}", cpu.avg);
+        assert_eq!((report.metrics[1].min, report.metrics[1].avg, report.metrics[1].max), (40.0, 40.0, 40.0));
+
+        assert_eq!(report.warning_count(), 289, "every warning of the day counts, not just the last 200 events");
+        assert_eq!(report.warnings.get("CPU warning"), Some(&288));
+        assert_eq!(report.warnings.get("disk warning"), Some(&1));
+        assert_eq!(report.notable.iter().map(|event| event.kind.as_str()).collect::<Vec<_>>(), vec!["device"]);
+        assert!(report.to_text().contains("Uptime: 1d 1h 1m"));
+        assert!(report.spoken().ends_with("289 warnings."), "{}", report.spoken());
+
+        // The next period starts where this one ended, from scratch
+        assert_eq!(rollup.due_at(ReportPeriod::Daily), local(12, 0, 0).naive_local());
+        let empty = rollup.take_if_due(ReportPeriod::Daily, local(12, 0, 5), 0).unwrap();
+        assert!(empty.metrics.is_empty() && empty.warnings.is_empty() && empty.notable.is_empty());
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        // Wednesday 10 June 2026, 14:25
+        let at = NaiveDate::from_ymd_opt(2026, 6, 10).unwrap().and_hms_opt(14, 25, 0).unwrap();
+        let day = |day, hour| NaiveDate::from_ymd_opt(2026, 6, day).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+        assert_eq!(ReportPeriod::Hourly.next_boundary(at), day(10, 15));
+        assert_eq!(ReportPeriod::Hourly.next_boundary(day(10, 23)), day(11, 0));
+        assert_eq!(ReportPeriod::Daily.next_boundary(at), day(11, 0));
+        assert_eq!(ReportPeriod::Daily.next_boundary(day(10, 0)), day(11, 0), "strictly after");
+        assert_eq!(ReportPeriod::Weekly.next_boundary(at), day(15, 0));
+        assert_eq!(ReportPeriod::Weekly.next_boundary(day(15, 0)), day(22, 0));
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+use egui::Event;
+use crate::message_system::MetricKind;
+use crate::spotlight::rotation_at;
+
+/// How long each metric is showcased before the screensaver moves on to the next
+pub const SHOWCASE_DURATION: Duration = Duration::from_secs(8);
+
+/// How much larger particles are drawn while the screensaver runs
+pub const SCREENSAVER_PARTICLE_SCALE: f32 = 2.5;
+
+/// Particles kept drifting across the screen while the screensaver runs
+pub const SCREENSAVER_PARTICLES: usize = 40;
+
+/// Whether `event` means someone touched the mouse, keyboard or screen
+pub fn is_user_input(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::PointerMoved(_)
+            | Event::PointerButton { .. }
+            | Event::Key { .. }
+            | Event::Text(_)
+            | Event::Scroll(_)
+            | Event::Zoom(_)
+            | Event::Touch { .. }
+            | Event::MouseWheel { .. }
+    )
+}
+
+/// Arms the screensaver once no input has arrived for the idle timeout and
+/// drops it again on the next input.
+///
+/// Warnings count as input, so whatever raised them is shown on the regular
+/// dashboard and the idle timeout starts over.
+#[derive(Debug, Clone, Copy)]
+pub struct IdleTracker {
+    last_input: Instant,
+    active_since: Option<Instant>,
+}
+
+impl IdleTracker {
+    pub fn new(now: Instant) -> Self {
+        Self { last_input: now, active_since: None }
+    }
+
+    /// Feeds one frame; returns whether the screensaver is showing after it
+    pub fn observe(&mut self, input: bool, now: Instant, idle_after: Duration) -> bool {
+        if input {
+            self.last_input = now;
+            self.active_since = None;
+        } else if self.active_since.is_none() && now.saturating_duration_since(self.last_input) >= idle_after {
+            self.active_since = Some(now);
+        }
+        self.is_active()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_since.is_some()
+    }
+
+    /// The metric on show `now`, cycling through `metrics` from when the screensaver started
+    pub fn showcase(&self, metrics: &[MetricKind], now: Instant) -> Option<MetricKind> {
+        rotation_at(metrics, SHOWCASE_DURATION, now.saturating_duration_since(self.active_since?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::pos2;
+
+    #[test]
+    fn test_idle_timeout_arms_and_input_reverts() {
+        let start = Instant::now();
+        let idle_after = Duration::from_secs(300);
+        let mut idle = IdleTracker::new(start);
+
+        assert!(!idle.observe(false, start + Duration::from_secs(299), idle_after));
+        assert!(idle.observe(false, start + Duration::from_secs(300), idle_after));
+        assert!(idle.observe(false, start + Duration::from_secs(900), idle_after), "stays on without input");
+
+        // Input reverts at once and the timeout starts over from it
+        assert!(!idle.observe(true, start + Duration::from_secs(901), idle_after));
+        assert!(!idle.observe(false, start + Duration::from_secs(1200), idle_after));
+        assert!(idle.observe(false, start + Duration::from_secs(1201), idle_after));
+
+        // Showcases cycle from when the screensaver started
+        let armed = start + Duration::from_secs(1201);
+        let metrics = [MetricKind::Cpu, MetricKind::Memory];
+        assert_eq!(idle.showcase(&metrics, armed), Some(MetricKind::Cpu));
+        assert_eq!(idle.showcase(&metrics, armed + SHOWCASE_DURATION), Some(MetricKind::Memory));
+        assert_eq!(idle.showcase(&metrics, armed + SHOWCASE_DURATION * 2), Some(MetricKind::Cpu));
+        assert_eq!(idle.showcase(&[], armed), None);
+        assert_eq!(IdleTracker::new(start).showcase(&metrics, armed), None);
+
+        assert!(is_user_input(&Event::PointerMoved(pos2(1.0, 1.0))));
+        assert!(is_user_input(&Event::Text("a".to_string())));
+        assert!(!is_user_input(&Event::PointerGone));
+    }
+}
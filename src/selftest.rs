@@ -0,0 +1,172 @@
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+use crate::ai_personality::AIPersonality;
+use crate::config::AppConfig;
+use crate::message_system::MessagePart;
+use crate::system_monitor::SystemMonitor;
+use crate::tts::{DryRunBackend, TTSManager};
+
+/// Result of one self-test check
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckOutcome {
+    Pass(String),
+    Fail(String),
+    Skip(String),
+}
+
+/// Pass/fail summary of a `--selftest` run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelfTestReport {
+    pub checks: Vec<(&'static str, CheckOutcome)>,
+}
+
+impl SelfTestReport {
+    pub fn record(&mut self, name: &'static str, outcome: CheckOutcome) {
+        self.checks.push((name, outcome));
+    }
+
+    pub fn failed(&self) -> usize {
+        self.checks.iter().filter(|(_, outcome)| matches!(outcome, CheckOutcome::Fail(_))).count()
+    }
+
+    /// Process exit code: 0 when nothing failed, 1 otherwise
+    pub fn exit_code(&self) -> i32 {
+        if self.failed() == 0 { 0 } else { 1 }
+    }
+
+    /// One line per check followed by a totals line
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        let (mut passed, mut skipped) = (0, 0);
+        for (name, outcome) in &self.checks {
+            let (tag, detail) = match outcome {
+                CheckOutcome::Pass(detail) => {
+                    passed += 1;
+                    ("PASS", detail)
+                }
+                CheckOutcome::Fail(detail) => ("FAIL", detail),
+                CheckOutcome::Skip(detail) => {
+                    skipped += 1;
+                    ("SKIP", detail)
+                }
+            };
+            lines.push(format!("[{}] {}: {}", tag, name, detail));
+        }
+        lines.push(format!("{} passed, {} failed, {} skipped", passed, self.failed(), skipped));
+        lines.join("\n")
+    }
+}
+
+/// Exercises the monitor, config, personality and (optionally) speech pipeline without the GUI
+pub fn run(with_tts: bool) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+    report.record("System monitor", check_system_monitor());
+    report.record("Config round trip", check_config_round_trip());
+    report.record("Personality transforms", check_personality());
+    report.record("Speech pipeline", check_speech(with_tts));
+    report
+}
+
+fn check_system_monitor() -> CheckOutcome {
+    let mut monitor = SystemMonitor::new();
+    let snapshot = monitor.refresh_now(Instant::now());
+    if !(0.0..=100.0).contains(&snapshot.memory_usage) || !snapshot.cpu_average.is_finite() {
+        return CheckOutcome::Fail(format!(
+            "implausible snapshot: CPU {:.1}%, memory {:.1}%",
+            snapshot.cpu_average, snapshot.memory_usage
+        ));
+    }
+    CheckOutcome::Pass(format!(
+        "CPU {:.1}%, memory {:.1}%, {} bytes received",
+        snapshot.cpu_average, snapshot.memory_usage, snapshot.network_rx_total
+    ))
+}
+
+fn check_config_round_trip() -> CheckOutcome {
+    let path = std::env::temp_dir().join(format!("cyber_ninja_selftest_{}.json", std::process::id()));
+    let config = AppConfig {
+        output_device: Some("Self-test device".to_string()),
+        ..AppConfig::default()
+    };
+
+    let result = config.save(&path).and_then(|_| AppConfig::load(&path));
+    let _ = std::fs::remove_file(&path);
+    match result {
+        Ok(loaded) if loaded == config => CheckOutcome::Pass(format!("saved and reloaded {:?}", path)),
+        Ok(_) => CheckOutcome::Fail("reloaded config differs from the saved one".to_string()),
+        Err(e) => CheckOutcome::Fail(e.to_string()),
+    }
+}
+
+fn check_personality() -> CheckOutcome {
+    let personality = AIPersonality {
+        drunk_level: 1.0,
+        enthusiasm: 1.0,
+        anxiety_level: 1.0,
+        sass_level: 0.0,
+        grand_pappi_references: 0.0,
+        ..AIPersonality::default()
+    };
+
+    let sample = "Système nominal, café ☕ ready.";
+    let transformed = match personality.apply_personality(&MessagePart::Static(sample.to_string())) {
+        MessagePart::Static(text) => text,
+        other => return CheckOutcome::Fail(format!("static text came back as {:?}", other)),
+    };
+    if !transformed.contains('☕') || !transformed.contains("🎉") || transformed == sample {
+        return CheckOutcome::Fail(format!("unexpected transform: {}", transformed));
+    }
+    if personality.generate_message("System is running normally").is_empty() {
+        return CheckOutcome::Fail("generated message was empty".to_string());
+    }
+    CheckOutcome::Pass(transformed)
+}
+
+fn check_speech(with_tts: bool) -> CheckOutcome {
+    if !with_tts {
+        return CheckOutcome::Skip("pass --with-tts to run".to_string());
+    }
+    if std::env::var("OPENAI_API_KEY").is_err() {
+        return CheckOutcome::Skip("OPENAI_API_KEY is not set".to_string());
+    }
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => return CheckOutcome::Fail(format!("could not start async runtime: {}", e)),
+    };
+    let backend = DryRunBackend::new();
+    let requests = backend.request_counter();
+    let mut tts = TTSManager::with_backend(Box::new(backend));
+    let message = vec![MessagePart::Static("Self-test complete.".to_string())];
+    let settings = AIPersonality::default().to_settings();
+
+    match runtime.block_on(tts.speak(message, &settings)) {
+        Ok(()) if requests.load(Ordering::SeqCst) == 1 => CheckOutcome::Pass("dry-run speak succeeded".to_string()),
+        Ok(()) => CheckOutcome::Fail(format!("expected 1 synthesis request, saw {}", requests.load(Ordering::SeqCst))),
+        Err(e) => CheckOutcome::Fail(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selftest_passes_without_tts() {
+        let report = run(false);
+        assert_eq!(report.failed(), 0, "{}", report.summary());
+        assert_eq!(report.exit_code(), 0);
+        assert!(report.summary().contains("[SKIP] Speech pipeline"));
+    }
+
+    #[test]
+    fn test_failures_set_exit_code() {
+        let mut report = SelfTestReport::default();
+        report.record("Good", CheckOutcome::Pass("ok".to_string()));
+        report.record("Bad", CheckOutcome::Fail("broken".to_string()));
+
+        assert_eq!(report.exit_code(), 1);
+        assert!(report.summary().ends_with("1 passed, 1 failed, 0 skipped"));
+        assert!(report.summary().contains("[FAIL] Bad: broken"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::time::Duration;
+use rodio::Source;
+use serde::{Serialize, Deserialize};
+use crate::message_system::{MetricKind, Severity};
+use crate::tts;
+
+/// A short synthesized tone played instead of, or alongside, a spoken warning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CueTone {
+    /// Three climbing notes, for something ramping up
+    Rising,
+    /// Three falling notes, for something draining away
+    Falling,
+    /// One low, dull hit, for something filling up
+    Thud,
+    /// A single short high note
+    Blip,
+    /// Two alternating notes repeated, for critical alerts
+    Alarm,
+}
+
+impl CueTone {
+    pub const ALL: [CueTone; 5] = [CueTone::Rising, CueTone::Falling, CueTone::Thud, CueTone::Blip, CueTone::Alarm];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CueTone::Rising => "Rising",
+            CueTone::Falling => "Falling",
+            CueTone::Thud => "Thud",
+            CueTone::Blip => "Blip",
+            CueTone::Alarm => "Alarm",
+        }
+    }
+
+    /// The notes of the tone in order, as (frequency in Hz, length in milliseconds)
+    pub fn notes(&self) -> &'static [(f32, u64)] {
+        match self {
+            CueTone::Rising => &[(440.0, 90), (554.0, 90), (659.0, 140)],
+            CueTone::Falling => &[(659.0, 90), (554.0, 90), (440.0, 140)],
+            CueTone::Thud => &[(70.0, 180)],
+            CueTone::Blip => &[(1200.0, 60)],
+            CueTone::Alarm => &[(880.0, 120), (660.0, 120), (880.0, 120), (660.0, 120)],
+        }
+    }
+}
+
+/// The tone played for one metric at one severity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoundCue {
+    pub metric: MetricKind,
+    pub severity: Severity,
+    pub tone: CueTone,
+}
+
+/// Non-verbal audio cues for warnings, independent of speech.
+///
+/// A critical alert without a cue of its own falls back to the metric's warning cue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundCueSettings {
+    pub enabled: bool,
+    /// Playback volume, 0.0 to 1.0
+    pub volume: f32,
+    /// Metrics whose cues are switched off
+    pub muted: Vec<MetricKind>,
+    pub cues: Vec<SoundCue>,
+}
+
+impl Default for SoundCueSettings {
+    fn default() -> Self {
+        let cue = |metric, severity, tone| SoundCue { metric, severity, tone };
+        Self {
+            enabled: false,
+            volume: 0.4,
+            muted: Vec::new(),
+            cues: vec![
+                cue(MetricKind::Cpu, Severity::Warning, CueTone::Rising),
+                cue(MetricKind::Memory, Severity::Warning, CueTone::Blip),
+                cue(MetricKind::Memory, Severity::Critical, CueTone::Alarm),
+                cue(MetricKind::Disk, Severity::Warning, CueTone::Thud),
+                cue(MetricKind::Network, Severity::Warning, CueTone::Falling),
+            ],
+        }
+    }
+}
+
+impl SoundCueSettings {
+    pub fn is_muted(&self, metric: MetricKind) -> bool {
+        self.muted.contains(&metric)
+    }
+
+    pub fn set_muted(&mut self, metric: MetricKind, muted: bool) {
+        self.muted.retain(|kind| *kind != metric);
+        if muted {
+            self.muted.push(metric);
+        }
+    }
+
+    /// The tone set for exactly `metric` at `severity`, without fallback
+    pub fn get(&self, metric: MetricKind, severity: Severity) -> Option<CueTone> {
+        self.cues.iter()
+            .find(|cue| cue.metric == metric && cue.severity == severity)
+            .map(|cue| cue.tone)
+    }
+
+    /// Sets the tone for `metric` at `severity`; None removes it
+    pub fn set(&mut self, metric: MetricKind, severity: Severity, tone: Option<CueTone>) {
+        self.cues.retain(|cue| cue.metric != metric || cue.severity != severity);
+        if let Some(tone) = tone {
+            self.cues.push(SoundCue { metric, severity, tone });
+        }
+    }
+
+    /// The tone to play for `metric` at `severity`, if cues are on and the metric isn't muted
+    pub fn cue_for(&self, metric: MetricKind, severity: Severity) -> Option<CueTone> {
+        if !self.enabled || self.is_muted(metric) {
+            return None;
+        }
+        self.get(metric, severity).or_else(|| match severity {
+            Severity::Critical => self.get(metric, Severity::Warning),
+            _ => None,
+        })
+    }
+
+    /// The cue for the most severe of `raised`; the earliest wins a tie
+    pub fn most_severe(&self, raised: &[(MetricKind, Severity)]) -> Option<CueTone> {
+        let mut best: Option<(Severity, CueTone)> = None;
+        for (metric, severity) in raised {
+            if let Some(tone) = self.cue_for(*metric, *severity) {
+                if best.is_none_or(|(best_severity, _)| *severity > best_severity) {
+                    best = Some((*severity, tone));
+                }
+            }
+        }
+        best.map(|(_, tone)| tone)
+    }
+}
+
+/// Plays `tone` on a background thread through `device` (None = system default)
+pub fn play(tone: CueTone, volume: f32, device: Option<String>) {
+    let volume = volume.clamp(0.0, 1.0);
+    std::thread::spawn(move || {
+        let result = tts::open_with_recovery(tts::open_device, device.as_deref(), tts::list_output_devices)
+            .and_then(|((_stream, handle), _)| {
+                let sink = rodio::Sink::try_new(&handle)?;
+                for (frequency, millis) in tone.notes() {
+                    let length = Duration::from_millis(*millis);
+                    sink.append(rodio::source::SineWave::new(*frequency)
+                        .take_duration(length)
+                        .fade_in(Duration::from_millis(5))
+                        .amplify(volume));
+                }
+                sink.sleep_until_end();
+                Ok(())
+            });
+        if let Err(e) = result {
+            eprintln!("Failed to play {} sound cue: {}", tone.label(), e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cue_selected_per_metric_and_severity() {
+        let mut cues = SoundCueSettings { enabled: true, ..SoundCueSettings::default() };
+        assert_eq!(cues.cue_for(MetricKind::Cpu, Severity::Warning), Some(CueTone::Rising));
+        assert_eq!(cues.cue_for(MetricKind::Disk, Severity::Warning), Some(CueTone::Thud));
+        assert_eq!(cues.cue_for(MetricKind::Memory, Severity::Critical), Some(CueTone::Alarm));
+        // No critical CPU cue of its own: the warning cue stands in
+        assert_eq!(cues.cue_for(MetricKind::Cpu, Severity::Critical), Some(CueTone::Rising));
+        assert_eq!(cues.cue_for(MetricKind::Cpu, Severity::Info), None);
+
+        assert_eq!(
+            cues.most_severe(&[(MetricKind::Disk, Severity::Warning), (MetricKind::Memory, Severity::Critical)]),
+            Some(CueTone::Alarm),
+        );
+        assert_eq!(cues.most_severe(&[(MetricKind::Disk, Severity::Warning), (MetricKind::Cpu, Severity::Warning)]), Some(CueTone::Thud));
+
+        cues.set_muted(MetricKind::Disk, true);
+        assert_eq!(cues.cue_for(MetricKind::Disk, Severity::Warning), None);
+        cues.set(MetricKind::Cpu, Severity::Warning, Some(CueTone::Blip));
+        assert_eq!(cues.cue_for(MetricKind::Cpu, Severity::Warning), Some(CueTone::Blip));
+
+        cues.enabled = false;
+        assert_eq!(cues.most_severe(&[(MetricKind::Cpu, Severity::Warning)]), None);
+    }
+}
@@ -0,0 +1,192 @@
+/// Units written after a number and how they are said, longest first so "GB/s" wins over "GB"
+const UNITS: [(&str, &str, &str); 20] = [
+    ("KiB/s", "kibibyte per second", "kibibytes per second"),
+    ("MiB/s", "mebibyte per second", "mebibytes per second"),
+    ("GiB/s", "gibibyte per second", "gibibytes per second"),
+    ("kB/s", "kilobyte per second", "kilobytes per second"),
+    ("KB/s", "kilobyte per second", "kilobytes per second"),
+    ("MB/s", "megabyte per second", "megabytes per second"),
+    ("GB/s", "gigabyte per second", "gigabytes per second"),
+    ("B/s", "byte per second", "bytes per second"),
+    ("KiB", "kibibyte", "kibibytes"),
+    ("MiB", "mebibyte", "mebibytes"),
+    ("GiB", "gibibyte", "gibibytes"),
+    ("TiB", "tebibyte", "tebibytes"),
+    ("kB", "kilobyte", "kilobytes"),
+    ("KB", "kilobyte", "kilobytes"),
+    ("MB", "megabyte", "megabytes"),
+    ("GB", "gigabyte", "gigabytes"),
+    ("TB", "terabyte", "terabytes"),
+    ("ms", "millisecond", "milliseconds"),
+    ("°C", "degree", "degrees"),
+    ("%", "percent", "percent"),
+];
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+const SCALES: [(u64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// A whole number in words, e.g. 73 as "seventy-three" and 1024 as "one thousand twenty-four"
+pub fn number_to_words(n: u64) -> String {
+    for (scale, name) in SCALES {
+        if n >= scale {
+            let rest = n % scale;
+            let head = format!("{} {}", number_to_words(n / scale), name);
+            return if rest == 0 { head } else { format!("{} {}", head, number_to_words(rest)) };
+        }
+    }
+    if n >= 100 {
+        let rest = n % 100;
+        let head = format!("{} hundred", ONES[(n / 100) as usize]);
+        return if rest == 0 { head } else { format!("{} {}", head, number_to_words(rest)) };
+    }
+    if n >= 20 {
+        let tens = TENS[(n / 10) as usize];
+        return match n % 10 {
+            0 => tens.to_string(),
+            ones => format!("{}-{}", tens, ONES[ones as usize]),
+        };
+    }
+    ONES[n as usize].to_string()
+}
+
+/// `value` rounded the way it would be said aloud, and whether it is exactly one.
+///
+/// Whole numbers are said as they are. Below ten a tenth still matters, so it is
+/// kept ("two point three", "seven and a half"); above that, "about" and the
+/// nearest whole number reads better than a decimal.
+pub fn spoken_value(value: f64) -> (String, bool) {
+    let whole = value.round();
+    if (value - whole).abs() < 0.05 {
+        return (number_to_words(whole as u64), whole == 1.0);
+    }
+    if value < 10.0 {
+        let tenths = (value * 10.0).round() as u64;
+        let words = number_to_words(tenths / 10);
+        return match tenths % 10 {
+            5 if tenths >= 10 => (format!("{} and a half", words), false),
+            tenth => (format!("{} point {}", words, ONES[tenth as usize]), false),
+        };
+    }
+    (format!("about {}", number_to_words(whole as u64)), false)
+}
+
+/// The unit written at the start of `rest`, if it ends there rather than running into a word
+fn unit_at(rest: &str) -> Option<(usize, &'static str, &'static str)> {
+    UNITS.iter()
+        .find(|(written, _, _)| {
+            rest.starts_with(written)
+                && !rest[written.len()..].chars().next().is_some_and(char::is_alphanumeric)
+        })
+        .map(|(written, one, many)| (written.len(), *one, *many))
+}
+
+/// Rewrites numbers in `text` as words for speech: "73.4%" becomes "about seventy-three percent"
+/// and "8.0 GB" becomes "eight gigabytes".
+///
+/// Numbers that are part of a name or version, like "CPU0" or "1.2.3", are left alone.
+pub fn humanize_numbers(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = rest.chars().next() {
+        let attached = previous.is_some_and(|p| p.is_alphanumeric() || p == '.' || p == '_');
+        if !c.is_ascii_digit() || attached {
+            output.push(c);
+            previous = Some(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        // Digits with optional thousands separators, then an optional decimal part
+        let mut end = rest.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(rest.len());
+        while rest[end..].starts_with(',')
+            && rest.get(end + 1..end + 4).is_some_and(|group| group.bytes().all(|b| b.is_ascii_digit()))
+            && !rest[end + 4..].starts_with(|ch: char| ch.is_ascii_digit())
+        {
+            end += 4;
+        }
+        if rest[end..].starts_with('.') && rest[end + 1..].starts_with(|ch: char| ch.is_ascii_digit()) {
+            end += 1 + rest[end + 1..].find(|ch: char| !ch.is_ascii_digit()).unwrap_or(rest.len() - end - 1);
+        }
+        let number = &rest[..end];
+        let after = &rest[end..];
+        let space = if after.starts_with(' ') { 1 } else { 0 };
+        let unit = unit_at(&after[space..]);
+        let runs_on = after.starts_with(|ch: char| ch.is_alphanumeric())
+            || (after.starts_with('.') && after[1..].starts_with(|ch: char| ch.is_ascii_digit()));
+
+        match number.replace(',', "").parse::<f64>() {
+            Ok(value) if !runs_on || unit.is_some() => {
+                let (words, is_one) = spoken_value(value);
+                output.push_str(&words);
+                rest = match unit {
+                    Some((length, one, many)) => {
+                        output.push(' ');
+                        output.push_str(if is_one { one } else { many });
+                        &after[space + length..]
+                    }
+                    None => after,
+                };
+            }
+            _ => {
+                output.push_str(number);
+                rest = after;
+            }
+        }
+        previous = output.chars().last();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_in_words() {
+        assert_eq!(number_to_words(0), "zero");
+        assert_eq!(number_to_words(13), "thirteen");
+        assert_eq!(number_to_words(73), "seventy-three");
+        assert_eq!(number_to_words(100), "one hundred");
+        assert_eq!(number_to_words(1024), "one thousand twenty-four");
+        assert_eq!(number_to_words(2_500_000), "two million five hundred thousand");
+    }
+
+    #[test]
+    fn test_percentages_sizes_and_rates_read_naturally() {
+        // Percentages
+        assert_eq!(humanize_numbers("CPU at 73.4%"), "CPU at about seventy-three percent");
+        assert_eq!(humanize_numbers("Memory usage: 50.0%"), "Memory usage: fifty percent");
+        assert_eq!(humanize_numbers("5.5% free"), "five and a half percent free");
+        assert_eq!(humanize_numbers("load 2.3 %"), "load two point three percent");
+
+        // Whole and fractional sizes
+        assert_eq!(humanize_numbers("8.0 GB used"), "eight gigabytes used");
+        assert_eq!(humanize_numbers("1.0 GB"), "one gigabyte");
+        assert_eq!(humanize_numbers("7.5 GB"), "seven and a half gigabytes");
+        assert_eq!(humanize_numbers("3.2GB of 15.9 GiB"), "three point two gigabytes of about sixteen gibibytes");
+
+        // Rates, abbreviated or already spelled out
+        assert_eq!(humanize_numbers("RX: 12.4 MB/s"), "RX: about twelve megabytes per second");
+        assert_eq!(humanize_numbers("1.5 megabytes per second"), "one and a half megabytes per second");
+        assert_eq!(humanize_numbers("1,024 kB/s"), "one thousand twenty-four kilobytes per second");
+
+        // Names, versions and plain words are left alone
+        assert_eq!(humanize_numbers("CPU0 and nvme0n1"), "CPU0 and nvme0n1");
+        assert_eq!(humanize_numbers("version 1.2.3"), "version 1.2.3");
+        assert_eq!(humanize_numbers("8 cores, 16.0 gigs."), "eight cores, sixteen gigs.");
+        assert_eq!(humanize_numbers("No numbers here"), "No numbers here");
+    }
+}
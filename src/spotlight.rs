@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+use crate::message_system::MetricKind;
+
+/// A large spotlight rotating through the metrics on its own, for wall-mounted ambient displays
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpotlightSettings {
+    pub enabled: bool,
+    /// How long each metric stays in the spotlight, in seconds
+    pub dwell_secs: u64,
+    /// Metrics taking part, in rotation order; empty rotates through every displayed metric
+    pub metrics: Vec<MetricKind>,
+    /// Have the AI read out each metric as the spotlight moves to it
+    pub narrate: bool,
+}
+
+impl Default for SpotlightSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dwell_secs: 10,
+            metrics: Vec::new(),
+            narrate: false,
+        }
+    }
+}
+
+impl SpotlightSettings {
+    pub fn dwell(&self) -> Duration {
+        Duration::from_secs(self.dwell_secs)
+    }
+}
+
+/// Whose turn it is `elapsed` into a rotation through `metrics`, `dwell` each
+pub fn rotation_at(metrics: &[MetricKind], dwell: Duration, elapsed: Duration) -> Option<MetricKind> {
+    if metrics.is_empty() {
+        return None;
+    }
+    let turn = (elapsed.as_secs_f64() / dwell.as_secs_f64().max(0.001)) as usize;
+    Some(metrics[turn % metrics.len()])
+}
+
+/// The metric in the spotlight: whichever is in alarm, otherwise the next in the rotation
+#[derive(Debug)]
+pub struct Spotlight {
+    started: Instant,
+    shown: Option<MetricKind>,
+}
+
+impl Spotlight {
+    pub fn new(now: Instant) -> Self {
+        Self { started: now, shown: None }
+    }
+
+    /// Moves the spotlight on if it is time to, or to the first metric in `alarming`
+    /// whether or not it takes part in the rotation; true when it moved
+    pub fn update(&mut self, metrics: &[MetricKind], dwell: Duration, alarming: &[MetricKind], now: Instant) -> bool {
+        let shown = alarming.first().copied()
+            .or_else(|| rotation_at(metrics, dwell, now.saturating_duration_since(self.started)));
+        let moved = shown != self.shown;
+        self.shown = shown;
+        moved
+    }
+
+    pub fn shown(&self) -> Option<MetricKind> {
+        self.shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_schedule_and_warning_override() {
+        let secs = Duration::from_secs;
+        let metrics = [MetricKind::Cpu, MetricKind::Memory, MetricKind::Network];
+        assert_eq!(rotation_at(&metrics, secs(10), secs(0)), Some(MetricKind::Cpu));
+        assert_eq!(rotation_at(&metrics, secs(10), secs(9)), Some(MetricKind::Cpu));
+        assert_eq!(rotation_at(&metrics, secs(10), secs(10)), Some(MetricKind::Memory));
+        assert_eq!(rotation_at(&metrics, secs(10), secs(25)), Some(MetricKind::Network));
+        assert_eq!(rotation_at(&metrics, secs(10), secs(30)), Some(MetricKind::Cpu), "wraps around");
+        assert_eq!(rotation_at(&[], secs(10), secs(30)), None);
+
+        let start = Instant::now();
+        let mut spotlight = Spotlight::new(start);
+        assert!(spotlight.update(&metrics, secs(10), &[], start));
+        assert_eq!(spotlight.shown(), Some(MetricKind::Cpu));
+        assert!(!spotlight.update(&metrics, secs(10), &[], start + secs(5)), "still CPU's turn");
+        assert!(spotlight.update(&metrics, secs(10), &[], start + secs(10)));
+        assert_eq!(spotlight.shown(), Some(MetricKind::Memory));
+
+        // An alarm takes the spotlight, even for a metric outside the rotation, and holds it
+        assert!(spotlight.update(&metrics, secs(10), &[MetricKind::Disk], start + secs(12)));
+        assert_eq!(spotlight.shown(), Some(MetricKind::Disk));
+        assert!(!spotlight.update(&metrics, secs(10), &[MetricKind::Disk], start + secs(35)));
+        assert_eq!(spotlight.shown(), Some(MetricKind::Disk));
+
+        // Once it clears, the rotation picks up where the clock says it is
+        assert!(spotlight.update(&metrics, secs(10), &[], start + secs(40)));
+        assert_eq!(spotlight.shown(), Some(MetricKind::Memory));
+    }
+}
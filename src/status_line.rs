@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use crate::system_monitor::{MetricsSnapshot, finite_percent, rate_per_sec};
+
+/// Template used by `--print` when none is given
+pub const DEFAULT_PRINT_TEMPLATE: &str = "%cpu% %mem% %disk% %rx% %tx%";
+
+/// Placeholder values for one status line.
+///
+/// Values are bare numbers without units so scripts can split on whitespace:
+/// `cpu`, `mem` and `disk` are percentages, `rx` and `tx` are KB/s and
+/// `rx_total` and `tx_total` are MB received and sent since boot, and
+/// `rx_bytes` and `tx_bytes` the same totals as exact byte counts.
+pub fn snapshot_values(previous: &MetricsSnapshot, current: &MetricsSnapshot, disk_usage: f32) -> BTreeMap<&'static str, String> {
+    let elapsed = current.since_previous
+        .unwrap_or_else(|| current.taken_at.saturating_duration_since(previous.taken_at));
+    let rate = |now: u64, before: u64| rate_per_sec(before, now, elapsed).map_or(0.0, |rate| rate / 1_000.0);
+
+    let mut values = BTreeMap::new();
+    values.insert("cpu", format!("{:.1}", finite_percent(current.cpu_average)));
+    values.insert("mem", format!("{:.1}", finite_percent(current.memory_usage)));
+    values.insert("disk", format!("{:.1}", finite_percent(disk_usage)));
+    values.insert("rx", format!("{:.1}", rate(current.network_rx_total, previous.network_rx_total)));
+    values.insert("tx", format!("{:.1}", rate(current.network_tx_total, previous.network_tx_total)));
+    values.insert("rx_total", format!("{:.1}", current.network_rx_total as f64 / 1_000_000.0));
+    values.insert("tx_total", format!("{:.1}", current.network_tx_total as f64 / 1_000_000.0));
+    values.insert("rx_bytes", current.network_rx_total.to_string());
+    values.insert("tx_bytes", current.network_tx_total.to_string());
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_system::{fill_placeholders, PlaceholderStyle};
+    use std::time::{Duration, Instant};
+
+    fn snapshot(taken_at: Instant, since_previous: Option<Duration>, rx: u64, tx: u64) -> MetricsSnapshot {
+        MetricsSnapshot {
+            taken_at,
+            since_previous,
+            sequence: 1,
+            cpu_average: 42.3,
+            memory_usage: 63.0,
+            network_rx_total: rx,
+            network_tx_total: tx,
+            updated: MetricsSnapshot::all_updated_at(taken_at),
+        }
+    }
+
+    #[test]
+    fn test_percent_placeholders_substitute() {
+        let mut values = BTreeMap::new();
+        values.insert("cpu", "12.5".to_string());
+        values.insert("mem", "40.0".to_string());
+
+        assert_eq!(fill_placeholders("%cpu% %mem%", &values, PlaceholderStyle::Percent), "12.5 40.0");
+        assert_eq!(fill_placeholders("CPU %cpu%%%", &values, PlaceholderStyle::Percent), "CPU 12.5%");
+        assert_eq!(fill_placeholders("%gpu% %cpu%", &values, PlaceholderStyle::Percent), "%gpu% 12.5");
+        assert_eq!(fill_placeholders("50% %cpu", &values, PlaceholderStyle::Percent), "50% %cpu");
+        assert_eq!(fill_placeholders("no placeholders", &values, PlaceholderStyle::Percent), "no placeholders");
+    }
+
+    #[test]
+    fn test_snapshot_values_compute_rates() {
+        let start = Instant::now();
+        let previous = snapshot(start, None, 1_000_000, 500_000);
+        let current = snapshot(start + Duration::from_millis(500), Some(Duration::from_millis(500)), 1_100_000, 550_000);
+
+        let values = snapshot_values(&previous, &current, 71.04);
+        assert_eq!(
+            fill_placeholders(DEFAULT_PRINT_TEMPLATE, &values, PlaceholderStyle::Percent),
+            "42.3 63.0 71.0 200.0 100.0"
+        );
+        assert_eq!(values["rx_total"], "1.1");
+        assert_eq!(values["tx_bytes"], "550000");
+
+        // Two samples at the same instant, with garbage percentages, still print plain numbers
+        let mut broken = snapshot(start, Some(Duration::ZERO), 2_000_000, 0);
+        broken.cpu_average = f32::NAN;
+        let values = snapshot_values(&current, &broken, f32::INFINITY);
+        assert_eq!(fill_placeholders(DEFAULT_PRINT_TEMPLATE, &values, PlaceholderStyle::Percent), "0.0 63.0 0.0 0.0 0.0");
+    }
+}
@@ -1,35 +1,507 @@
-use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworkExt, NetworksExt};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+use sysinfo::{System, SystemExt, CpuExt, ComponentExt, DiskExt, NetworkExt, NetworksExt, ProcessExt, PidExt};
+use serde::{Serialize, Deserialize};
+use crate::disk_latency::DiskLatencyTracker;
+use crate::virtualization::{self, CpuStealTracker};
+use crate::message_system::{MetricKind, SystemData};
+
+/// How often the monitor re-samples the system unless configured otherwise
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Readings closer together than this are too noisy to turn into a rate
+pub const MIN_RATE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A percentage safe to draw or speak: NaN and infinities become 0, the rest is clamped to 0-100
+pub fn finite_percent(value: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(0.0, 100.0)
+    } else {
+        0.0
+    }
+}
+
+/// `part` as a percentage of `whole`; 0 when there is no whole, as on some VMs and virtual filesystems
+pub fn percent_of(part: u64, whole: u64) -> f32 {
+    if whole == 0 {
+        return 0.0;
+    }
+    finite_percent((part as f64 / whole as f64 * 100.0) as f32)
+}
+
+/// Per-second rate between two counter readings `elapsed` apart.
+///
+/// None when the readings are too close together to be meaningful; a counter
+/// that went backwards (an interface reset) counts as no traffic.
+pub fn rate_per_sec(before: u64, now: u64, elapsed: Duration) -> Option<f64> {
+    if elapsed < MIN_RATE_INTERVAL {
+        return None;
+    }
+    Some(now.saturating_sub(before) as f64 / elapsed.as_secs_f64())
+}
+
+/// Bytes per mebibyte, the unit the panels show transfer rates in
+pub const BYTES_PER_MB: f64 = 1_048_576.0;
+
+/// Whether sizes count in powers of 1024 (KiB, MiB, GiB) or 1000 (kB, MB, GB)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ByteUnits {
+    #[default]
+    Binary,
+    Decimal,
+}
+
+impl ByteUnits {
+    pub const ALL: [ByteUnits; 2] = [ByteUnits::Binary, ByteUnits::Decimal];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ByteUnits::Binary => "Binary (KiB, MiB, GiB)",
+            ByteUnits::Decimal => "Decimal (kB, MB, GB)",
+        }
+    }
+
+    fn base(&self) -> f64 {
+        match self {
+            ByteUnits::Binary => 1024.0,
+            ByteUnits::Decimal => 1000.0,
+        }
+    }
+
+    fn suffixes(&self) -> [&'static str; 4] {
+        match self {
+            ByteUnits::Binary => ["B", "KiB", "MiB", "GiB"],
+            ByteUnits::Decimal => ["B", "kB", "MB", "GB"],
+        }
+    }
+}
+
+/// A size in the largest unit that keeps it at 1 or more, e.g. "512.0 KiB" or "1.2 GB",
+/// or in raw mode the exact number of bytes
+pub fn format_bytes(bytes: u64, units: ByteUnits, raw: bool) -> String {
+    let suffixes = units.suffixes();
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while !raw && value >= units.base() && unit < suffixes.len() - 1 {
+        value /= units.base();
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, suffixes[unit])
+    }
+}
+
+/// A transfer rate for display: MB/s to one decimal, or in raw mode the exact bytes per second
+pub fn format_rate(bytes_per_sec: u64, raw: bool) -> String {
+    if raw {
+        format!("{} B/s", bytes_per_sec)
+    } else {
+        format!("{:.1} MB/s", bytes_per_sec as f64 / BYTES_PER_MB)
+    }
+}
+
+/// A percentage for display: rounded to `decimals`, or in raw mode every digit of the
+/// reading, printed so that it parses back to the same value
+pub fn format_percent(percent: f32, decimals: usize, raw: bool) -> String {
+    if raw {
+        format!("{}%", percent)
+    } else {
+        format!("{:.*}%", decimals, percent)
+    }
+}
+
+/// Headline numbers from one refresh of the system
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSnapshot {
+    pub taken_at: Instant,
+    /// Time since the previous snapshot, for rate calculations
+    pub since_previous: Option<Duration>,
+    /// Number of snapshots taken so far, starting at 1
+    pub sequence: u64,
+    pub cpu_average: f32,
+    pub memory_usage: f32,
+    pub network_rx_total: u64,
+    pub network_tx_total: u64,
+    /// When each metric last got a real reading; a source that returns nothing keeps its old time
+    pub updated: HashMap<MetricKind, Instant>,
+}
+
+impl MetricsSnapshot {
+    /// Every metric read at `taken_at`
+    pub fn all_updated_at(taken_at: Instant) -> HashMap<MetricKind, Instant> {
+        MetricKind::ALL.into_iter().map(|kind| (kind, taken_at)).collect()
+    }
+
+    /// Whether `kind` hasn't had a real reading for longer than `max_age`
+    pub fn is_stale(&self, kind: MetricKind, now: Instant, max_age: Duration) -> bool {
+        self.updated.get(&kind)
+            .is_none_or(|updated| now.saturating_duration_since(*updated) > max_age)
+    }
+}
+
+/// One running process, as of the last refresh
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// Resident memory in bytes
+    pub memory: u64,
+    /// Share of the whole machine, like the CPU panel
+    pub cpu: f32,
+}
+
+/// Column the process list is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProcessSort {
+    #[default]
+    Memory,
+    Cpu,
+    Name,
+}
+
+/// Orders `processes` by `sort`: largest memory or CPU first, names alphabetically.
+///
+/// Memory compares raw bytes, never the formatted text, so 900 MiB sorts below 1.2 GiB.
+pub fn sort_processes(processes: &mut [ProcessInfo], sort: ProcessSort) {
+    match sort {
+        ProcessSort::Memory => processes.sort_by_key(|process| std::cmp::Reverse(process.memory)),
+        ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu)),
+        ProcessSort::Name => processes.sort_by_key(|process| process.name.to_lowercase()),
+    }
+}
+
+/// The busiest process, if its name is on the `expected` list.
+///
+/// Names match ignoring case and a trailing ".exe", so "ffmpeg" also covers "FFmpeg.exe".
+pub fn expected_cpu_consumer<'a>(processes: &'a [ProcessInfo], expected: &[String]) -> Option<&'a ProcessInfo> {
+    let busiest = processes.iter()
+        .filter(|process| process.cpu > 0.0)
+        .max_by(|a, b| a.cpu.total_cmp(&b.cpu))?;
+    let normalize = |name: &str| {
+        let name = name.trim().to_lowercase();
+        name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+    };
+    let name = normalize(&busiest.name);
+    expected.iter()
+        .any(|entry| !entry.trim().is_empty() && normalize(entry) == name)
+        .then_some(busiest)
+}
+
+/// A metric that moved sharply between two consecutive snapshots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricChange {
+    pub metric: MetricKind,
+    /// Percentage points, positive when the metric rose
+    pub delta: f32,
+}
+
+/// CPU and memory swings of at least `significance` percentage points from `previous` to `current`, largest first
+pub fn diff_snapshots(previous: &MetricsSnapshot, current: &MetricsSnapshot, significance: f32) -> Vec<MetricChange> {
+    let mut changes: Vec<MetricChange> = [
+        (MetricKind::Cpu, previous.cpu_average, current.cpu_average),
+        (MetricKind::Memory, previous.memory_usage, current.memory_usage),
+    ]
+        .into_iter()
+        .filter(|(_, before, after)| before.is_finite() && after.is_finite())
+        .map(|(metric, before, after)| MetricChange { metric, delta: finite_percent(after) - finite_percent(before) })
+        .filter(|change| change.delta.abs() >= significance)
+        .collect();
+    changes.sort_by(|a, b| b.delta.abs().total_cmp(&a.delta.abs()));
+    changes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceKind {
+    Disk,
+    NetworkInterface,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceChange {
+    Added,
+    Removed,
+}
+
+/// A disk or network interface that appeared or disappeared between two refreshes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceEvent {
+    pub kind: DeviceKind,
+    pub change: DeviceChange,
+    pub name: String,
+}
+
+/// Devices present in `current` but not `previous` (added) and the reverse (removed)
+pub fn diff_devices(kind: DeviceKind, previous: &BTreeSet<String>, current: &BTreeSet<String>) -> Vec<DeviceEvent> {
+    let added = current.difference(previous).map(|name| DeviceEvent {
+        kind,
+        change: DeviceChange::Added,
+        name: name.clone(),
+    });
+    let removed = previous.difference(current).map(|name| DeviceEvent {
+        kind,
+        change: DeviceChange::Removed,
+        name: name.clone(),
+    });
+    added.chain(removed).collect()
+}
+
+/// Whether the CPU panel lists logical CPUs (hardware threads) or physical cores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CoreView {
+    #[default]
+    Logical,
+    Physical,
+}
+
+impl CoreView {
+    pub const ALL: [CoreView; 2] = [CoreView::Logical, CoreView::Physical];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CoreView::Logical => "Logical",
+            CoreView::Physical => "Physical",
+        }
+    }
+}
+
+/// Averages logical CPU usage per physical core; `core_of[i]` is the physical core logical CPU `i` belongs to
+pub fn group_by_physical_core(usage: &[(String, f32)], core_of: &[usize]) -> Vec<(String, f32)> {
+    let mut cores: BTreeMap<usize, (f32, usize)> = BTreeMap::new();
+    for (i, (_, value)) in usage.iter().enumerate() {
+        let core = core_of.get(i).copied().unwrap_or(i);
+        let entry = cores.entry(core).or_insert((0.0, 0));
+        entry.0 += finite_percent(*value);
+        entry.1 += 1;
+    }
+    cores.into_values()
+        .enumerate()
+        .map(|(n, (sum, threads))| {
+            let label = if threads > 1 { format!("Core{} ({} threads)", n, threads) } else { format!("Core{}", n) };
+            (label, sum / threads as f32)
+        })
+        .collect()
+}
+
+/// Best-effort guess when the platform doesn't expose topology: siblings numbered `core`, `core + physical`, ...
+pub fn interleaved_topology(logical: usize, physical: Option<usize>) -> Vec<usize> {
+    let physical = physical.filter(|count| (1..=logical).contains(count)).unwrap_or(logical.max(1));
+    (0..logical).map(|i| i % physical).collect()
+}
+
+/// Physical core of each logical CPU, from sysfs where available
+fn core_topology(logical: usize, physical: Option<usize>) -> Vec<usize> {
+    read_core_topology(logical).unwrap_or_else(|| interleaved_topology(logical, physical))
+}
+
+#[cfg(target_os = "linux")]
+fn read_core_topology(logical: usize) -> Option<Vec<usize>> {
+    let read = |cpu: usize, file: &str| -> Option<u32> {
+        std::fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/topology/{}", cpu, file)).ok()?.trim().parse().ok()
+    };
+    // (package, core id) pairs aren't dense, so number them in order of appearance
+    let mut indices: BTreeMap<(u32, u32), usize> = BTreeMap::new();
+    (0..logical)
+        .map(|cpu| {
+            let key = (read(cpu, "physical_package_id")?, read(cpu, "core_id")?);
+            let next = indices.len();
+            Some(*indices.entry(key).or_insert(next))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_core_topology(_logical: usize) -> Option<Vec<usize>> {
+    None
+}
 
 pub struct SystemMonitor {
     sys: System,
+    refresh_interval: Duration,
+    snapshot: Option<MetricsSnapshot>,
+    known_disks: Option<BTreeSet<String>>,
+    known_interfaces: Option<BTreeSet<String>>,
+    device_events: Vec<DeviceEvent>,
+    disk_latency: DiskLatencyTracker,
+    cpu_steal: CpuStealTracker,
+    /// Hypervisor this machine runs under, None on bare metal
+    virtualization: Option<String>,
+    /// Physical core of each logical CPU
+    core_of: Vec<usize>,
+    /// This process's CPU% and resident memory as of the last refresh
+    own_usage: Option<(f32, u64)>,
 }
 
 impl SystemMonitor {
     pub fn new() -> Self {
         let mut sys = System::new_all();
         sys.refresh_all();
-        SystemMonitor { sys }
+        let core_of = core_topology(sys.cpus().len(), sys.physical_core_count());
+        SystemMonitor {
+            sys,
+            core_of,
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            snapshot: None,
+            known_disks: None,
+            known_interfaces: None,
+            device_events: Vec::new(),
+            disk_latency: DiskLatencyTracker::new(),
+            cpu_steal: CpuStealTracker::new(),
+            virtualization: virtualization::detect_virtualization(),
+            own_usage: None,
+        }
     }
 
     pub fn refresh(&mut self) {
         self.sys.refresh_all();
     }
 
+    pub fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = interval;
+    }
+
+    /// Takes a new snapshot if the refresh interval has elapsed; returns true if it did
+    pub fn refresh_if_due(&mut self, now: Instant) -> bool {
+        let due = self.snapshot.as_ref()
+            .is_none_or(|snapshot| now.saturating_duration_since(snapshot.taken_at) >= self.refresh_interval);
+        if due {
+            self.refresh_now(now);
+        }
+        due
+    }
+
+    /// Re-samples immediately, ignoring the refresh interval
+    pub fn refresh_now(&mut self, now: Instant) -> &MetricsSnapshot {
+        self.sys.refresh_all();
+
+        let cpus = self.sys.cpus();
+        let cpu_read = !cpus.is_empty();
+        let cpu_average = if cpus.is_empty() {
+            0.0
+        } else {
+            cpus.iter().map(|cpu| finite_percent(cpu.cpu_usage())).sum::<f32>() / cpus.len() as f32
+        };
+        let total_memory = self.sys.total_memory();
+        let memory_usage = percent_of(self.sys.used_memory(), total_memory);
+        let (network_rx_total, network_tx_total) = self.sys.networks()
+            .iter()
+            .fold((0, 0), |(rx, tx), (_, data)| (rx + data.total_received(), tx + data.total_transmitted()));
+
+        self.detect_device_changes();
+        self.disk_latency.sample();
+        self.cpu_steal.sample();
+        self.own_usage = self.lookup_own_usage();
+
+        let previous = self.snapshot.as_ref();
+        let mut updated = previous.map(|p| p.updated.clone()).unwrap_or_default();
+        for (kind, fresh) in [
+            (MetricKind::Cpu, cpu_read),
+            (MetricKind::Memory, total_memory > 0),
+            (MetricKind::Disk, !self.sys.disks().is_empty()),
+            (MetricKind::Network, self.sys.networks().iter().next().is_some()),
+        ] {
+            if fresh {
+                updated.insert(kind, now);
+            }
+        }
+        let snapshot = MetricsSnapshot {
+            taken_at: now,
+            since_previous: previous.map(|p| now.saturating_duration_since(p.taken_at)),
+            sequence: previous.map_or(1, |p| p.sequence + 1),
+            cpu_average,
+            memory_usage,
+            network_rx_total,
+            network_tx_total,
+            updated,
+        };
+        self.snapshot.insert(snapshot)
+    }
+
+    /// Compares disks and interfaces against the previous refresh; the first refresh only records them
+    fn detect_device_changes(&mut self) {
+        // refresh_all only updates the devices already known, so look for new ones first
+        self.sys.refresh_disks_list();
+        self.sys.refresh_networks_list();
+        let disks: BTreeSet<String> = self.sys.disks()
+            .iter()
+            .map(|disk| disk.mount_point().to_string_lossy().into_owned())
+            .collect();
+        let interfaces: BTreeSet<String> = self.sys.networks()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect();
+        self.record_devices(disks, interfaces);
+    }
+
+    fn record_devices(&mut self, disks: BTreeSet<String>, interfaces: BTreeSet<String>) {
+        if let Some(previous) = &self.known_disks {
+            self.device_events.extend(diff_devices(DeviceKind::Disk, previous, &disks));
+        }
+        if let Some(previous) = &self.known_interfaces {
+            self.device_events.extend(diff_devices(DeviceKind::NetworkInterface, previous, &interfaces));
+        }
+        self.known_disks = Some(disks);
+        self.known_interfaces = Some(interfaces);
+    }
+
+    /// Device changes seen since the last call
+    pub fn take_device_events(&mut self) -> Vec<DeviceEvent> {
+        std::mem::take(&mut self.device_events)
+    }
+
+    /// The most recent snapshot, if one has been taken
+    pub fn snapshot(&self) -> Option<&MetricsSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// What the latest snapshot's refresh read, for warnings; nothing is sampled again.
+    ///
+    /// Disk and network are the first disk and interface, as the warnings have always used.
+    pub fn readings(&self) -> Option<SystemData> {
+        let snapshot = self.snapshot.as_ref()?;
+        let (disk_total, disk_available) = self.sys.disks().first()
+            .map(|disk| (disk.total_space(), disk.available_space()))
+            .unwrap_or_default();
+        let (network_rx, network_tx) = self.sys.networks().iter().next()
+            .map(|(_, data)| (data.received(), data.transmitted()))
+            .unwrap_or_default();
+        Some(SystemData {
+            cpu_usage: self.sys.cpus().iter()
+                .enumerate()
+                .map(|(i, cpu)| (format!("CPU{}", i), finite_percent(cpu.cpu_usage())))
+                .collect(),
+            memory_total: self.sys.total_memory(),
+            memory_used: self.sys.used_memory(),
+            memory_usage: snapshot.memory_usage,
+            disk_total,
+            disk_available,
+            disk_usage: percent_of(disk_total.saturating_sub(disk_available), disk_total),
+            network_rx,
+            network_tx,
+        })
+    }
+
     pub fn get_cpu_usage(&mut self) -> Vec<(String, f32)> {
         self.sys.refresh_cpu();
         self.sys.cpus()
             .iter()
             .enumerate()
-            .map(|(i, cpu)| (format!("CPU{}", i), cpu.cpu_usage()))
+            .map(|(i, cpu)| (format!("CPU{}", i), finite_percent(cpu.cpu_usage())))
             .collect()
     }
 
+    /// Usage per physical core, averaging its hardware threads
+    pub fn get_physical_cpu_usage(&mut self) -> Vec<(String, f32)> {
+        let usage = self.get_cpu_usage();
+        group_by_physical_core(&usage, &self.core_of)
+    }
+
     pub fn get_memory_usage(&mut self) -> (u64, u64, f32) {
         self.sys.refresh_memory();
         let total = self.sys.total_memory();
         let used = self.sys.used_memory();
-        let usage = (used as f32 / total as f32) * 100.0;
-        (total, used, usage)
+        (total, used, percent_of(used, total))
     }
 
     pub fn get_disk_usage(&mut self) -> Vec<(String, u64, u64, f32)> {
@@ -40,12 +512,70 @@ impl SystemMonitor {
                 let mount_point = disk.mount_point().to_string_lossy().into_owned();
                 let total = disk.total_space();
                 let available = disk.available_space();
-                let usage = ((total - available) as f32 / total as f32) * 100.0;
+                // Some virtual filesystems report no size, or more free space than total
+                let usage = percent_of(total.saturating_sub(available), total);
                 (mount_point, total, available, usage)
             })
             .collect()
     }
 
+    /// Per-device (name, average I/O service time in ms, in-flight requests) over the last refresh.
+    ///
+    /// Only Linux exposes these counters; elsewhere, and until two refreshes have happened, it's empty.
+    pub fn get_disk_latency(&self) -> Vec<(String, f32, u32)> {
+        self.disk_latency.latest().to_vec()
+    }
+
+    /// Hypervisor name when running in a virtual machine, e.g. "Amazon EC2"
+    pub fn virtualization(&self) -> Option<&str> {
+        self.virtualization.as_deref()
+    }
+
+    /// Share of CPU time the hypervisor took over the last refresh.
+    ///
+    /// None on bare metal, where nothing can be stolen, on platforms without
+    /// the counter, and until two refreshes have happened.
+    pub fn get_cpu_steal(&self) -> Option<f32> {
+        self.virtualization.as_ref().and(self.cpu_steal.latest())
+    }
+
+    /// The hottest temperature sensor in °C; None on machines without sensors sysinfo can read
+    pub fn max_temperature(&mut self) -> Option<f32> {
+        self.sys.refresh_components();
+        self.sys.components()
+            .iter()
+            .map(|component| component.temperature())
+            .filter(|temperature| temperature.is_finite() && *temperature > 0.0)
+            .reduce(f32::max)
+    }
+
+    /// The monitor's own CPU% (share of the whole machine, like the CPU panel) and resident memory in bytes
+    fn lookup_own_usage(&self) -> Option<(f32, u64)> {
+        let pid = sysinfo::get_current_pid().ok()?;
+        let process = self.sys.process(pid)?;
+        let cpu_count = self.sys.cpus().len().max(1) as f32;
+        Some((finite_percent(process.cpu_usage() / cpu_count), process.memory()))
+    }
+
+    /// Every process seen at the last refresh, in no particular order
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        let cpu_count = self.sys.cpus().len().max(1) as f32;
+        self.sys.processes()
+            .iter()
+            .map(|(pid, process)| ProcessInfo {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                memory: process.memory(),
+                cpu: finite_percent(process.cpu_usage() / cpu_count),
+            })
+            .collect()
+    }
+
+    /// What the monitor itself cost as of the last refresh, None until then or if the process can't be found
+    pub fn own_usage(&self) -> Option<(f32, u64)> {
+        self.own_usage
+    }
+
     pub fn get_network_usage(&mut self) -> Vec<(String, u64, u64)> {
         self.sys.refresh_networks();
         self.sys.networks()
@@ -70,6 +600,62 @@ impl SystemMonitor {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_raw_formatting_keeps_full_precision() {
+        assert_eq!(format_bytes(17_179_869_184, ByteUnits::Binary, false), "16.0 GiB");
+        assert_eq!(format_bytes(17_179_869_183, ByteUnits::Binary, true), "17179869183 B");
+        assert_eq!(format_rate(1_572_864, false), "1.5 MB/s");
+        assert_eq!(format_rate(1_572_863, true), "1572863 B/s");
+
+        let reading = 37.123_46_f32;
+        assert_eq!(format_percent(reading, 1, false), "37.1%");
+        assert_eq!(format_percent(reading, 0, false), "37%");
+        let raw = format_percent(reading, 1, true);
+        assert_eq!(raw.trim_end_matches('%').parse::<f32>().unwrap(), reading, "raw text round-trips exactly");
+        assert_eq!(format_percent(50.0, 1, true), "50%");
+    }
+
+    #[test]
+    fn test_process_memory_sorts_by_bytes_and_formats_by_unit() {
+        let process = |name: &str, memory| ProcessInfo { pid: 1, name: name.to_string(), memory, cpu: 0.0 };
+        // As text "900.0 MiB" would sort above "1.2 GiB"
+        let mut processes = vec![process("small", 4_096), process("medium", 900 * 1_048_576), process("large", 1_288_490_189)];
+        sort_processes(&mut processes, ProcessSort::Memory);
+        let order: Vec<&str> = processes.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(order, ["large", "medium", "small"]);
+        sort_processes(&mut processes, ProcessSort::Name);
+        assert_eq!(processes[0].name, "large");
+
+        assert_eq!(format_bytes(processes[1].memory, ByteUnits::Binary, false), "900.0 MiB");
+        assert_eq!(format_bytes(processes[0].memory, ByteUnits::Binary, false), "1.2 GiB");
+        assert_eq!(format_bytes(4_096, ByteUnits::Binary, false), "4.0 KiB");
+        assert_eq!(format_bytes(4_096, ByteUnits::Decimal, false), "4.1 kB");
+        assert_eq!(format_bytes(1_500_000_000, ByteUnits::Decimal, false), "1.5 GB");
+        assert_eq!(format_bytes(512, ByteUnits::Decimal, false), "512 B");
+        assert_eq!(format_bytes(4_096, ByteUnits::Decimal, true), "4096 B");
+    }
+
+    #[test]
+    fn test_percentages_and_rates_stay_finite() {
+        assert_eq!(percent_of(5, 0), 0.0);
+        assert_eq!(percent_of(0, 0), 0.0);
+        assert_eq!(percent_of(1, 4), 25.0);
+        assert_eq!(percent_of(8, 4), 100.0, "more used than total is clamped");
+
+        assert_eq!(finite_percent(f32::NAN), 0.0);
+        assert_eq!(finite_percent(f32::INFINITY), 0.0);
+        assert_eq!(finite_percent(-3.0), 0.0);
+        assert_eq!(finite_percent(250.0), 100.0);
+
+        assert_eq!(rate_per_sec(0, 1000, Duration::ZERO), None);
+        assert_eq!(rate_per_sec(0, 1000, Duration::from_millis(1)), None, "too close together to be a rate");
+        assert_eq!(rate_per_sec(0, 1000, Duration::from_millis(500)), Some(2000.0));
+        assert_eq!(rate_per_sec(5000, 10, Duration::from_secs(1)), Some(0.0), "counter reset");
+
+        let cores = group_by_physical_core(&[("CPU0".to_string(), f32::NAN), ("CPU1".to_string(), 40.0)], &[0, 0]);
+        assert_eq!(cores[0].1, 20.0);
+    }
+
     #[test]
     fn test_cpu_usage() {
         let mut monitor = SystemMonitor::new();
@@ -87,6 +673,16 @@ mod tests {
         assert!(used <= total, "Used memory cannot exceed total memory");
     }
 
+    #[test]
+    fn test_own_process_lookup() {
+        let mut monitor = SystemMonitor::new();
+        assert_eq!(monitor.own_usage(), None);
+        monitor.refresh_now(Instant::now());
+        let (cpu, resident) = monitor.own_usage().expect("the test process is in the process list");
+        assert!(cpu.is_finite() && cpu >= 0.0);
+        assert!(resident > 0, "a running process has resident memory");
+    }
+
     #[test]
     fn test_disk_usage() {
         let mut monitor = SystemMonitor::new();
@@ -106,6 +702,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_only_significant_swings_are_reported() {
+        let snapshot = |cpu_average, memory_usage| MetricsSnapshot {
+            taken_at: Instant::now(),
+            since_previous: None,
+            sequence: 1,
+            cpu_average,
+            memory_usage,
+            network_rx_total: 0,
+            network_tx_total: 0,
+            updated: HashMap::new(),
+        };
+        let previous = snapshot(10.0, 70.0);
+
+        let changes = diff_snapshots(&previous, &snapshot(55.0, 40.0), 25.0);
+        assert_eq!(changes, vec![
+            MetricChange { metric: MetricKind::Cpu, delta: 45.0 },
+            MetricChange { metric: MetricKind::Memory, delta: -30.0 },
+        ]);
+        let remark = crate::message_system::change_remark(&changes[0]).text().to_string();
+        assert!(remark.contains("CPU just shot up"), "{}", remark);
+        assert!(crate::message_system::change_remark(&changes[1]).text().contains("freed up"));
+
+        assert!(diff_snapshots(&previous, &snapshot(20.0, 65.0), 25.0).is_empty(), "small moves stay quiet");
+        assert!(diff_snapshots(&previous, &snapshot(f32::NAN, 70.0), 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_metric_older_than_threshold_is_stale() {
+        let start = Instant::now();
+        let mut snapshot = MetricsSnapshot {
+            taken_at: start,
+            since_previous: None,
+            sequence: 1,
+            cpu_average: 0.0,
+            memory_usage: 0.0,
+            network_rx_total: 0,
+            network_tx_total: 0,
+            updated: MetricsSnapshot::all_updated_at(start),
+        };
+        let max_age = Duration::from_secs(3);
+        let now = start + Duration::from_secs(10);
+        // Disk failed to read on later refreshes, so it kept its first timestamp
+        for kind in [MetricKind::Cpu, MetricKind::Memory, MetricKind::Network] {
+            snapshot.updated.insert(kind, start + Duration::from_secs(9));
+        }
+
+        assert!(snapshot.is_stale(MetricKind::Disk, now, max_age));
+        assert!(!snapshot.is_stale(MetricKind::Cpu, now, max_age));
+        assert!(!snapshot.is_stale(MetricKind::Disk, start + max_age, max_age), "exactly at the threshold is still live");
+        snapshot.updated.remove(&MetricKind::Network);
+        assert!(snapshot.is_stale(MetricKind::Network, now, max_age), "never read counts as stale");
+
+        let mut monitor = SystemMonitor::new();
+        let taken_at = monitor.refresh_now(Instant::now()).taken_at;
+        assert!(!monitor.snapshot().unwrap().is_stale(MetricKind::Cpu, taken_at, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_sibling_threads_average_per_physical_core() {
+        let usage: Vec<(String, f32)> = [10.0, 50.0, 30.0, 70.0].iter()
+            .enumerate()
+            .map(|(i, value)| (format!("CPU{}", i), *value))
+            .collect();
+
+        // Siblings numbered core, core + 2 (the usual Linux enumeration)
+        assert_eq!(group_by_physical_core(&usage, &[0, 1, 0, 1]), vec![
+            ("Core0 (2 threads)".to_string(), 20.0),
+            ("Core1 (2 threads)".to_string(), 60.0),
+        ]);
+        // Adjacent siblings
+        assert_eq!(group_by_physical_core(&usage, &[0, 0, 1, 1]), vec![
+            ("Core0 (2 threads)".to_string(), 30.0),
+            ("Core1 (2 threads)".to_string(), 50.0),
+        ]);
+        // No hyperthreading: one thread per core
+        assert_eq!(group_by_physical_core(&usage[..2], &[0, 1]), vec![
+            ("Core0".to_string(), 10.0),
+            ("Core1".to_string(), 50.0),
+        ]);
+
+        assert_eq!(interleaved_topology(4, Some(2)), vec![0, 1, 0, 1]);
+        assert_eq!(interleaved_topology(4, None), vec![0, 1, 2, 3]);
+        assert_eq!(interleaved_topology(2, Some(8)), vec![0, 1], "more physical than logical cores is bogus");
+    }
+
+    #[test]
+    fn test_refresh_respects_interval() {
+        let mut monitor = SystemMonitor::new();
+        monitor.set_refresh_interval(Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(monitor.refresh_if_due(start));
+        assert!(!monitor.refresh_if_due(start + Duration::from_secs(1)));
+        assert!(monitor.refresh_if_due(start + Duration::from_secs(60)));
+        assert_eq!(monitor.snapshot().unwrap().sequence, 2);
+    }
+
+    #[test]
+    fn test_refresh_now_ignores_interval() {
+        let mut monitor = SystemMonitor::new();
+        monitor.set_refresh_interval(Duration::from_secs(3600));
+        let start = Instant::now();
+        assert!(monitor.refresh_if_due(start));
+
+        let later = start + Duration::from_millis(250);
+        let snapshot = monitor.refresh_now(later).clone();
+
+        assert_eq!(snapshot.sequence, 2);
+        assert_eq!(snapshot.taken_at, later);
+        assert_eq!(snapshot.since_previous, Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_readings_come_from_the_last_snapshot() {
+        let mut monitor = SystemMonitor::new();
+        assert!(monitor.readings().is_none());
+
+        let snapshot = monitor.refresh_now(Instant::now()).clone();
+        let readings = monitor.readings().unwrap();
+        assert_eq!(readings.memory_usage, snapshot.memory_usage);
+        assert_eq!(readings.cpu_usage.len(), monitor.sys.cpus().len());
+        assert!(readings.disk_usage.is_finite() && (0.0..=100.0).contains(&readings.disk_usage));
+    }
+
+    #[test]
+    fn test_device_diff() {
+        let previous: BTreeSet<String> = ["/".to_string(), "/home".to_string()].into_iter().collect();
+        let current: BTreeSet<String> = ["/".to_string(), "/media/usb".to_string()].into_iter().collect();
+
+        let events = diff_devices(DeviceKind::Disk, &previous, &current);
+
+        assert_eq!(events, vec![
+            DeviceEvent { kind: DeviceKind::Disk, change: DeviceChange::Added, name: "/media/usb".to_string() },
+            DeviceEvent { kind: DeviceKind::Disk, change: DeviceChange::Removed, name: "/home".to_string() },
+        ]);
+        assert!(diff_devices(DeviceKind::Disk, &current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_first_refresh_reports_no_device_changes() {
+        let mut monitor = SystemMonitor::new();
+        monitor.refresh_now(Instant::now());
+        assert!(monitor.take_device_events().is_empty());
+    }
+
+    #[test]
+    fn test_monitor_reports_devices_changed_between_refreshes() {
+        let mut monitor = SystemMonitor::new();
+        monitor.refresh_now(Instant::now());
+        let mut disks = monitor.known_disks.clone().unwrap();
+        let mut interfaces = monitor.known_interfaces.clone().unwrap();
+
+        disks.insert("/media/usb".to_string());
+        interfaces.insert("usb0".to_string());
+        monitor.record_devices(disks.clone(), interfaces.clone());
+        interfaces.remove("usb0");
+        monitor.record_devices(disks, interfaces);
+
+        assert_eq!(monitor.take_device_events(), vec![
+            DeviceEvent { kind: DeviceKind::Disk, change: DeviceChange::Added, name: "/media/usb".to_string() },
+            DeviceEvent { kind: DeviceKind::NetworkInterface, change: DeviceChange::Added, name: "usb0".to_string() },
+            DeviceEvent { kind: DeviceKind::NetworkInterface, change: DeviceChange::Removed, name: "usb0".to_string() },
+        ]);
+        assert!(monitor.take_device_events().is_empty());
+    }
+
     #[test]
     fn test_system_info_validity() {
         let monitor = SystemMonitor::new();
@@ -1,4 +1,5 @@
 use eframe::egui::{Color32, Pos2};
+use serde::{Serialize, Deserialize};
 
 #[derive(Clone)]
 pub struct CyberTheme {
@@ -35,6 +36,15 @@ impl Default for CyberTheme {
     }
 }
 
+impl CyberTheme {
+    /// The theme with its background fills see-through at `opacity` (0 to 1)
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.background = with_opacity(self.background, opacity);
+        self.background_light = with_opacity(self.background_light, opacity);
+        self
+    }
+}
+
 // Core colors
 pub const ACCENT_COLOR: Color32 = Color32::from_rgb(0, 255, 136);
 pub const BACKGROUND_COLOR: Color32 = Color32::from_rgb(16, 24, 32);
@@ -42,6 +52,10 @@ pub const BACKGROUND_DARK: Color32 = Color32::from_rgb(8, 12, 16);
 pub const FOREGROUND_COLOR: Color32 = Color32::from_rgb(200, 255, 200);
 pub const FOREGROUND_DIM: Color32 = Color32::from_rgb(100, 128, 100);
 
+// Severity colors for numeric labels; critical uses the theme's neon_alert
+pub const SEVERITY_OK: Color32 = Color32::from_rgb(0, 255, 136);
+pub const SEVERITY_WARN: Color32 = Color32::from_rgb(255, 191, 0);
+
 // UI constants
 pub const WINDOW_ROUNDING: f32 = 4.0;
 pub const WINDOW_SHADOW: f32 = 8.0;
@@ -49,6 +63,7 @@ pub const PANEL_SPACING: f32 = 8.0;
 pub const BORDER_WIDTH: f32 = 2.0;
 pub const GRID_SIZE: i32 = 32;
 pub const HEADER_HEIGHT: f32 = 48.0;
+pub const RESPONSIVE_BREAKPOINT: f32 = 720.0;
 
 // Animation constants
 pub const PULSE_SPEED: f32 = 1.0;
@@ -119,4 +134,111 @@ pub fn pulse_color(color: Color32, intensity: f32) -> Color32 {
     let b = (color.b() as f32 * intensity) as u8;
     let a = color.a();
     Color32::from_rgba_unmultiplied(r, g, b, a)
-} 
\ No newline at end of file
+}
+
+/// Lowest window opacity offered, so the dashboard never disappears entirely
+pub const MIN_WINDOW_OPACITY: f32 = 0.2;
+
+/// Alpha channel for a window opacity, clamped to MIN_WINDOW_OPACITY..=1
+pub fn opacity_alpha(opacity: f32) -> u8 {
+    let opacity = if opacity.is_finite() { opacity.clamp(MIN_WINDOW_OPACITY, 1.0) } else { 1.0 };
+    (opacity * 255.0).round() as u8
+}
+
+/// `color` with its alpha scaled by the window opacity
+pub fn with_opacity(color: Color32, opacity: f32) -> Color32 {
+    let alpha = color.a() as u16 * opacity_alpha(opacity) as u16 / 255;
+    Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), alpha as u8)
+}
+
+/// Whether windows can be made see-through on this platform
+pub fn transparency_supported() -> bool {
+    cfg!(any(target_os = "windows", target_os = "macos", target_os = "linux"))
+}
+
+/// What sets the speed and brightness of the neon pulse and hologram flicker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PulseDriver {
+    /// Steady timer, independent of anything
+    #[default]
+    Constant,
+    /// Brighter and quicker while the AI is talking
+    Speaking,
+    /// Faster and brighter as CPU load rises
+    CpuLoad,
+}
+
+/// Animation speed multiplier and peak brightness (0 to 1) for one frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pulse {
+    pub speed: f32,
+    pub intensity: f32,
+}
+
+impl PulseDriver {
+    pub const ALL: [PulseDriver; 3] = [PulseDriver::Constant, PulseDriver::Speaking, PulseDriver::CpuLoad];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PulseDriver::Constant => "Constant",
+            PulseDriver::Speaking => "While speaking",
+            PulseDriver::CpuLoad => "CPU load",
+        }
+    }
+
+    /// Pulse for the current signal; `cpu_load` is a percentage
+    pub fn pulse(&self, speaking: bool, cpu_load: f32) -> Pulse {
+        match self {
+            PulseDriver::Constant => Pulse { speed: 1.0, intensity: 1.0 },
+            PulseDriver::Speaking if speaking => Pulse { speed: 2.5, intensity: 1.0 },
+            PulseDriver::Speaking => Pulse { speed: 1.0, intensity: 0.4 },
+            PulseDriver::CpuLoad => {
+                let load = if cpu_load.is_finite() { (cpu_load / 100.0).clamp(0.0, 1.0) } else { 0.0 };
+                Pulse {
+                    speed: 0.5 + 3.5 * load,
+                    intensity: 0.4 + 0.6 * load,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opacity_to_alpha() {
+        assert_eq!(opacity_alpha(1.0), 255);
+        assert_eq!(opacity_alpha(0.5), 128);
+        assert_eq!(opacity_alpha(0.0), opacity_alpha(MIN_WINDOW_OPACITY));
+        assert_eq!(opacity_alpha(MIN_WINDOW_OPACITY), 51);
+        assert_eq!(opacity_alpha(3.0), 255);
+        assert_eq!(opacity_alpha(f32::NAN), 255);
+
+        assert_eq!(with_opacity(Color32::from_rgb(0, 0, 20), 0.5).a(), 128);
+        // Colors that were already translucent fade further
+        assert_eq!(with_opacity(Color32::from_rgba_unmultiplied(0, 255, 255, 100), 0.5).a(), 50);
+        assert_eq!(with_opacity(Color32::from_rgb(10, 10, 30), 1.0), Color32::from_rgb(10, 10, 30));
+    }
+
+    #[test]
+    fn test_pulse_driver_mapping() {
+        let steady = Pulse { speed: 1.0, intensity: 1.0 };
+        assert_eq!(PulseDriver::Constant.pulse(true, 100.0), steady);
+        assert_eq!(PulseDriver::Constant.pulse(false, 0.0), steady);
+
+        let talking = PulseDriver::Speaking.pulse(true, 0.0);
+        let quiet = PulseDriver::Speaking.pulse(false, 0.0);
+        assert!(talking.intensity > quiet.intensity && talking.speed > quiet.speed);
+        assert_eq!(PulseDriver::Speaking.pulse(false, 100.0), quiet);
+
+        assert_eq!(PulseDriver::CpuLoad.pulse(false, 0.0), Pulse { speed: 0.5, intensity: 0.4 });
+        assert_eq!(PulseDriver::CpuLoad.pulse(true, 100.0), Pulse { speed: 4.0, intensity: 1.0 });
+        let half = PulseDriver::CpuLoad.pulse(false, 50.0);
+        assert!(half.speed > 0.5 && half.speed < 4.0);
+        // Out-of-range readings are clamped
+        assert_eq!(PulseDriver::CpuLoad.pulse(false, 250.0), PulseDriver::CpuLoad.pulse(false, 100.0));
+        assert_eq!(PulseDriver::CpuLoad.pulse(false, f32::NAN), PulseDriver::CpuLoad.pulse(false, 0.0));
+    }
+}
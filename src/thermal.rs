@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+use crate::warnings::linear_slope;
+
+/// Readings needed before the temperature trend is trusted
+pub const THERMAL_MIN_SAMPLES: usize = 3;
+
+/// When climbing temperatures and fast fans together count as a thermal warning.
+///
+/// Neither has to cross a flat cutoff: fans working hard while the machine
+/// keeps getting hotter means the load is sustained and cooling is losing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThermalSettings {
+    pub enabled: bool,
+    /// Temperature rise that counts as climbing, in °C per minute
+    pub min_rise_per_min: f32,
+    /// Average fan speed that counts as ramping hard, in RPM
+    pub fan_rpm: u32,
+    /// How much history the trend is taken over, in seconds
+    pub window_secs: u64,
+}
+
+impl Default for ThermalSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_rise_per_min: 1.0,
+            fan_rpm: 2500,
+            window_secs: 120,
+        }
+    }
+}
+
+impl ThermalSettings {
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_secs)
+    }
+}
+
+/// Whether `samples` of (seconds, °C, fan RPM) show temperatures climbing while the fans run hard.
+///
+/// The samples must span at least half the window, so a brief spike at startup doesn't count.
+pub fn is_thermal_escalation(samples: &[(f64, f32, u32)], settings: &ThermalSettings) -> bool {
+    if samples.len() < THERMAL_MIN_SAMPLES {
+        return false;
+    }
+    let span = samples[samples.len() - 1].0 - samples[0].0;
+    if span < settings.window_secs as f64 / 2.0 {
+        return false;
+    }
+    let mean_rpm = samples.iter().map(|(_, _, rpm)| *rpm as f64).sum::<f64>() / samples.len() as f64;
+    let temperatures: Vec<(f64, f64)> = samples.iter().map(|(time, temp, _)| (*time, *temp as f64)).collect();
+    let rise_per_min = linear_slope(&temperatures).map_or(0.0, |slope| slope * 60.0);
+    mean_rpm >= settings.fan_rpm as f64 && rise_per_min >= settings.min_rise_per_min as f64
+}
+
+/// Recent temperature and fan readings, for spotting a thermal escalation
+#[derive(Debug, Default)]
+pub struct ThermalTracker {
+    samples: VecDeque<(Instant, f32, u32)>,
+}
+
+impl ThermalTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the hottest sensor and fastest fan, keeping `window` of history
+    pub fn observe(&mut self, temperature: f32, fan_rpm: u32, now: Instant, window: Duration) {
+        self.samples.push_back((now, temperature, fan_rpm));
+        while self.samples.front().is_some_and(|(time, _, _)| now.saturating_duration_since(*time) > window) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The last (°C, RPM) reading
+    pub fn latest(&self) -> Option<(f32, u32)> {
+        self.samples.back().map(|(_, temperature, rpm)| (*temperature, *rpm))
+    }
+
+    pub fn is_escalating(&self, settings: &ThermalSettings) -> bool {
+        let start = match self.samples.front() {
+            Some((start, _, _)) => *start,
+            None => return false,
+        };
+        let samples: Vec<(f64, f32, u32)> = self.samples.iter()
+            .map(|(time, temperature, rpm)| (time.saturating_duration_since(start).as_secs_f64(), *temperature, *rpm))
+            .collect();
+        is_thermal_escalation(&samples, settings)
+    }
+}
+
+/// Parses a hwmon `fan*_input` file, which holds the speed in RPM
+pub fn parse_fan_rpm(content: &str) -> Option<u32> {
+    content.trim().parse().ok()
+}
+
+/// Speed of the fastest fan, from /sys/class/hwmon; None where no fan reports its speed
+#[cfg(target_os = "linux")]
+pub fn read_fan_rpm() -> Option<u32> {
+    let hwmon = std::fs::read_dir("/sys/class/hwmon").ok()?;
+    hwmon.flatten()
+        .filter_map(|device| std::fs::read_dir(device.path()).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.starts_with("fan") && name.ends_with("_input")
+        })
+        .filter_map(|entry| parse_fan_rpm(&std::fs::read_to_string(entry.path()).ok()?))
+        .max()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_fan_rpm() -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(count: usize, step_secs: f64, start_temp: f32, rise_per_min: f32, rpm: u32) -> Vec<(f64, f32, u32)> {
+        (0..count)
+            .map(|i| {
+                let time = i as f64 * step_secs;
+                (time, start_temp + rise_per_min * time as f32 / 60.0, rpm)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_climbing_temperature_with_fast_fans_escalates() {
+        let settings = ThermalSettings { enabled: true, ..ThermalSettings::default() };
+
+        assert!(is_thermal_escalation(&series(25, 5.0, 60.0, 2.0, 3200), &settings));
+        assert!(!is_thermal_escalation(&series(25, 5.0, 60.0, 2.0, 1200), &settings), "quiet fans: the cooling isn't struggling");
+        assert!(!is_thermal_escalation(&series(25, 5.0, 85.0, 0.0, 4000), &settings), "hot but steady: fans are keeping up");
+        assert!(!is_thermal_escalation(&series(25, 5.0, 60.0, -1.5, 4000), &settings), "cooling down");
+        assert!(!is_thermal_escalation(&series(5, 5.0, 60.0, 6.0, 4000), &settings), "too short to call sustained");
+        assert!(!is_thermal_escalation(&series(2, 60.0, 60.0, 6.0, 4000), &settings));
+
+        let sensitive = ThermalSettings { min_rise_per_min: 0.5, fan_rpm: 1000, ..settings.clone() };
+        assert!(is_thermal_escalation(&series(25, 5.0, 60.0, 0.8, 1200), &sensitive));
+
+        let mut tracker = ThermalTracker::new();
+        let start = Instant::now();
+        for (time, temperature, rpm) in series(40, 5.0, 60.0, 2.0, 3200) {
+            tracker.observe(temperature, rpm, start + Duration::from_secs_f64(time), settings.window());
+        }
+        assert!(tracker.is_escalating(&settings));
+        assert_eq!(tracker.latest().map(|(_, rpm)| rpm), Some(3200));
+
+        assert_eq!(parse_fan_rpm("2870\n"), Some(2870));
+        assert_eq!(parse_fan_rpm("n/a"), None);
+    }
+}
@@ -1,47 +1,841 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use reqwest;
+use serde::{Serialize, Deserialize};
 use serde_json::json;
-use super::message_system::{MessagePart, CacheKey, PersonalitySettings};
+use super::message_system::{self, MessagePart, CacheKey, PersonalitySettings, Severity};
+use super::audio_processing::{self, AudioProcessing, DecodedClip};
+use super::spoken_numbers;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use async_trait::async_trait;
 use tokio::time::Duration as TokioDuration;
+use tokio::sync::Semaphore;
 use rodio;
+use rodio::cpal::traits::HostTrait;
+use rodio::DeviceTrait;
+use std::time::{Duration, Instant};
 
+/// Whether the default host has a default audio output device
+pub fn default_output_device_available() -> bool {
+    rodio::cpal::default_host().default_output_device().is_some()
+}
+
+/// Lists the names of the audio output devices exposed by the default host
+pub fn list_output_devices() -> Vec<String> {
+    let host = rodio::cpal::default_host();
+    match host.output_devices() {
+        Ok(devices) => devices.filter_map(|device| device.name().ok()).collect(),
+        Err(e) => {
+            eprintln!("Failed to enumerate audio output devices: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Opens the output device named `device` (or the default) with `open`, retrying once if that fails.
+///
+/// A failed open usually means the device was unplugged. Devices are then
+/// re-enumerated with `enumerate`: the retry uses the same device if it is
+/// listed again, otherwise the default. On a successful retry the second
+/// value describes the change so it can be shown to the user.
+pub fn open_with_recovery<T, F, E>(mut open: F, device: Option<&str>, enumerate: E) -> Result<(T, Option<String>), Box<dyn std::error::Error>>
+where
+    F: FnMut(Option<&str>) -> Result<T, Box<dyn std::error::Error>>,
+    E: FnOnce() -> Vec<String>,
+{
+    let first_error = match open(device) {
+        Ok(output) => return Ok((output, None)),
+        Err(e) => e,
+    };
+
+    let available = enumerate();
+    let retry_device = device.filter(|name| available.iter().any(|listed| listed == name));
+    let target = retry_device.unwrap_or("the system default");
+    println!("Failed to open audio output ({}), re-opening {}", first_error, target);
+
+    match open(retry_device) {
+        Ok(output) => {
+            let notice = match (device, retry_device) {
+                (Some(name), None) => format!("Audio device '{}' unavailable, using the system default", name),
+                _ => format!("Audio device reconnected: {}", target),
+            };
+            Ok((output, Some(notice)))
+        }
+        Err(e) => Err(format!("{} (re-opening {} also failed: {})", first_error, target, e).into()),
+    }
+}
+
+/// Opens the output device called `name`, or the default device if None
+pub(crate) fn open_device(name: Option<&str>) -> Result<(rodio::OutputStream, rodio::OutputStreamHandle), Box<dyn std::error::Error>> {
+    let name = match name {
+        Some(name) => name,
+        None => return Ok(rodio::OutputStream::try_default()?),
+    };
+    let host = rodio::cpal::default_host();
+    let device = host.output_devices()?
+        .find(|d| d.name().is_ok_and(|n| n == name))
+        .ok_or_else(|| format!("audio output device '{}' not found", name))?;
+    Ok(rodio::OutputStream::try_from_device(&device)?)
+}
+
+/// Responses shorter than this cannot be a real audio clip
+pub const MIN_AUDIO_BYTES: usize = 128;
+
+/// Why a synthesis request did not produce usable audio
+#[derive(Debug, Clone, PartialEq)]
+pub enum TtsError {
+    /// The API returned an error status
+    Api { status: u16, message: String },
+    /// The API answered successfully but with something other than audio, e.g. a JSON refusal
+    NotAudio { content_type: String, message: String },
+    /// The response body is too short to hold any audio
+    TooShort { len: usize },
+    /// No API key has been set in the environment or the settings window
+    MissingKey,
+}
+
+impl std::fmt::Display for TtsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TtsError::Api { status, message } => write!(f, "TTS API error ({}): {}", status, message),
+            TtsError::NotAudio { content_type, message } => {
+                write!(f, "TTS response is not audio ({}): {}", content_type, message)
+            }
+            TtsError::TooShort { len } => {
+                write!(f, "TTS response too short to be audio ({} bytes, need {})", len, MIN_AUDIO_BYTES)
+            }
+            TtsError::MissingKey => write!(f, "OPENAI_API_KEY is not set"),
+        }
+    }
+}
+
+impl std::error::Error for TtsError {}
+
+/// Pulls a readable message out of an API error body, preferring OpenAI's `error.message`
+fn error_message_from_body(body: &[u8]) -> String {
+    if let Ok(value) = serde_json::from_slice::<serde_json::Value>(body) {
+        if let Some(message) = value["error"]["message"].as_str() {
+            return message.to_string();
+        }
+    }
+    let text = String::from_utf8_lossy(body);
+    let text = text.trim();
+    if text.is_empty() {
+        "empty response body".to_string()
+    } else {
+        text.chars().take(200).collect()
+    }
+}
+
+/// Checks that a successful TTS response actually carries audio before it is decoded or cached
+pub fn validate_audio_response(content_type: Option<&str>, body: Vec<u8>) -> Result<Vec<u8>, TtsError> {
+    let content_type = content_type.unwrap_or("").to_ascii_lowercase();
+    let looks_like_json = body.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{');
+    let declared_audio = content_type.is_empty()
+        || content_type.starts_with("audio/")
+        || content_type.starts_with("application/octet-stream");
+
+    if !declared_audio || looks_like_json {
+        return Err(TtsError::NotAudio {
+            content_type: if content_type.is_empty() { "unknown".to_string() } else { content_type },
+            message: error_message_from_body(&body),
+        });
+    }
+    if body.len() < MIN_AUDIO_BYTES {
+        return Err(TtsError::TooShort { len: body.len() });
+    }
+    Ok(body)
+}
+
+/// Voices offered by the speech API
+pub const VOICES: [&str; 6] = ["alloy", "echo", "fable", "nova", "onyx", "shimmer"];
+
+/// Voice used when a configured one is unknown
+pub const DEFAULT_VOICE: &str = "alloy";
+
+/// Speech languages as (code, name, recommended voice). The speech API takes no
+/// language parameter and infers it from the text, so the voice is the only lever.
+pub const LOCALES: [(&str, &str, &str); 8] = [
+    ("en", "English", "alloy"),
+    ("es", "Español", "nova"),
+    ("fr", "Français", "shimmer"),
+    ("de", "Deutsch", "onyx"),
+    ("it", "Italiano", "nova"),
+    ("pt", "Português", "shimmer"),
+    ("ja", "日本語", "nova"),
+    ("zh", "中文", "alloy"),
+];
+
+/// Locale used when none is configured
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// The voice that handles `locale` best, the default voice for unknown locales.
+/// Regional variants like "es-MX" use their language's voice.
+pub fn recommended_voice(locale: &str) -> &'static str {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale).to_lowercase();
+    LOCALES.iter()
+        .find(|(code, _, _)| *code == language)
+        .map_or(DEFAULT_VOICE, |(_, _, voice)| voice)
+}
+
+/// Voice to use after switching to `locale`: the recommended one, unless the user picked `current` themselves
+pub fn voice_for_locale(locale: &str, current: &str, chosen_explicitly: bool) -> String {
+    if chosen_explicitly {
+        current.to_string()
+    } else {
+        recommended_voice(locale).to_string()
+    }
+}
+
+/// List price of the tts-1 model in US dollars per million characters of input
+pub const TTS_PRICE_PER_MILLION_CHARS: f64 = 15.0;
+
+/// File the monthly character count is kept in, next to the config
+pub const USAGE_FILE: &str = "tts_usage.json";
+
+/// Estimated cost in US dollars of sending `characters` characters to the speech API
+pub fn estimate_cost(characters: u64) -> f64 {
+    characters as f64 * TTS_PRICE_PER_MILLION_CHARS / 1_000_000.0
+}
+
+/// The calendar month usage is counted against, as "YYYY-MM"
+pub fn current_month() -> String {
+    chrono::Local::now().format("%Y-%m").to_string()
+}
+
+/// Characters sent to the speech API this session and this calendar month.
+///
+/// Only the monthly count is saved; it starts again from zero when the month changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtsUsage {
+    pub month: String,
+    pub month_characters: u64,
+    #[serde(skip)]
+    pub session_characters: u64,
+}
+
+impl TtsUsage {
+    pub fn new(month: &str) -> Self {
+        Self {
+            month: month.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Starts a fresh monthly count if `month` is not the one being counted
+    pub fn roll_over(&mut self, month: &str) {
+        if self.month != month {
+            self.month = month.to_string();
+            self.month_characters = 0;
+        }
+    }
+
+    pub fn record(&mut self, characters: u64, month: &str) {
+        self.roll_over(month);
+        self.month_characters += characters;
+        self.session_characters += characters;
+    }
+
+    pub fn month_cost(&self) -> f64 {
+        estimate_cost(self.month_characters)
+    }
+
+    pub fn session_cost(&self) -> f64 {
+        estimate_cost(self.session_characters)
+    }
+
+    /// Whether this month's estimated spend has reached `budget` dollars; a budget of 0 is no cap
+    pub fn over_budget(&self, budget: f64) -> bool {
+        budget > 0.0 && self.month_cost() >= budget
+    }
+
+    /// Reads saved usage, starting from nothing if the file is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Speech API requests allowed in flight at once unless configured otherwise
+pub const DEFAULT_MAX_CONCURRENT_GENERATIONS: usize = 2;
+
+/// Highest speech request rate that can be configured, in requests per minute
+pub const MAX_REQUESTS_PER_MINUTE: u32 = 10_000;
+
+/// Token bucket that paces speech requests to a requests-per-minute ceiling.
+///
+/// The bucket holds one token, so requests go out evenly spaced; a burst
+/// reserves future tokens and each request waits its turn instead of failing.
+/// A rate of 0 means unlimited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimiter {
+    requests_per_minute: u32,
+    /// Tokens available; negative when requests have reserved future tokens
+    tokens: f64,
+    last_refill: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            tokens: 1.0,
+            last_refill: None,
+        }
+    }
+
+    pub fn requests_per_minute(&self) -> u32 {
+        self.requests_per_minute
+    }
+
+    /// Takes a token for one request at `now` and returns how long it must wait before being sent
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        if self.requests_per_minute == 0 {
+            return Duration::ZERO;
+        }
+        let per_second = self.requests_per_minute as f64 / 60.0;
+        if let Some(last) = self.last_refill {
+            let refill = now.saturating_duration_since(last).as_secs_f64() * per_second;
+            self.tokens = (self.tokens + refill).min(1.0);
+        }
+        self.last_refill = Some(now);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / per_second)
+        }
+    }
+}
+
+/// Requests that fail transiently are tried this many times in total
+pub const MAX_TTS_ATTEMPTS: u32 = 2;
+
+/// Pause before retrying a transiently failed request
+const TTS_RETRY_DELAY: TokioDuration = TokioDuration::from_millis(500);
+
+/// Whether a failed synthesis is worth retrying: network errors, rate limits and server errors
+fn is_transient(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    if error.downcast_ref::<std::env::VarError>().is_some() {
+        return false;
+    }
+    match error.downcast_ref::<TtsError>() {
+        Some(TtsError::Api { status, .. }) => *status == 429 || *status >= 500,
+        Some(_) => false,
+        None => true,
+    }
+}
+
+/// Health of the speech API as shown by the top-bar status dot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// No request has been made yet
+    Idle,
+    /// The last request succeeded
+    Ok,
+    /// The last attempt failed and is being retried
+    Retrying,
+    /// The last request failed for good (bad key, network down, ...)
+    Failing,
+}
+
+/// Outcome of the most recent speech API request
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionStatus {
+    pub state: ConnectionState,
+    pub last_latency: Option<Duration>,
+    pub last_error: Option<String>,
+}
+
+impl Default for ConnectionStatus {
+    fn default() -> Self {
+        Self {
+            state: ConnectionState::Idle,
+            last_latency: None,
+            last_error: None,
+        }
+    }
+}
+
+impl ConnectionStatus {
+    pub fn record_success(&mut self, latency: Duration) {
+        self.state = ConnectionState::Ok;
+        self.last_latency = Some(latency);
+        self.last_error = None;
+    }
+
+    pub fn record_retry(&mut self, error: String) {
+        self.state = ConnectionState::Retrying;
+        self.last_error = Some(error);
+    }
+
+    pub fn record_failure(&mut self, latency: Duration, error: String) {
+        self.state = ConnectionState::Failing;
+        self.last_latency = Some(latency);
+        self.last_error = Some(error);
+    }
+
+    /// Tooltip text for the status dot
+    pub fn describe(&self) -> String {
+        let mut text = match self.state {
+            ConnectionState::Idle => "Speech API idle, no requests yet",
+            ConnectionState::Ok => "Speech API reachable",
+            ConnectionState::Retrying => "Speech API request failed, retrying",
+            ConnectionState::Failing => "Speech API failing",
+        }.to_string();
+        if let Some(latency) = self.last_latency {
+            text.push_str(&format!("\nLast request: {} ms", latency.as_millis()));
+        }
+        if let Some(error) = &self.last_error {
+            text.push_str(&format!("\nError: {}", error));
+        }
+        text
+    }
+}
+
+/// Consecutive failed speak calls before the watchdog reinitializes speech
+pub const DEFAULT_WATCHDOG_THRESHOLD: u32 = 3;
+/// Wait before the second recovery attempt; doubles after each failed attempt
+const WATCHDOG_BASE_BACKOFF: Duration = Duration::from_secs(30);
+/// Longest wait between recovery attempts while speech stays offline
+const WATCHDOG_MAX_BACKOFF: Duration = Duration::from_secs(600);
+/// Spoken once speech works again, doubling as the check that it does
+pub const AUDIO_RESTORED_MESSAGE: &str = "Audio restored.";
+
+/// Counts consecutive speak failures and decides when speech should be reinitialized.
+///
+/// Once a recovery attempt fails, speech counts as offline and further
+/// attempts back off exponentially until one succeeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtsWatchdog {
+    threshold: u32,
+    consecutive_failures: u32,
+    failed_recoveries: u32,
+    recovery_attempts: u32,
+    next_attempt: Option<Instant>,
+}
+
+impl TtsWatchdog {
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            consecutive_failures: 0,
+            failed_recoveries: 0,
+            recovery_attempts: 0,
+            next_attempt: None,
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: u32) {
+        self.threshold = threshold.max(1);
+    }
+
+    /// A speak call worked; returns true if speech had been offline
+    pub fn record_success(&mut self) -> bool {
+        let was_offline = self.is_offline();
+        self.consecutive_failures = 0;
+        self.failed_recoveries = 0;
+        self.next_attempt = None;
+        was_offline
+    }
+
+    /// A speak call failed; returns true if a recovery should be attempted now
+    pub fn record_failure(&mut self, now: Instant) -> bool {
+        self.consecutive_failures += 1;
+        self.consecutive_failures >= self.threshold && self.next_attempt.is_none_or(|next| now >= next)
+    }
+
+    /// Marks the start of a recovery attempt
+    pub fn begin_recovery(&mut self) {
+        self.recovery_attempts += 1;
+    }
+
+    /// The recovery attempt didn't bring speech back; schedules the next one further out
+    pub fn recovery_failed(&mut self, now: Instant) {
+        let backoff = WATCHDOG_BASE_BACKOFF.saturating_mul(1u32 << self.failed_recoveries.min(8)).min(WATCHDOG_MAX_BACKOFF);
+        self.failed_recoveries += 1;
+        self.next_attempt = Some(now + backoff);
+    }
+
+    /// Whether recovery has been tried and failed since speech last worked
+    pub fn is_offline(&self) -> bool {
+        self.failed_recoveries > 0
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Recovery attempts made since startup
+    pub fn recovery_attempts(&self) -> u32 {
+        self.recovery_attempts
+    }
+}
+
+/// Turns text into encoded speech audio
+#[async_trait]
+pub trait SpeechBackend: Send + Sync {
+    async fn synthesize(&self, text: &str, voice: &str, speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Whether the backend produces real audio worth playing
+    fn is_live(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str;
+
+    /// Replaces the API key used for subsequent requests; backends without keys ignore it
+    fn set_api_key(&self, _key: String) {}
+
+    /// Drops and recreates any connection state, e.g. after repeated failures
+    fn reset(&self) {}
+}
+
+/// Base URL of the official OpenAI API
+pub const DEFAULT_TTS_BASE_URL: &str = "https://api.openai.com/v1";
+/// Environment variable that overrides the configured TTS base URL
+pub const TTS_BASE_URL_ENV: &str = "CNM_TTS_BASE_URL";
+const SPEECH_PATH: &str = "audio/speech";
+
+/// Turns a base URL (or a full speech endpoint) into the speech endpoint, rejecting unusable URLs
+pub fn speech_endpoint(base_url: &str) -> Result<reqwest::Url, Box<dyn std::error::Error>> {
+    let url = reqwest::Url::parse(base_url.trim())
+        .map_err(|e| format!("Invalid TTS base URL '{}': {}", base_url, e))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(format!("TTS base URL '{}' must use http or https", base_url).into());
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("TTS base URL '{}' has no host", base_url).into());
+    }
+
+    if url.path().trim_end_matches('/').ends_with(SPEECH_PATH) {
+        return Ok(url);
+    }
+    let base = format!("{}/", url.as_str().trim_end_matches('/'));
+    Ok(reqwest::Url::parse(&base)?.join(SPEECH_PATH)?)
+}
+
+/// Synthesizes speech with the OpenAI TTS API or any OpenAI-compatible gateway
+pub struct OpenAiBackend {
+    client: Mutex<reqwest::Client>,
+    endpoint: reqwest::Url,
+    extra_headers: Vec<(String, String)>,
+    api_key: Mutex<Option<String>>,
+}
+
+impl Default for OpenAiBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenAiBackend {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_TTS_BASE_URL, Vec::new()).expect("default TTS endpoint is valid")
+    }
+
+    /// Sends requests to `base_url` (e.g. a LiteLLM proxy) with extra headers on every request
+    pub fn with_endpoint(base_url: &str, extra_headers: Vec<(String, String)>) -> Result<Self, Box<dyn std::error::Error>> {
+        for (name, value) in &extra_headers {
+            reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid TTS header name '{}': {}", name, e))?;
+            reqwest::header::HeaderValue::from_str(value)
+                .map_err(|e| format!("Invalid value for TTS header '{}': {}", name, e))?;
+        }
+
+        Ok(Self {
+            client: Mutex::new(reqwest::Client::new()),
+            endpoint: speech_endpoint(base_url)?,
+            extra_headers,
+            // Read once; later changes come in through set_api_key
+            api_key: Mutex::new(std::env::var("OPENAI_API_KEY").ok()),
+        })
+    }
+
+    fn build_request(&self, api_key: &str, text: &str, voice: &str, speed: f32) -> reqwest::RequestBuilder {
+        let client = self.client.lock().unwrap().clone();
+        let mut request = client
+            .post(self.endpoint.clone())
+            .header("Authorization", format!("Bearer {}", api_key));
+        for (name, value) in &self.extra_headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request.json(&json!({
+            "model": "tts-1",
+            "input": text,
+            "voice": voice,
+            "speed": speed
+        }))
+    }
+}
+
+#[async_trait]
+impl SpeechBackend for OpenAiBackend {
+    async fn synthesize(&self, text: &str, voice: &str, speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let api_key = match self.api_key.lock().unwrap().clone() {
+            Some(key) => key,
+            None => return Err(Box::new(TtsError::MissingKey)),
+        };
+
+        println!("Making API request to TTS endpoint {}", self.endpoint);
+        let response = self.build_request(&api_key, text, voice, speed)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.bytes().await?;
+            let message = error_message_from_body(&body);
+            println!("OpenAI API error: {}", message);
+            return Err(Box::new(TtsError::Api { status: status.as_u16(), message }));
+        }
+
+        println!("Successfully received response from OpenAI");
+        let content_type = response.headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.bytes().await?.to_vec();
+        let audio_data = validate_audio_response(content_type.as_deref(), body)?;
+        println!("Converted response to {} bytes of audio data", audio_data.len());
+        Ok(audio_data)
+    }
+
+    fn name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn set_api_key(&self, key: String) {
+        *self.api_key.lock().unwrap() = Some(key);
+    }
+
+    fn reset(&self) {
+        // A fresh client drops pooled connections that may have gone stale during an outage
+        *self.client.lock().unwrap() = reqwest::Client::new();
+    }
+}
+
+/// Offline stand-in that never touches the network or plays audio.
+///
+/// Used when no API key is configured or the dry-run flag is set, and as a
+/// mock in tests since it counts every synthesis request.
+#[derive(Clone, Default)]
+pub struct DryRunBackend {
+    requests: Arc<AtomicUsize>,
+    delay: Option<TokioDuration>,
+}
+
+impl DryRunBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Simulates API latency on every request
+    #[cfg(test)]
+    pub fn with_delay(delay: TokioDuration) -> Self {
+        Self {
+            delay: Some(delay),
+            ..Self::default()
+        }
+    }
+
+    /// Shared counter of synthesis requests made against this backend
+    pub fn request_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.requests)
+    }
+}
+
+#[async_trait]
+impl SpeechBackend for DryRunBackend {
+    async fn synthesize(&self, text: &str, _voice: &str, _speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+        println!("Dry run, not synthesizing: {}", text);
+        Ok(Vec::new())
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &'static str {
+        "Dry run"
+    }
+}
+
+/// Silence between the clips of a message when they don't crossfade
+pub const CLIP_GAP: Duration = Duration::from_millis(100);
+
+/// Longest crossfade offered between clips, in milliseconds
+pub const MAX_CROSSFADE_MS: u32 = 500;
+
+/// Where the clips of a message are played: the speakers, or a recorder in tests
+pub trait ClipOutput {
+    /// Starts `clip`, mixed with anything still playing
+    fn start(&mut self, clip: DecodedClip) -> Result<(), Box<dyn std::error::Error>>;
+    /// Lets playback run for `duration`
+    fn wait(&mut self, duration: Duration);
+    /// Lets playback run until every started clip has finished
+    fn wait_until_done(&mut self);
+}
+
+/// How long clips of lengths `a` and `b` overlap: `crossfade`, but never more than half of either
+pub fn crossfade_between(a: Duration, b: Duration, crossfade: Duration) -> Duration {
+    crossfade.min(a / 2).min(b / 2)
+}
+
+/// Plays `clips` in order on `output`.
+///
+/// Consecutive clips overlap by `crossfade`, the outgoing one fading out as
+/// the next fades in. A zero crossfade plays each clip to the end and
+/// leaves `gap` of silence before the next.
+pub fn play_sequence(output: &mut dyn ClipOutput, clips: Vec<DecodedClip>, crossfade: Duration, gap: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    let durations: Vec<Duration> = clips.iter().map(DecodedClip::duration).collect();
+    let overlaps: Vec<Duration> = durations.windows(2)
+        .map(|pair| crossfade_between(pair[0], pair[1], crossfade))
+        .collect();
+    let total_clips = clips.len();
+    for (i, mut clip) in clips.into_iter().enumerate() {
+        let fade_in = if i == 0 { Duration::ZERO } else { overlaps[i - 1] };
+        let fade_out = overlaps.get(i).copied().unwrap_or(Duration::ZERO);
+        println!("Playing clip {} of {}", i + 1, total_clips);
+        clip.apply_fades(fade_in, fade_out);
+        output.start(clip)?;
+        if i + 1 == total_clips {
+            output.wait_until_done();
+        } else if fade_out.is_zero() {
+            output.wait_until_done();
+            output.wait(gap);
+        } else {
+            // The next clip starts as this one begins to fade out
+            output.wait(durations[i].saturating_sub(fade_out));
+        }
+    }
+    Ok(())
+}
+
+/// Plays clips through rodio, one sink per clip so overlapping clips mix
+struct SpeakerOutput<'a> {
+    tts: &'a TTSManager,
+    stream: (rodio::OutputStream, rodio::OutputStreamHandle),
+    sinks: Vec<rodio::Sink>,
+    reopened: bool,
+}
+
+impl ClipOutput for SpeakerOutput<'_> {
+    fn start(&mut self, clip: DecodedClip) -> Result<(), Box<dyn std::error::Error>> {
+        self.sinks.retain(|sink| !sink.empty());
+        let sink = match rodio::Sink::try_new(&self.stream.1) {
+            Ok(sink) => sink,
+            Err(e) if !self.reopened => {
+                // The device went away mid-message; re-open once and carry on
+                println!("Failed to create audio sink: {}, re-opening the output device", e);
+                self.reopened = true;
+                self.sinks.clear();
+                self.stream = self.tts.open_output_stream()?;
+                rodio::Sink::try_new(&self.stream.1)?
+            },
+            Err(e) => {
+                println!("Failed to create audio sink: {}", e);
+                return Err(e.into());
+            }
+        };
+        self.tts.apply_volume(&sink);
+        sink.append(clip.into_source());
+        self.sinks.push(sink);
+        Ok(())
+    }
+
+    fn wait(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    fn wait_until_done(&mut self) {
+        for sink in self.sinks.drain(..) {
+            sink.sleep_until_end();
+        }
+    }
+}
+
+/// Speech settings and the state they share.
+///
+/// Clones share the backend, cache, usage and playback state, so a clone
+/// handed to the speech thread reports back to the one the window holds;
+/// settings changed afterwards only apply to messages queued after them.
+#[derive(Clone)]
 pub struct TTSManager {
-    client: reqwest::Client,
+    backend: Arc<dyn SpeechBackend>,
+    /// Endpoint to switch to once a key is entered, when started in dry run for lack of one
+    pending_endpoint: Option<(String, Vec<(String, String)>)>,
     cache: Arc<Mutex<HashMap<CacheKey, Vec<u8>>>>,
     voice_type: String,
     volume: f32,
     speech_rate: f32,
     audio_enabled: bool,
+    output_device: Option<String>,
+    processing: AudioProcessing,
+    dynamic_cache_bucket: f32,
+    last_spoken_text: Arc<Mutex<Option<String>>>,
+    last_clips: Arc<Mutex<Vec<Vec<u8>>>>,
+    status: Arc<Mutex<ConnectionStatus>>,
+    generation_permits: Arc<Semaphore>,
+    max_concurrent_generations: usize,
+    rate_limiter: Arc<Mutex<RateLimiter>>,
+    severity_voices: HashMap<Severity, String>,
+    /// Last output device recovery, so each change is announced once
+    last_device_recovery: Arc<Mutex<Option<String>>>,
+    device_notice: Arc<Mutex<Option<String>>>,
+    speaking: Arc<AtomicBool>,
+    watchdog: Arc<Mutex<TtsWatchdog>>,
+    archive_dir: PathBuf,
+    usage: Arc<Mutex<TtsUsage>>,
+    /// Monthly spend in US dollars at which speech stops; 0 is no cap
+    monthly_budget: f64,
+    /// Where the monthly count is saved, if it persists across sessions
+    usage_path: Option<PathBuf>,
+    budget_notice: Arc<Mutex<Option<String>>>,
+    /// Overlap between consecutive clips of a message; zero leaves a short gap instead
+    crossfade: Duration,
+    /// Say numbers as words ("about seventy-three percent") instead of leaving them to the voice
+    natural_numbers: bool,
 }
 
 impl TTSManager {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_endpoint(DEFAULT_TTS_BASE_URL, Vec::new())
+    }
+
+    /// Like `new`, but talks to an OpenAI-compatible endpoint at `base_url`
+    pub fn with_endpoint(base_url: &str, extra_headers: Vec<(String, String)>) -> Result<Self, Box<dyn std::error::Error>> {
         println!("Initializing TTSManager...");
-        
+
+        // Validate the endpoint even when the key is missing so bad config is reported early
+        let backend = OpenAiBackend::with_endpoint(base_url, extra_headers.clone())?;
+
         // Check if OpenAI API key is available
         if std::env::var("OPENAI_API_KEY").is_err() {
-            eprintln!("Error: OPENAI_API_KEY environment variable not found");
-            return Ok(Self {
-                client: reqwest::Client::new(),
-                cache: Arc::new(Mutex::new(HashMap::new())),
-                voice_type: "alloy".to_string(),
-                volume: 1.0,
-                speech_rate: 1.0,
-                audio_enabled: true,
-            });
+            eprintln!("Error: OPENAI_API_KEY environment variable not found, using dry-run backend");
+            let mut tts = Self::with_backend(Box::new(DryRunBackend::new()));
+            tts.pending_endpoint = Some((base_url.to_string(), extra_headers));
+            return Ok(tts);
         }
 
-        let tts = Self {
-            client: reqwest::Client::new(),
-            cache: Arc::new(Mutex::new(HashMap::new())),
-            voice_type: "alloy".to_string(),
-            volume: 1.0,
-            speech_rate: 1.0,
-            audio_enabled: true,
-        };
+        let tts = Self::with_backend(Box::new(backend));
 
         // Initialize audio cache
         println!("Initializing audio cache...");
@@ -53,17 +847,132 @@ impl TTSManager {
         Ok(tts)
     }
 
+    pub fn with_backend(backend: Box<dyn SpeechBackend>) -> Self {
+        Self {
+            backend: Arc::from(backend),
+            pending_endpoint: None,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            voice_type: "alloy".to_string(),
+            volume: 1.0,
+            speech_rate: 1.0,
+            audio_enabled: true,
+            output_device: None,
+            processing: AudioProcessing::default(),
+            dynamic_cache_bucket: message_system::DEFAULT_DISCRETIZE_BUCKET,
+            last_spoken_text: Arc::new(Mutex::new(None)),
+            last_clips: Arc::new(Mutex::new(Vec::new())),
+            status: Arc::new(Mutex::new(ConnectionStatus::default())),
+            generation_permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_GENERATIONS)),
+            max_concurrent_generations: DEFAULT_MAX_CONCURRENT_GENERATIONS,
+            rate_limiter: Arc::new(Mutex::new(RateLimiter::new(0))),
+            severity_voices: HashMap::new(),
+            last_device_recovery: Arc::new(Mutex::new(None)),
+            device_notice: Arc::new(Mutex::new(None)),
+            speaking: Arc::new(AtomicBool::new(false)),
+            watchdog: Arc::new(Mutex::new(TtsWatchdog::new(DEFAULT_WATCHDOG_THRESHOLD))),
+            archive_dir: Path::new("cache").join("tts").join("archive"),
+            usage: Arc::new(Mutex::new(TtsUsage::new(&current_month()))),
+            monthly_budget: 0.0,
+            usage_path: None,
+            budget_notice: Arc::new(Mutex::new(None)),
+            crossfade: Duration::ZERO,
+            natural_numbers: false,
+        }
+    }
+
+    /// Whether speech is actually synthesized and played
+    pub fn is_live(&self) -> bool {
+        self.backend.is_live()
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Text of the most recent message passed to speak
+    #[cfg(test)]
+    pub fn last_spoken_text(&self) -> Option<String> {
+        self.last_spoken_text.lock().unwrap().clone()
+    }
+
+    /// Whether there is a spoken message to replay
+    pub fn has_replay(&self) -> bool {
+        !self.last_clips.lock().unwrap().is_empty()
+    }
+
+    /// Plays the most recently spoken message again from its stored clips, without re-generating it.
+    ///
+    /// Returns false if nothing has been spoken yet.
+    pub async fn replay_last(&self) -> Result<bool, Box<dyn std::error::Error>> {
+        let clips = self.last_clips.lock().unwrap().clone();
+        if clips.is_empty() {
+            return Ok(false);
+        }
+        if !self.audio_enabled {
+            println!("Audio is disabled, skipping replay");
+            return Ok(true);
+        }
+        if !self.backend.is_live() {
+            println!("Dry run, skipping replay of {} clips", clips.len());
+            return Ok(true);
+        }
+        println!("Replaying {} audio clips", clips.len());
+        self.play_composed_message(clips).await?;
+        Ok(true)
+    }
+
+    /// Uses `key` for all further requests instead of the OPENAI_API_KEY read at startup.
+    ///
+    /// When the app started in dry run because no key was set, this switches
+    /// to the configured endpoint. The key is only kept in memory.
+    pub fn set_api_key(&mut self, key: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err("API key is empty".into());
+        }
+        if let Some((base_url, extra_headers)) = self.pending_endpoint.take() {
+            println!("API key provided, switching from dry run to {}", base_url);
+            self.backend = Arc::new(OpenAiBackend::with_endpoint(&base_url, extra_headers)?);
+            // Dry-run clips are silent; nothing spoken so far may be replayed from them
+            self.forget_cached_audio();
+        }
+        self.backend.set_api_key(key.to_string());
+        Ok(())
+    }
+
+    /// Sets `key` and makes a short test request with it, returning the request latency
+    pub async fn validate_api_key(&mut self, key: &str) -> Result<Duration, Box<dyn std::error::Error>> {
+        self.set_api_key(key)?;
+        let started = Instant::now();
+        self.generate_audio("Key check.").await?;
+        Ok(started.elapsed())
+    }
+
+    /// Outcome of the most recent synthesis request
+    pub fn connection_status(&self) -> ConnectionStatus {
+        self.status.lock().unwrap().clone()
+    }
+
     pub fn is_some(&self) -> bool {
         true
     }
 
+    /// Where archive_and_clear_cache writes cached clips
+    pub fn set_archive_dir(&mut self, archive_dir: PathBuf) {
+        self.archive_dir = archive_dir;
+    }
+
     pub fn archive_and_clear_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
         println!("Archiving and clearing audio cache");
         let mut cache = self.cache.lock().unwrap();
-        
+        if cache.is_empty() {
+            println!("Audio cache is empty, nothing to archive");
+            return Ok(());
+        }
+
         // Create archive directory if it doesn't exist
-        let archive_dir = Path::new("cache").join("tts").join("archive");
-        if let Err(e) = fs::create_dir_all(&archive_dir) {
+        let archive_dir = &self.archive_dir;
+        if let Err(e) = fs::create_dir_all(archive_dir) {
             println!("Failed to create archive directory: {}", e);
             return Ok(());
         }
@@ -101,31 +1010,227 @@ impl TTSManager {
     }
 
     pub fn set_voice_type(&mut self, voice_type: String) {
+        if voice_type != self.voice_type {
+            self.forget_cached_audio();
+        }
         self.voice_type = voice_type;
     }
 
+    /// Voices used instead of the personality voice for messages of a given severity
+    pub fn set_severity_voices<I: IntoIterator<Item = (Severity, String)>>(&mut self, voices: I) {
+        let voices: HashMap<Severity, String> = voices.into_iter()
+            .filter(|(_, voice)| !voice.trim().is_empty())
+            .collect();
+        if voices != self.severity_voices {
+            self.forget_cached_audio();
+        }
+        self.severity_voices = voices;
+    }
+
+    /// Drops cached clips once they no longer sound the way new ones would
+    fn forget_cached_audio(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Voice a message of `severity` is spoken with
+    pub fn voice_for(&self, severity: Severity) -> &str {
+        self.severity_voices.get(&severity).unwrap_or(&self.voice_type)
+    }
+
     pub fn set_volume(&mut self, volume: f32) {
         self.volume = volume.clamp(0.0, 1.0);
     }
 
+    /// Plays `sink` at the volume setting; call before anything is appended to it
+    fn apply_volume(&self, sink: &rodio::Sink) {
+        sink.set_volume(self.volume);
+    }
+
     pub fn set_speech_rate(&mut self, rate: f32) {
-        self.speech_rate = rate.clamp(0.5, 2.0);
+        let rate = rate.clamp(0.5, 2.0);
+        if rate != self.speech_rate {
+            self.forget_cached_audio();
+        }
+        self.speech_rate = rate;
     }
 
     pub fn set_audio_enabled(&mut self, enabled: bool) {
         self.audio_enabled = enabled;
     }
 
-    pub async fn speak(&mut self, message_parts: Vec<MessagePart>, personality: &PersonalitySettings) -> Result<(), Box<dyn std::error::Error>> {
+    /// Selects the output device by name; None uses the system default
+    pub fn set_output_device(&mut self, device: Option<String>) {
+        self.output_device = device;
+    }
+
+    pub fn set_audio_processing(&mut self, processing: AudioProcessing) {
+        if processing != self.processing {
+            self.forget_cached_audio();
+        }
+        self.processing = processing;
+    }
+
+    /// How long consecutive clips of a message overlap; zero plays them apart with a short gap
+    pub fn set_crossfade(&mut self, crossfade: Duration) {
+        self.crossfade = crossfade;
+    }
+
+    /// Rewrite numbers as words before they are synthesized; what is displayed is unchanged
+    pub fn set_natural_numbers(&mut self, enabled: bool) {
+        if enabled != self.natural_numbers {
+            self.forget_cached_audio();
+        }
+        self.natural_numbers = enabled;
+    }
+
+    /// Numbers in dynamic text are rounded to this bucket for cache keys; 0 keys on the exact text
+    pub fn set_dynamic_cache_bucket(&mut self, bucket: f32) {
+        self.dynamic_cache_bucket = bucket.max(0.0);
+    }
+
+    /// Caps how many speech API requests run at once; further requests wait their turn
+    pub fn set_max_concurrent_generations(&mut self, limit: usize) {
+        let limit = limit.max(1);
+        if limit != self.max_concurrent_generations {
+            self.max_concurrent_generations = limit;
+            self.generation_permits = Arc::new(Semaphore::new(limit));
+        }
+    }
+
+    /// Paces speech API requests to this many per minute, making bursts wait; 0 removes the limit
+    pub fn set_requests_per_minute(&mut self, requests_per_minute: u32) {
+        let requests_per_minute = requests_per_minute.min(MAX_REQUESTS_PER_MINUTE);
+        let mut limiter = self.rate_limiter.lock().unwrap();
+        if limiter.requests_per_minute() != requests_per_minute {
+            *limiter = RateLimiter::new(requests_per_minute);
+        }
+    }
+
+    pub fn set_watchdog_threshold(&mut self, threshold: u32) {
+        self.watchdog.lock().unwrap().set_threshold(threshold);
+    }
+
+    #[cfg(test)]
+    pub fn watchdog(&self) -> TtsWatchdog {
+        self.watchdog.lock().unwrap().clone()
+    }
+
+    /// Whether speech is down and the watchdog's recovery attempts have failed
+    pub fn is_offline(&self) -> bool {
+        self.watchdog.lock().unwrap().is_offline()
+    }
+
+    /// Whether a message is being played right now
+    pub fn is_speaking(&self) -> bool {
+        self.speaking.load(Ordering::SeqCst)
+    }
+
+    /// Takes the latest "audio device changed" message, if the output device was recovered since the last call
+    pub fn take_device_notice(&self) -> Option<String> {
+        self.device_notice.lock().unwrap().take()
+    }
+
+    /// Stops speaking once this month's estimated spend reaches `budget` US dollars; 0 removes the cap
+    pub fn set_monthly_budget(&mut self, budget: f64) {
+        self.monthly_budget = budget.max(0.0);
+    }
+
+    pub fn monthly_budget(&self) -> f64 {
+        self.monthly_budget
+    }
+
+    /// Keeps the monthly count in `path`, picking up where an earlier session left off
+    pub fn set_usage_path(&mut self, path: PathBuf) {
+        let mut saved = TtsUsage::load(&path);
+        let mut usage = self.usage.lock().unwrap();
+        saved.roll_over(&usage.month);
+        saved.month_characters += usage.month_characters;
+        saved.session_characters = usage.session_characters;
+        *usage = saved;
+        self.usage_path = Some(path);
+    }
+
+    /// Characters synthesized so far, with the month rolled over if it has changed
+    pub fn usage(&self) -> TtsUsage {
+        let mut usage = self.usage.lock().unwrap();
+        usage.roll_over(&current_month());
+        usage.clone()
+    }
+
+    /// Whether this month's spend has reached the budget, so new speech is skipped
+    pub fn budget_exceeded(&self) -> bool {
+        self.usage().over_budget(self.monthly_budget)
+    }
+
+    /// Takes the "budget reached" message, set once when speech is first paused for the budget
+    pub fn take_budget_notice(&self) -> Option<String> {
+        self.budget_notice.lock().unwrap().take()
+    }
+
+    /// Counts characters sent to the API, announcing the first request that uses up the budget
+    fn record_usage(&self, characters: u64) {
+        let mut usage = self.usage.lock().unwrap();
+        let was_over = usage.over_budget(self.monthly_budget);
+        usage.record(characters, &current_month());
+        if !was_over && usage.over_budget(self.monthly_budget) {
+            *self.budget_notice.lock().unwrap() = Some(format!(
+                "💸 TTS budget of ${:.2} reached, speech paused until next month", self.monthly_budget
+            ));
+        }
+        if let Some(path) = &self.usage_path {
+            if let Err(e) = usage.save(path) {
+                eprintln!("Failed to save TTS usage to {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Opens the configured output device, re-enumerating and falling back to the default device if it has gone away
+    fn open_output_stream(&self) -> Result<(rodio::OutputStream, rodio::OutputStreamHandle), Box<dyn std::error::Error>> {
+        let (output, recovery) = open_with_recovery(open_device, self.output_device.as_deref(), list_output_devices)?;
+        let mut last_recovery = self.last_device_recovery.lock().unwrap();
+        if recovery.is_some() && *last_recovery != recovery {
+            *self.device_notice.lock().unwrap() = recovery.clone();
+        }
+        *last_recovery = recovery;
+        Ok(output)
+    }
+
+    /// Whether speech is on and within budget; messages are skipped otherwise
+    fn can_speak(&self) -> bool {
+        self.audio_enabled && !(self.backend.is_live() && self.budget_exceeded())
+    }
+
+    fn remember_text(&self, message_parts: &[MessagePart]) {
+        let full_text = message_parts.iter()
+            .map(|part| part.text())
+            .filter(|text| !text.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        *self.last_spoken_text.lock().unwrap() = Some(full_text);
+    }
+
+    /// Speaks the parts in the default voice; the personality has already shaped their text
+    pub async fn speak(&mut self, message_parts: Vec<MessagePart>, _personality: &PersonalitySettings) -> Result<(), Box<dyn std::error::Error>> {
+        self.speak_with_severity(message_parts, Severity::Info).await
+    }
+
+    /// Like `speak`, but uses the voice configured for `severity` for every clip
+    pub async fn speak_with_severity(&mut self, message_parts: Vec<MessagePart>, severity: Severity) -> Result<(), Box<dyn std::error::Error>> {
         if !self.audio_enabled {
             println!("Audio is disabled, skipping speech");
             return Ok(());
         }
+        if self.backend.is_live() && self.budget_exceeded() {
+            println!("Monthly TTS budget reached, skipping speech");
+            return Ok(());
+        }
 
         println!("Starting speak function with {} message parts", message_parts.len());
+        self.remember_text(&message_parts);
         
         let mut audio_clips = Vec::new();
-        
+        let mut failed = false;
+
         for part in message_parts {
             println!("Processing message part: {:?}", part);
             
@@ -140,137 +1245,306 @@ impl TTSManager {
                 continue;
             }
 
+            // Repeated lines are played from the cache instead of synthesized again
+            let cache_key = self.get_cache_key_for(&part, severity);
+            if let Some(audio_data) = self.cache.lock().unwrap().get(&cache_key) {
+                println!("Using cached audio for text: {}", text);
+                audio_clips.push(audio_data.clone());
+                continue;
+            }
+
+            let spoken;
+            let text = if self.natural_numbers {
+                spoken = spoken_numbers::humanize_numbers(text);
+                &spoken
+            } else {
+                text
+            };
+
             println!("Generating audio for text: {}", text);
             
-            let audio_data = match self.generate_audio(text).await {
+            let voice = self.voice_for(severity).to_string();
+            let audio_data = match self.generate_audio_with_voice(text, &voice).await {
                 Ok(data) => data,
                 Err(e) => {
                     eprintln!("Failed to generate audio: {}", e);
+                    failed = true;
                     continue; // Skip this part but continue with others
                 }
             };
 
             println!("Successfully generated audio data of size: {} bytes", audio_data.len());
+
+            // Trim silence and even out loudness before the clip is cached
+            let audio_data = if self.backend.is_live() {
+                match audio_processing::process_clip(&audio_data, &self.processing) {
+                    Ok(processed) => processed,
+                    Err(e) => {
+                        eprintln!("Failed to post-process audio, using raw clip: {}", e);
+                        audio_data
+                    }
+                }
+            } else {
+                audio_data
+            };
             
-            // Store in cache with proper CacheKey type
-            let cache_key = self.get_cache_key(&part, personality);
             self.cache.lock().unwrap().insert(cache_key, audio_data.clone());
             
             // Add to clips for playback
             audio_clips.push(audio_data);
         }
 
-        // Play all generated audio clips
+        // Keep the composed message around for replay
         if !audio_clips.is_empty() {
+            *self.last_clips.lock().unwrap() = audio_clips.clone();
+        }
+
+        // Play all generated audio clips
+        if !self.backend.is_live() {
+            println!("Dry run, skipping playback of {} clips", audio_clips.len());
+        } else if !audio_clips.is_empty() {
             println!("Playing {} audio clips", audio_clips.len());
             if let Err(e) = self.play_composed_message(audio_clips).await {
                 eprintln!("Failed to play audio: {}", e);
+                failed = true;
+            }
+        }
+
+        if !failed {
+            self.watchdog.lock().unwrap().record_success();
+        } else if self.watchdog.lock().unwrap().record_failure(Instant::now()) {
+            self.recover().await;
+        }
+
+        Ok(())
+    }
+
+    /// Reinitializes the client and output device after repeated failures, then checks the key
+    /// by speaking AUDIO_RESTORED_MESSAGE
+    async fn recover(&mut self) {
+        let watchdog = {
+            let mut watchdog = self.watchdog.lock().unwrap();
+            watchdog.begin_recovery();
+            watchdog.clone()
+        };
+        println!(
+            "TTS watchdog: {} consecutive failures, reinitializing speech (attempt {})",
+            watchdog.consecutive_failures(), watchdog.recovery_attempts()
+        );
+        self.backend.reset();
+        *self.last_device_recovery.lock().unwrap() = None;
+
+        let result = match self.generate_audio(AUDIO_RESTORED_MESSAGE).await {
+            Ok(clip) if self.backend.is_live() => self.play_composed_message(vec![clip]).await,
+            Ok(_) => Ok(()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                println!("TTS watchdog: speech restored");
+                self.watchdog.lock().unwrap().record_success();
+                *self.device_notice.lock().unwrap() = Some("🔊 Audio restored".to_string());
+            }
+            Err(e) => {
+                eprintln!("TTS watchdog: recovery failed, backing off: {}", e);
+                self.watchdog.lock().unwrap().recovery_failed(Instant::now());
             }
         }
-
-        Ok(())
     }
 
-    fn get_cache_key(&self, message: &MessagePart, personality: &PersonalitySettings) -> CacheKey {
+    /// Cache key including the voice `severity` resolves to, so clips spoken in different voices never mix
+    fn get_cache_key_for(&self, message: &MessagePart, severity: Severity) -> CacheKey {
+        let voice = self.voice_for(severity).to_string();
         match message {
-            MessagePart::Static(text) => CacheKey::Static(text.clone(), personality.clone()),
-            MessagePart::Dynamic(text) => CacheKey::Dynamic(text.clone()),
-            MessagePart::Full(text) => CacheKey::Full("full".to_string(), text.clone()),
+            // Volume is applied at playback and traits are already in the text, so neither changes the clip
+            MessagePart::Static(text) => CacheKey::Static(text.clone(), voice, (self.speech_rate * 100.0).round() as u32),
+            MessagePart::Dynamic(text) => CacheKey::Dynamic(message_system::discretize_numbers(text, self.dynamic_cache_bucket), voice),
+            MessagePart::Full(text) => CacheKey::Full("full".to_string(), text.clone(), voice),
         }
     }
 
     async fn generate_audio(&self, text: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        println!("Generating audio for text: {}", text);
-        let api_key = std::env::var("OPENAI_API_KEY")?;
-        let url = "https://api.openai.com/v1/audio/speech";
-
-        println!("Making API request to OpenAI TTS endpoint");
-        let response = self.client
-            .post(url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .json(&json!({
-                "model": "tts-1",
-                "input": text,
-                "voice": self.voice_type,
-                "speed": self.speech_rate
-            }))
-            .send()
-            .await?;
+        self.generate_audio_with_voice(text, &self.voice_type).await
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            println!("OpenAI API error: {}", error_text);
-            return Err(format!("OpenAI API error: {}", error_text).into());
-        }
+    async fn generate_audio_with_voice(&self, text: &str, voice: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        // Queue behind other generations so bursts of warnings don't trip rate limits
+        let _permit = Arc::clone(&self.generation_permits).acquire_owned().await?;
 
-        println!("Successfully received response from OpenAI");
-        let audio_data = response.bytes().await?.to_vec();
-        println!("Converted response to {} bytes of audio data", audio_data.len());
-        Ok(audio_data)
+        println!("Generating audio for text: {} (backend: {})", text, self.backend.name());
+        let mut attempt = 1;
+        loop {
+            // Retries count against the rate limit too
+            let wait = self.rate_limiter.lock().unwrap().reserve(Instant::now());
+            if !wait.is_zero() {
+                println!("Pacing TTS request, waiting {:.1}s for the rate limit", wait.as_secs_f64());
+                tokio::time::sleep(wait).await;
+            }
+
+            let started = Instant::now();
+            let result = self.backend
+                .synthesize(text, voice, self.speech_rate)
+                .await;
+            let latency = started.elapsed();
+
+            match result {
+                Ok(audio_data) => {
+                    self.status.lock().unwrap().record_success(latency);
+                    if self.backend.is_live() {
+                        self.record_usage(text.chars().count() as u64);
+                    }
+                    return Ok(audio_data);
+                }
+                Err(e) if attempt < MAX_TTS_ATTEMPTS && is_transient(e.as_ref()) => {
+                    eprintln!("TTS request failed (attempt {} of {}), retrying: {}", attempt, MAX_TTS_ATTEMPTS, e);
+                    self.status.lock().unwrap().record_retry(e.to_string());
+                    attempt += 1;
+                    tokio::time::sleep(TTS_RETRY_DELAY).await;
+                }
+                Err(e) => {
+                    self.status.lock().unwrap().record_failure(latency, e.to_string());
+                    return Err(e as Box<dyn std::error::Error>);
+                }
+            }
+        }
     }
 
     async fn play_composed_message(&self, clips: Vec<Vec<u8>>) -> Result<(), Box<dyn std::error::Error>> {
+        self.speaking.store(true, Ordering::SeqCst);
+        let result = self.play_clips(clips);
+        self.speaking.store(false, Ordering::SeqCst);
+        result
+    }
+
+    fn play_clips(&self, clips: Vec<Vec<u8>>) -> Result<(), Box<dyn std::error::Error>> {
         println!("Initializing audio output device");
-        let (_stream, stream_handle) = match rodio::OutputStream::try_default() {
+        let stream = match self.open_output_stream() {
             Ok(output) => {
                 println!("Successfully initialized audio output device");
                 output
             },
             Err(e) => {
                 println!("Failed to initialize audio output device: {}", e);
-                return Err(e.into());
+                return Err(e);
             }
         };
 
-        let total_clips = clips.len();
-        for (i, clip) in clips.into_iter().enumerate() {
-            println!("Playing clip {} of {}", i + 1, total_clips);
-            let sink = match rodio::Sink::try_new(&stream_handle) {
-                Ok(sink) => {
-                    println!("Successfully created audio sink");
-                    sink
-                },
-                Err(e) => {
-                    println!("Failed to create audio sink: {}", e);
-                    return Err(e.into());
-                }
-            };
-            
-            let cursor = std::io::Cursor::new(clip);
-            match rodio::Decoder::new(cursor) {
-                Ok(decoder) => {
-                    println!("Successfully created audio decoder");
-                    sink.append(decoder);
-                    sink.sleep_until_end();
-                    println!("Finished playing clip {}", i + 1);
-                },
-                Err(e) => {
-                    println!("Failed to create audio decoder: {}", e);
-                    return Err(e.into());
+        let clips = match clips.iter().map(|clip| DecodedClip::decode(clip)).collect::<Result<Vec<_>, _>>() {
+            Ok(clips) => clips,
+            Err(e) => {
+                println!("Failed to create audio decoder: {}", e);
+                return Err(e);
+            }
+        };
+        let mut output = SpeakerOutput { tts: self, stream, sinks: Vec::new(), reopened: false };
+        play_sequence(&mut output, clips, self.crossfade, CLIP_GAP)
+    }
+
+}
+
+/// Work handed to the speech thread
+enum SpeechJob {
+    Speak {
+        tts: TTSManager,
+        label: String,
+        parts: Vec<MessagePart>,
+        severity: Severity,
+    },
+    Replay(TTSManager),
+}
+
+/// How a queued message went
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechOutcome {
+    pub label: String,
+    /// Why the message wasn't spoken, if it failed
+    pub error: Option<String>,
+}
+
+/// Speaks messages one after another on a background thread.
+///
+/// Synthesis, rate-limit pacing and playback all happen there, so whoever
+/// queues a message never waits on them and polls for outcomes instead.
+/// Messages play in the order they were queued, never over each other.
+pub struct SpeechQueue {
+    jobs: mpsc::Sender<SpeechJob>,
+    outcomes: mpsc::Receiver<SpeechOutcome>,
+    /// Messages queued whose outcome hasn't been polled yet
+    in_flight: usize,
+}
+
+impl SpeechQueue {
+    /// Starts the speech thread, which runs the async speech calls on `runtime`
+    pub fn new(runtime: tokio::runtime::Handle) -> Self {
+        let (jobs, queued) = mpsc::channel();
+        let (report, outcomes) = mpsc::channel();
+        std::thread::spawn(move || {
+            for job in queued {
+                let (label, result) = match job {
+                    SpeechJob::Speak { mut tts, label, parts, severity } => {
+                        (label, runtime.block_on(tts.speak_with_severity(parts, severity)))
+                    }
+                    SpeechJob::Replay(tts) => ("replay".to_string(), runtime.block_on(tts.replay_last()).map(|_| ())),
+                };
+                let outcome = SpeechOutcome { label, error: result.err().map(|e| e.to_string()) };
+                if report.send(outcome).is_err() {
+                    break;
                 }
             }
+        });
+        Self { jobs, outcomes, in_flight: 0 }
+    }
 
-            // Add a small pause between clips
-            tokio::time::sleep(TokioDuration::from_millis(100)).await;
+    /// Queues `parts` to be spoken with `tts` as it is set up now.
+    ///
+    /// The text counts as the last spoken straight away, as it would with a direct speak call.
+    pub fn speak(&mut self, tts: &TTSManager, label: &str, parts: Vec<MessagePart>, severity: Severity) {
+        if tts.can_speak() {
+            tts.remember_text(&parts);
         }
-        Ok(())
+        self.push(SpeechJob::Speak {
+            tts: tts.clone(),
+            label: label.to_string(),
+            parts,
+            severity,
+        });
     }
 
-    pub fn handle_message(&mut self, message_part: &MessagePart) {
-        match message_part {
-            MessagePart::Static(text) => {
-                // Handle static text
-                println!("TTS: {}", text);
-            }
-            MessagePart::Dynamic(text) => {
-                // Handle dynamic text
-                println!("TTS: {}", text);
-            }
-            MessagePart::Full(text) => {
-                // Handle full text
-                println!("TTS: {}", text);
-            }
+    /// Queues a replay of the last message `tts` spoke
+    pub fn replay(&mut self, tts: &TTSManager) {
+        self.push(SpeechJob::Replay(tts.clone()));
+    }
+
+    fn push(&mut self, job: SpeechJob) {
+        if self.jobs.send(job).is_ok() {
+            self.in_flight += 1;
+        } else {
+            eprintln!("Speech thread has stopped, dropping message");
+        }
+    }
+
+    /// Outcomes of the messages finished since the last call, in the order they were queued
+    pub fn poll(&mut self) -> Vec<SpeechOutcome> {
+        let finished: Vec<SpeechOutcome> = self.outcomes.try_iter().collect();
+        self.in_flight -= finished.len();
+        finished
+    }
+
+    /// Whether every queued message has finished and been polled
+    pub fn is_idle(&self) -> bool {
+        self.in_flight == 0
+    }
+
+    /// Blocks until every queued message has finished, returning their outcomes
+    #[cfg(test)]
+    pub fn wait_idle(&mut self) -> Vec<SpeechOutcome> {
+        let mut finished = Vec::new();
+        while self.in_flight > 0 {
+            let outcome = self.outcomes.recv_timeout(Duration::from_secs(10)).expect("speech thread finishes");
+            self.in_flight -= 1;
+            finished.push(outcome);
         }
+        finished
     }
 }
 
@@ -301,10 +1575,10 @@ mod tests {
                 MessagePart::Static(test_text.clone()),
                 MessagePart::Dynamic("audio system".to_string()),
             ];
-            let key = tts.get_cache_key(&messages[0], &personality);
+            let key = tts.get_cache_key_for(&messages[0], Severity::Info);
             match &messages[0] {
                 MessagePart::Static(text) => {
-                    assert!(matches!(key, CacheKey::Static(t, _) if t == test_text));
+                    assert!(matches!(key, CacheKey::Static(t, _, _) if t == test_text));
                 }
                 _ => panic!("Expected Static message part"),
             }
@@ -396,7 +1670,7 @@ mod tests {
             
             // Create mock audio data
             let mock_audio_data = vec![0x1, 0x2, 0x3, 0x4, 0x5]; // Mock MP3 header
-            let cache_key = tts.get_cache_key(&test_message[0], &personality);
+            let cache_key = tts.get_cache_key_for(&test_message[0], Severity::Info);
             tts.cache.lock().unwrap().insert(cache_key, mock_audio_data.clone());
             println!("Added mock audio data to cache");
 
@@ -448,6 +1722,674 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_dry_run_backend_skips_network() {
+        let backend = DryRunBackend::new();
+        let requests = backend.request_counter();
+        let mut tts = TTSManager::with_backend(Box::new(backend));
+        assert!(!tts.is_live());
+
+        let message = vec![MessagePart::Static("Dry run test".to_string())];
+        tts.speak(message, &PersonalitySettings::default()).await.unwrap();
+
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(tts.last_spoken_text().as_deref(), Some("Dry run test"));
+    }
+
+    #[test]
+    fn test_speaking_on_a_clone_shows_on_the_original() {
+        let tts = TTSManager::with_backend(Box::new(DryRunBackend::new()));
+        let speech_thread_copy = tts.clone();
+
+        speech_thread_copy.speaking.store(true, Ordering::SeqCst);
+        assert!(tts.is_speaking(), "the window sees the speech thread talking");
+        speech_thread_copy.speaking.store(false, Ordering::SeqCst);
+        assert!(!tts.is_speaking());
+    }
+
+    #[test]
+    fn test_speech_queue_speaks_in_order_without_blocking() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let backend = DryRunBackend::with_delay(TokioDuration::from_millis(200));
+        let requests = backend.request_counter();
+        let tts = TTSManager::with_backend(Box::new(backend));
+        let mut queue = SpeechQueue::new(runtime.handle().clone());
+
+        let started = Instant::now();
+        queue.speak(&tts, "first", vec![MessagePart::Static("First".to_string())], Severity::Info);
+        queue.speak(&tts, "second", vec![MessagePart::Static("Second".to_string())], Severity::Warning);
+        assert!(started.elapsed() < Duration::from_millis(200), "queueing never waits on synthesis");
+        assert!(!queue.is_idle());
+        assert_eq!(tts.last_spoken_text().as_deref(), Some("Second"));
+
+        let outcomes = queue.wait_idle();
+        assert!(outcomes.iter().all(|outcome| outcome.error.is_none()), "{:?}", outcomes);
+        let labels: Vec<&str> = outcomes.iter().map(|outcome| outcome.label.as_str()).collect();
+        assert_eq!(labels, ["first", "second"]);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert!(queue.is_idle());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_message_is_played_from_cache() {
+        let backend = DryRunBackend::new();
+        let requests = backend.request_counter();
+        let mut tts = TTSManager::with_backend(Box::new(backend));
+        let personality = PersonalitySettings::default();
+
+        for _ in 0..2 {
+            tts.speak(vec![MessagePart::Static("CPU over threshold".to_string())], &personality).await.unwrap();
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 1, "the second time comes from the cache");
+
+        // Neither volume nor traits change the clip
+        tts.set_volume(0.3);
+        let anxious = PersonalitySettings { anxiety_level: 90, ..PersonalitySettings::default() };
+        tts.speak(vec![MessagePart::Static("CPU over threshold".to_string())], &anxious).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // A severity without its own voice shares the default one's clips
+        tts.speak_with_severity(vec![MessagePart::Static("CPU over threshold".to_string())], Severity::Critical).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // A different voice or rate starts over
+        tts.set_severity_voices(vec![(Severity::Critical, "onyx".to_string())]);
+        tts.speak_with_severity(vec![MessagePart::Static("CPU over threshold".to_string())], Severity::Critical).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        tts.set_speech_rate(1.5);
+        tts.speak(vec![MessagePart::Static("CPU over threshold".to_string())], &personality).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_lines_cached_in_dry_run_are_synthesized_once_live() {
+        // Stands in for the speech server, counting requests and refusing them
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                counted.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0u8; 4096];
+                let _ = std::io::Read::read(&mut stream, &mut request);
+                let _ = std::io::Write::write_all(&mut stream, b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            }
+        });
+
+        let mut tts = TTSManager::with_backend(Box::new(DryRunBackend::new()));
+        tts.pending_endpoint = Some((format!("http://127.0.0.1:{}/v1", port), Vec::new()));
+        let message = || vec![MessagePart::Static("CPU over threshold".to_string())];
+        tts.speak(message(), &PersonalitySettings::default()).await.unwrap();
+
+        tts.set_api_key("sk-test").unwrap();
+        assert!(tts.is_live());
+        tts.speak(message(), &PersonalitySettings::default()).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1, "the silent dry-run clip isn't replayed");
+    }
+
+    /// Backend that replays a fixed sequence of outcomes
+    struct ScriptedBackend {
+        outcomes: Mutex<std::collections::VecDeque<Result<Vec<u8>, TtsError>>>,
+    }
+
+    impl ScriptedBackend {
+        fn new(outcomes: Vec<Result<Vec<u8>, TtsError>>) -> Self {
+            Self { outcomes: Mutex::new(outcomes.into()) }
+        }
+    }
+
+    #[async_trait]
+    impl SpeechBackend for ScriptedBackend {
+        async fn synthesize(&self, _text: &str, _voice: &str, _speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+            match self.outcomes.lock().unwrap().pop_front() {
+                Some(Ok(audio)) => Ok(audio),
+                Some(Err(e)) => Err(Box::new(e)),
+                None => Err(Box::new(TtsError::TooShort { len: 0 })),
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "Scripted"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_status_transitions() {
+        let unauthorized = TtsError::Api { status: 401, message: "Incorrect API key".to_string() };
+        let unavailable = TtsError::Api { status: 503, message: "Overloaded".to_string() };
+        let tts = TTSManager::with_backend(Box::new(ScriptedBackend::new(vec![
+            Ok(vec![0; MIN_AUDIO_BYTES]),
+            Err(unauthorized),
+            Err(unavailable),
+            Ok(vec![0; MIN_AUDIO_BYTES]),
+        ])));
+        assert_eq!(tts.connection_status().state, ConnectionState::Idle);
+
+        assert!(tts.generate_audio("first").await.is_ok());
+        let status = tts.connection_status();
+        assert_eq!(status.state, ConnectionState::Ok);
+        assert!(status.last_latency.is_some());
+
+        // A bad key is not retried and turns the indicator red
+        assert!(tts.generate_audio("second").await.is_err());
+        let status = tts.connection_status();
+        assert_eq!(status.state, ConnectionState::Failing);
+        assert!(status.describe().contains("Incorrect API key"));
+
+        // A server error is retried once and recovers
+        assert!(tts.generate_audio("third").await.is_ok());
+        let status = tts.connection_status();
+        assert_eq!(status.state, ConnectionState::Ok);
+        assert_eq!(status.last_error, None);
+    }
+
+    #[test]
+    fn test_locale_change_suggests_voice() {
+        assert_eq!(recommended_voice("es"), "nova");
+        assert_eq!(recommended_voice("es-MX"), "nova");
+        assert_eq!(recommended_voice("DE_at"), "onyx");
+        assert_eq!(recommended_voice("xx"), DEFAULT_VOICE);
+        assert!(LOCALES.iter().all(|(_, _, voice)| VOICES.contains(voice)));
+
+        // Switching languages follows the mapping until the user picks a voice by hand
+        let voice = voice_for_locale("es", "alloy", false);
+        assert_eq!(voice, "nova");
+        assert_eq!(voice_for_locale("de", &voice, false), "onyx");
+        assert_eq!(voice_for_locale("de", "fable", true), "fable");
+        assert_eq!(voice_for_locale("en", "fable", true), "fable");
+    }
+
+    #[test]
+    fn test_rate_limiter_spaces_bursts() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(30);
+        let waits: Vec<Duration> = (0..4).map(|_| limiter.reserve(start)).collect();
+        assert_eq!(waits, [0, 2, 4, 6].map(Duration::from_secs), "30 per minute is one every 2 seconds");
+
+        // Time catches up with the reservations, then the bucket refills to a single request
+        let later = start + Duration::from_secs(60);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::from_secs(2));
+        assert_eq!(limiter.reserve(later + Duration::from_secs(1)), Duration::from_secs(3));
+
+        let mut unlimited = RateLimiter::new(0);
+        assert!((0..100).all(|_| unlimited.reserve(start).is_zero()));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_burst_waits_instead_of_failing() {
+        let backend = DryRunBackend::new();
+        let requests = backend.request_counter();
+        let mut tts = TTSManager::with_backend(Box::new(backend));
+        tts.set_requests_per_minute(600);
+
+        let started = Instant::now();
+        for text in ["one", "two", "three"] {
+            tts.generate_audio(text).await.unwrap();
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+        assert!(started.elapsed() >= Duration::from_millis(190), "600 per minute spaces requests 100 ms apart");
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_reinitializes_after_repeated_failures() {
+        let broken = || Err(TtsError::TooShort { len: 0 });
+        let mut tts = TTSManager::with_backend(Box::new(ScriptedBackend::new(vec![
+            broken(), broken(), broken(),
+            // The recovery check fails too, so speech goes offline
+            broken(),
+            broken(),
+        ])));
+        tts.set_watchdog_threshold(3);
+        let message = || vec![MessagePart::Static("Status".to_string())];
+        let settings = PersonalitySettings::default();
+
+        for _ in 0..2 {
+            tts.speak(message(), &settings).await.unwrap();
+        }
+        assert_eq!(tts.watchdog().recovery_attempts(), 0);
+
+        tts.speak(message(), &settings).await.unwrap();
+        assert_eq!(tts.watchdog().recovery_attempts(), 1, "third failure triggers a reinit");
+        assert!(tts.is_offline());
+
+        // Backing off: the next failure doesn't retry straight away
+        tts.speak(message(), &settings).await.unwrap();
+        assert_eq!(tts.watchdog().recovery_attempts(), 1);
+
+        let mut watchdog = TtsWatchdog::new(2);
+        let now = Instant::now();
+        assert!(!watchdog.record_failure(now));
+        assert!(watchdog.record_failure(now));
+        watchdog.begin_recovery();
+        watchdog.recovery_failed(now);
+        assert!(!watchdog.record_failure(now + Duration::from_secs(1)));
+        assert!(watchdog.record_failure(now + WATCHDOG_BASE_BACKOFF));
+        assert!(watchdog.record_success(), "success after being offline reports the recovery");
+        assert!(!watchdog.is_offline());
+    }
+
+    /// Backend that records the most requests it ever saw in flight at once
+    #[derive(Default)]
+    struct ConcurrencyProbe {
+        in_flight: AtomicUsize,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SpeechBackend for ConcurrencyProbe {
+        async fn synthesize(&self, _text: &str, _voice: &str, _speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(TokioDuration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(Vec::new())
+        }
+
+        fn name(&self) -> &'static str {
+            "Concurrency probe"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_generations_are_limited() {
+        for limit in [1, 2] {
+            let probe = ConcurrencyProbe::default();
+            let peak = Arc::clone(&probe.peak);
+            let mut tts = TTSManager::with_backend(Box::new(probe));
+            tts.set_max_concurrent_generations(limit);
+
+            let (a, b, c, d) = tokio::join!(
+                tts.generate_audio("one"),
+                tts.generate_audio("two"),
+                tts.generate_audio("three"),
+                tts.generate_audio("four"),
+            );
+            assert!(a.is_ok() && b.is_ok() && c.is_ok() && d.is_ok());
+            assert_eq!(peak.load(Ordering::SeqCst), limit);
+        }
+    }
+
+    #[test]
+    fn test_retry_marks_status_as_retrying() {
+        let mut status = ConnectionStatus::default();
+        status.record_failure(Duration::from_millis(120), "timed out".to_string());
+        status.record_retry("timed out".to_string());
+        assert_eq!(status.state, ConnectionState::Retrying);
+        assert!(status.describe().contains("120 ms"));
+
+        assert!(is_transient(&TtsError::Api { status: 429, message: String::new() }));
+        assert!(!is_transient(&TtsError::Api { status: 401, message: String::new() }));
+        assert!(!is_transient(&std::env::VarError::NotPresent));
+    }
+
+    /// Backend that records the voice of every synthesis request
+    #[derive(Default)]
+    struct VoiceRecorder {
+        voices: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl SpeechBackend for VoiceRecorder {
+        async fn synthesize(&self, _text: &str, voice: &str, _speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+            self.voices.lock().unwrap().push(voice.to_string());
+            Ok(Vec::new())
+        }
+
+        fn is_live(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &'static str {
+            "Voice recorder"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_critical_messages_use_critical_voice() {
+        let backend = VoiceRecorder::default();
+        let voices = Arc::clone(&backend.voices);
+        let mut tts = TTSManager::with_backend(Box::new(backend));
+        tts.set_severity_voices(vec![(Severity::Critical, "onyx".to_string())]);
+        let personality = PersonalitySettings::default();
+
+        let message = vec![MessagePart::Static("Memory almost full".to_string())];
+        tts.speak_with_severity(message.clone(), Severity::Critical).await.unwrap();
+        tts.speak(message, &personality).await.unwrap();
+
+        assert_eq!(*voices.lock().unwrap(), vec!["onyx".to_string(), "alloy".to_string()]);
+        assert_eq!(tts.voice_for(Severity::Warning), "alloy");
+
+        let part = MessagePart::Dynamic("CPU at 95%".to_string());
+        assert_ne!(
+            tts.get_cache_key_for(&part, Severity::Critical),
+            tts.get_cache_key_for(&part, Severity::Info)
+        );
+        // Severities left on the default voice share its clips
+        assert_eq!(
+            tts.get_cache_key_for(&part, Severity::Warning),
+            tts.get_cache_key_for(&part, Severity::Info)
+        );
+    }
+
+    /// Backend that only accepts one API key, like the real API answering 401 otherwise
+    #[derive(Default)]
+    struct KeyedBackend {
+        key: Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl SpeechBackend for KeyedBackend {
+        async fn synthesize(&self, _text: &str, _voice: &str, _speed: f32) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+            match self.key.lock().unwrap().as_deref() {
+                Some("sk-good") => Ok(vec![0; MIN_AUDIO_BYTES]),
+                Some(_) => Err(Box::new(TtsError::Api { status: 401, message: "Incorrect API key".to_string() })),
+                None => Err(Box::new(TtsError::MissingKey)),
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "Keyed"
+        }
+
+        fn set_api_key(&self, key: String) {
+            *self.key.lock().unwrap() = Some(key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_api_key_uses_in_memory_key() {
+        let mut tts = TTSManager::with_backend(Box::new(KeyedBackend::default()));
+        let error = tts.generate_audio("no key yet").await.unwrap_err();
+        assert!(error.to_string().contains("OPENAI_API_KEY"));
+
+        assert!(tts.validate_api_key("   ").await.is_err());
+
+        let error = tts.validate_api_key("sk-bad").await.unwrap_err();
+        assert!(error.to_string().contains("401"));
+        assert_eq!(tts.connection_status().state, ConnectionState::Failing);
+
+        // Surrounding whitespace from pasting is ignored
+        assert!(tts.validate_api_key(" sk-good\n").await.is_ok());
+        assert_eq!(tts.connection_status().state, ConnectionState::Ok);
+        assert!(tts.generate_audio("still works").await.is_ok());
+    }
+
+    #[test]
+    fn test_failed_open_retries_once_after_reenumerating() {
+        // Headset unplugged: the retry goes to the default device and says so
+        let mut opened = Vec::new();
+        let (output, notice) = open_with_recovery(
+            |device: Option<&str>| {
+                opened.push(device.map(str::to_string));
+                match device {
+                    Some(_) => Err("device disconnected".into()),
+                    None => Ok("default stream"),
+                }
+            },
+            Some("USB Headset"),
+            || vec!["Speakers".to_string()],
+        ).unwrap();
+        assert_eq!(output, "default stream");
+        assert_eq!(opened, vec![Some("USB Headset".to_string()), None]);
+        assert!(notice.unwrap().contains("USB Headset"));
+
+        // Nothing works: exactly one re-open before giving up
+        let mut attempts = 0;
+        let result: Result<((), Option<String>), _> = open_with_recovery(
+            |_: Option<&str>| {
+                attempts += 1;
+                Err("no audio backend".into())
+            },
+            Some("USB Headset"),
+            || vec!["USB Headset".to_string()],
+        );
+        assert_eq!(attempts, 2);
+        assert!(result.unwrap_err().to_string().contains("re-opening USB Headset also failed"));
+
+        // A healthy device opens first time without a notice
+        let (_, notice) = open_with_recovery(|_: Option<&str>| Ok(()), None, Vec::new).unwrap();
+        assert_eq!(notice, None);
+    }
+
+    #[tokio::test]
+    async fn test_replay_last_reuses_clips_without_synthesis() {
+        let backend = DryRunBackend::new();
+        let requests = backend.request_counter();
+        let mut tts = TTSManager::with_backend(Box::new(backend));
+        assert!(!tts.has_replay());
+        assert!(!tts.replay_last().await.unwrap());
+
+        let message = vec![
+            MessagePart::Static("Replay me".to_string()),
+            MessagePart::Dynamic("twice".to_string()),
+        ];
+        tts.speak(message, &PersonalitySettings::default()).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert!(tts.has_replay());
+
+        assert!(tts.replay_last().await.unwrap());
+        assert!(tts.replay_last().await.unwrap());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_volume_setting_reaches_playback() {
+        let mut tts = TTSManager::with_backend(Box::new(DryRunBackend::new()));
+        let (sink, _queue) = rodio::Sink::new_idle();
+        tts.apply_volume(&sink);
+        assert_eq!(sink.volume(), 1.0);
+
+        tts.set_volume(0.0);
+        assert_eq!(tts.volume, 0.0);
+        tts.apply_volume(&sink);
+        assert_eq!(sink.volume(), 0.0, "muted by the slider");
+
+        tts.set_volume(1.5);
+        assert_eq!(tts.volume, 1.0, "clamped");
+    }
+
+    #[test]
+    fn test_near_equal_dynamic_values_share_cache_key() {
+        let mut tts = TTSManager::with_backend(Box::new(DryRunBackend::new()));
+        let key = |tts: &TTSManager, text: &str| tts.get_cache_key_for(&MessagePart::Dynamic(text.to_string()), Severity::Info);
+
+        assert_eq!(key(&tts, "CPU at 46%"), key(&tts, "CPU at 47%"));
+        assert_ne!(key(&tts, "CPU at 47%"), key(&tts, "CPU at 53%"));
+
+        tts.set_dynamic_cache_bucket(0.0);
+        assert_ne!(key(&tts, "CPU at 46%"), key(&tts, "CPU at 47%"));
+    }
+
+    #[test]
+    fn test_json_response_is_rejected() {
+        let body = br#"{"error": {"message": "Input was flagged by moderation", "type": "invalid_request_error"}}"#.to_vec();
+        let result = validate_audio_response(Some("application/json"), body);
+
+        match result {
+            Err(TtsError::NotAudio { content_type, message }) => {
+                assert_eq!(content_type, "application/json");
+                assert_eq!(message, "Input was flagged by moderation");
+            }
+            other => panic!("Expected NotAudio error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_body_with_audio_content_type_is_rejected() {
+        let body = b"  {\"error\": {\"message\": \"refused\"}}".to_vec();
+        assert!(matches!(
+            validate_audio_response(Some("audio/mpeg"), body),
+            Err(TtsError::NotAudio { .. })
+        ));
+    }
+
+    #[test]
+    fn test_short_or_empty_audio_is_rejected() {
+        assert_eq!(validate_audio_response(Some("audio/mpeg"), Vec::new()), Err(TtsError::TooShort { len: 0 }));
+        assert_eq!(validate_audio_response(Some("audio/mpeg"), vec![0xFF; 10]), Err(TtsError::TooShort { len: 10 }));
+    }
+
+    #[test]
+    fn test_valid_audio_passes_validation() {
+        let body = vec![0xFF; MIN_AUDIO_BYTES * 2];
+        assert_eq!(validate_audio_response(Some("audio/mpeg"), body.clone()), Ok(body));
+    }
+
+    #[test]
+    fn test_speech_endpoint_from_base_url() {
+        assert_eq!(speech_endpoint(DEFAULT_TTS_BASE_URL).unwrap().as_str(), "https://api.openai.com/v1/audio/speech");
+        assert_eq!(speech_endpoint("http://localhost:4000/v1/").unwrap().as_str(), "http://localhost:4000/v1/audio/speech");
+        assert_eq!(
+            speech_endpoint("https://gateway.example.com/openai/v1/audio/speech").unwrap().as_str(),
+            "https://gateway.example.com/openai/v1/audio/speech"
+        );
+    }
+
+    #[test]
+    fn test_invalid_base_urls_are_rejected() {
+        assert!(speech_endpoint("not a url").is_err());
+        assert!(speech_endpoint("ftp://example.com/v1").is_err());
+        assert!(OpenAiBackend::with_endpoint(DEFAULT_TTS_BASE_URL, vec![("bad header".to_string(), "x".to_string())]).is_err());
+    }
+
+    #[test]
+    fn test_configured_base_url_is_used_in_request() {
+        let backend = OpenAiBackend::with_endpoint(
+            "http://localhost:4000/v1",
+            vec![("api-key".to_string(), "azure-secret".to_string())],
+        ).unwrap();
+
+        let request = backend.build_request("test-key", "hello", "alloy", 1.0).build().unwrap();
+
+        assert_eq!(request.url().as_str(), "http://localhost:4000/v1/audio/speech");
+        assert_eq!(request.headers()["Authorization"], "Bearer test-key");
+        assert_eq!(request.headers()["api-key"], "azure-secret");
+    }
+
+    #[test]
+    fn test_cost_estimate_and_month_rollover() {
+        assert_eq!(estimate_cost(0), 0.0);
+        assert!((estimate_cost(1_000_000) - TTS_PRICE_PER_MILLION_CHARS).abs() < 1e-9);
+        assert!((estimate_cost(2_000) - 0.03).abs() < 1e-9);
+
+        let mut usage = TtsUsage::new("2024-05");
+        usage.record(600_000, "2024-05");
+        usage.record(400_000, "2024-05");
+        assert!((usage.month_cost() - 15.0).abs() < 1e-9);
+        assert!(usage.over_budget(10.0));
+        assert!(!usage.over_budget(20.0));
+        assert!(!usage.over_budget(0.0), "0 is no cap");
+
+        // A new month starts the count again, the session keeps its total
+        usage.record(1_000, "2024-06");
+        assert_eq!((usage.month.as_str(), usage.month_characters, usage.session_characters), ("2024-06", 1_000, 1_001_000));
+        assert!(!usage.over_budget(10.0));
+
+        // Only the monthly count is saved
+        let path = std::env::temp_dir().join(format!("cyber_ninja_usage_{}.json", std::process::id()));
+        usage.save(&path).unwrap();
+        let loaded = TtsUsage::load(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(loaded, TtsUsage { session_characters: 0, ..usage });
+    }
+
+    #[tokio::test]
+    async fn test_budget_cap_pauses_speech() {
+        let mut tts = TTSManager::with_backend(Box::new(ScriptedBackend::new(vec![
+            Ok(vec![0; MIN_AUDIO_BYTES]),
+            Ok(vec![0; MIN_AUDIO_BYTES]),
+        ])));
+        tts.set_monthly_budget(estimate_cost(15));
+
+        tts.generate_audio("0123456789").await.unwrap();
+        assert_eq!(tts.usage().session_characters, 10);
+        assert!(!tts.budget_exceeded());
+        assert_eq!(tts.take_budget_notice(), None);
+
+        tts.generate_audio("0123456789").await.unwrap();
+        assert!(tts.budget_exceeded());
+        assert!(tts.take_budget_notice().unwrap().contains("budget"));
+        assert_eq!(tts.take_budget_notice(), None, "announced once");
+
+        // Over budget, speech is skipped before anything is sent
+        let settings = PersonalitySettings::default();
+        tts.speak(vec![MessagePart::Static("Status".to_string())], &settings).await.unwrap();
+        assert_eq!(tts.last_spoken_text(), None);
+        assert_eq!(tts.usage().session_characters, 20);
+
+        // Raising the budget lets it speak again
+        tts.set_monthly_budget(0.0);
+        assert!(!tts.budget_exceeded());
+    }
+
+    /// Records what would have been played, and when
+    #[derive(Default)]
+    struct RecordingOutput {
+        events: Vec<Played>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Played {
+        /// A clip's length, first sample and last sample
+        Clip(Duration, i16, i16),
+        Wait(Duration),
+        UntilDone,
+    }
+
+    impl ClipOutput for RecordingOutput {
+        fn start(&mut self, clip: DecodedClip) -> Result<(), Box<dyn std::error::Error>> {
+            self.events.push(Played::Clip(clip.duration(), clip.samples[0], *clip.samples.last().unwrap()));
+            Ok(())
+        }
+
+        fn wait(&mut self, duration: Duration) {
+            self.events.push(Played::Wait(duration));
+        }
+
+        fn wait_until_done(&mut self) {
+            self.events.push(Played::UntilDone);
+        }
+    }
+
+    #[test]
+    fn test_crossfade_overlaps_and_fades_clips() {
+        // Constant tones at 1 kHz sampling, so one sample is one millisecond
+        let clip = |millis: usize| DecodedClip { samples: vec![1000; millis], channels: 1, sample_rate: 1000 };
+        let ms = Duration::from_millis;
+        let play = |crossfade: Duration| {
+            let mut output = RecordingOutput::default();
+            play_sequence(&mut output, vec![clip(1000), clip(500), clip(1000)], crossfade, CLIP_GAP).unwrap();
+            output.events
+        };
+
+        assert_eq!(play(ms(200)), [
+            Played::Clip(ms(1000), 1000, 0),
+            Played::Wait(ms(800)),
+            Played::Clip(ms(500), 0, 0),
+            Played::Wait(ms(300)),
+            Played::Clip(ms(1000), 0, 1000),
+            Played::UntilDone,
+        ]);
+
+        // Never overlap more than half of the shorter clip
+        assert_eq!(play(ms(400))[1], Played::Wait(ms(750)));
+        assert_eq!(crossfade_between(ms(1000), ms(500), ms(400)), ms(250));
+
+        // No crossfade: each clip plays out whole with a gap after it
+        assert_eq!(play(Duration::ZERO), [
+            Played::Clip(ms(1000), 1000, 1000),
+            Played::UntilDone,
+            Played::Wait(CLIP_GAP),
+            Played::Clip(ms(500), 1000, 1000),
+            Played::UntilDone,
+            Played::Wait(CLIP_GAP),
+            Played::Clip(ms(1000), 1000, 1000),
+            Played::UntilDone,
+        ]);
+    }
+
     #[test]
     fn test_tts_manager() {
         if let Ok(tts) = TTSManager::new() {
@@ -0,0 +1,212 @@
+use std::fmt;
+use std::hash::Hash;
+use egui::{Color32, Response, Stroke, TextEdit, Ui};
+use crate::tts;
+
+/// Longest callsign accepted; anything longer gets tiresome to hear in every warning
+pub const MAX_CALLSIGN_CHARS: usize = 32;
+
+/// Longest warning template accepted
+pub const MAX_TEMPLATE_CHARS: usize = 300;
+
+/// Why typed input was rejected, shown beside the field
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputError {
+    /// Nothing entered where a value is needed
+    Empty,
+    /// Not something the field understands; says what is wrong
+    Malformed(String),
+    OutOfRange { min: f64, max: f64 },
+    TooLong { max: usize },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "Enter a value"),
+            InputError::Malformed(reason) => write!(f, "{}", reason),
+            InputError::OutOfRange { min, max } => write!(f, "Must be between {} and {}", min, max),
+            InputError::TooLong { max } => write!(f, "At most {} characters", max),
+        }
+    }
+}
+
+/// A number between `min` and `max`, allowing spaces around it
+pub fn parse_number(input: &str, min: f64, max: f64) -> Result<f64, InputError> {
+    let text = input.trim();
+    if text.is_empty() {
+        return Err(InputError::Empty);
+    }
+    // "NaN" and "inf" parse as floats but aren't settings anyone means
+    let value = text.parse::<f64>().ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| InputError::Malformed(format!("\"{}\" isn't a number", text)))?;
+    if value < min || value > max {
+        return Err(InputError::OutOfRange { min, max });
+    }
+    Ok(value)
+}
+
+/// A callsign to be spoken. Blank is fine and means the default; braces would
+/// read as placeholders and control characters can't be said.
+pub fn validate_callsign(input: &str) -> Result<String, InputError> {
+    if input.trim().chars().count() > MAX_CALLSIGN_CHARS {
+        return Err(InputError::TooLong { max: MAX_CALLSIGN_CHARS });
+    }
+    if let Some(c) = input.chars().find(|c| c.is_control() || *c == '{' || *c == '}') {
+        return Err(InputError::Malformed(format!("{:?} can't be part of a callsign", c)));
+    }
+    Ok(input.to_string())
+}
+
+/// A warning template using only `placeholders`. Blank is fine and means the standard message.
+pub fn validate_template(input: &str, placeholders: &[&str]) -> Result<String, InputError> {
+    if input.chars().count() > MAX_TEMPLATE_CHARS {
+        return Err(InputError::TooLong { max: MAX_TEMPLATE_CHARS });
+    }
+    let mut rest = input;
+    while let Some(index) = rest.find(['{', '}']) {
+        if rest[index..].starts_with('}') {
+            return Err(InputError::Malformed("\"}\" without a matching \"{\"".to_string()));
+        }
+        let after = &rest[index + 1..];
+        let end = after.find(['{', '}'])
+            .filter(|end| after[*end..].starts_with('}'))
+            .ok_or_else(|| InputError::Malformed("\"{\" without a matching \"}\"".to_string()))?;
+        let name = &after[..end];
+        if !placeholders.contains(&name) {
+            return Err(InputError::Malformed(format!("Unknown placeholder {{{}}}", name)));
+        }
+        rest = &after[end + 1..];
+    }
+    Ok(input.to_string())
+}
+
+/// A speech server base URL; blank means the default server
+pub fn validate_url(input: &str) -> Result<Option<String>, InputError> {
+    let text = input.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    tts::speech_endpoint(text).map_err(|e| InputError::Malformed(e.to_string()))?;
+    Ok(Some(text.to_string()))
+}
+
+/// A one-character hotkey that doesn't clash with the volume (`+`, `=`, `-`) and speech rate (`[`, `]`) keys
+pub fn validate_hotkey(input: &str) -> Result<String, InputError> {
+    let text = input.trim();
+    let mut chars = text.chars();
+    let key = chars.next().ok_or(InputError::Empty)?;
+    if chars.next().is_some() {
+        return Err(InputError::TooLong { max: 1 });
+    }
+    if key.is_control() || "+=-[]".contains(key) {
+        return Err(InputError::Malformed(format!("{:?} is already taken", key)));
+    }
+    Ok(text.to_string())
+}
+
+/// A single-line text field that only hands back input `parse` accepts.
+///
+/// Rejected input stays in the field with a red border and the reason beside
+/// it, so it never reaches the setting. Accepted input follows `current` while
+/// the field isn't being typed in, picking up changes made elsewhere, e.g. by a slider.
+pub fn validated_text<T>(
+    ui: &mut Ui,
+    id_source: impl Hash,
+    current: &str,
+    error_color: Color32,
+    edit: impl FnOnce(TextEdit) -> TextEdit,
+    parse: impl Fn(&str) -> Result<T, InputError>,
+) -> (Response, Option<T>) {
+    let id = ui.make_persistent_id(id_source);
+    let focused = ui.memory(|memory| memory.has_focus(id));
+    let mut draft = ui.data_mut(|data| data.get_temp::<String>(id))
+        .filter(|draft| focused || parse(draft).is_err())
+        .unwrap_or_else(|| current.to_string());
+
+    ui.vertical(|ui| {
+        let response = ui.add(edit(TextEdit::singleline(&mut draft).id(id)));
+        let parsed = parse(&draft);
+        if let Err(e) = &parsed {
+            ui.painter().rect_stroke(response.rect.expand(1.0), 2.0, Stroke::new(1.5, error_color));
+            ui.colored_label(error_color, e.to_string());
+        }
+        ui.data_mut(|data| data.insert_temp(id, draft.clone()));
+        let value = if response.changed() { parsed.ok() } else { None };
+        (response, value)
+    }).inner
+}
+
+/// A number field for values between `min` and `max`, see `validated_text`; true when `value` was changed
+pub fn validated_number(ui: &mut Ui, id_source: impl Hash, value: &mut f32, min: f32, max: f32, error_color: Color32) -> bool {
+    let (_, parsed) = validated_text(
+        ui,
+        id_source,
+        &value.to_string(),
+        error_color,
+        |edit| edit.desired_width(60.0),
+        |text| parse_number(text, min as f64, max as f64),
+    );
+    match parsed {
+        Some(parsed) => {
+            *value = parsed as f32;
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_reject_empty_malformed_and_out_of_range() {
+        assert_eq!(parse_number(" 42.5 ", 0.0, 100.0), Ok(42.5));
+        assert_eq!(parse_number("0", 0.0, 100.0), Ok(0.0));
+        assert_eq!(parse_number("", 0.0, 100.0), Err(InputError::Empty));
+        assert_eq!(parse_number("   ", 0.0, 100.0), Err(InputError::Empty));
+        assert_eq!(parse_number("101", 0.0, 100.0), Err(InputError::OutOfRange { min: 0.0, max: 100.0 }));
+        assert_eq!(parse_number("-1", 0.0, 100.0), Err(InputError::OutOfRange { min: 0.0, max: 100.0 }));
+        for malformed in ["abc", "12%", "1,5", "1.2.3", "NaN", "inf", "-infinity"] {
+            assert!(matches!(parse_number(malformed, 0.0, 100.0), Err(InputError::Malformed(_))), "{}", malformed);
+        }
+        assert_eq!(InputError::OutOfRange { min: 0.1, max: 10000.0 }.to_string(), "Must be between 0.1 and 10000");
+    }
+
+    #[test]
+    fn test_text_fields_reject_malformed_and_overlong_input() {
+        // Callsigns: blank falls back to the default
+        assert_eq!(validate_callsign(""), Ok(String::new()));
+        assert_eq!(validate_callsign("Commander Shepard"), Ok("Commander Shepard".to_string()));
+        assert_eq!(validate_callsign(&"x".repeat(MAX_CALLSIGN_CHARS + 1)), Err(InputError::TooLong { max: MAX_CALLSIGN_CHARS }));
+        assert!(matches!(validate_callsign("{cpu_pct}"), Err(InputError::Malformed(_))));
+        assert!(matches!(validate_callsign("Cap\ntain"), Err(InputError::Malformed(_))));
+
+        // Templates: blank is the standard message; only known placeholders, properly closed
+        let placeholders = ["cpu_pct", "callsign"];
+        assert_eq!(validate_template("", &placeholders), Ok(String::new()));
+        assert!(validate_template("{callsign}, CPU at {cpu_pct}%!", &placeholders).is_ok());
+        assert_eq!(validate_template(&"a".repeat(MAX_TEMPLATE_CHARS + 1), &placeholders), Err(InputError::TooLong { max: MAX_TEMPLATE_CHARS }));
+        assert_eq!(validate_template("CPU at {cpu}", &placeholders), Err(InputError::Malformed("Unknown placeholder {cpu}".to_string())));
+        for malformed in ["CPU at {cpu_pct", "CPU at cpu_pct}", "{{cpu_pct}}", "{cpu_pct{callsign}}"] {
+            assert!(matches!(validate_template(malformed, &placeholders), Err(InputError::Malformed(_))), "{}", malformed);
+        }
+
+        // URLs: blank is the default server
+        assert_eq!(validate_url("  "), Ok(None));
+        assert_eq!(validate_url(" http://localhost:8000/v1 "), Ok(Some("http://localhost:8000/v1".to_string())));
+        for malformed in ["not a url", "ftp://example.com", "https://"] {
+            assert!(matches!(validate_url(malformed), Err(InputError::Malformed(_))), "{}", malformed);
+        }
+
+        // Hotkeys: exactly one key, not one the volume or speech rate already use
+        assert_eq!(validate_hotkey(" n "), Ok("n".to_string()));
+        assert_eq!(validate_hotkey(""), Err(InputError::Empty));
+        assert_eq!(validate_hotkey("np"), Err(InputError::TooLong { max: 1 }));
+        for taken in ["+", "-", "]"] {
+            assert!(matches!(validate_hotkey(taken), Err(InputError::Malformed(_))), "{}", taken);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+/// Steal above this share of CPU time is shown as a warning in the CPU panel
+pub const STEAL_WARN_PERCENT: f32 = 10.0;
+
+/// Cumulative CPU time from the aggregate `cpu` line of /proc/stat, in clock ticks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    /// user, nice, system, idle, iowait, irq, softirq and steal together; guest time is already in user
+    pub total: u64,
+    /// Time the hypervisor ran something else while this machine wanted the CPU
+    pub steal: u64,
+}
+
+/// Parses the aggregate line of /proc/stat: `cpu user nice system idle iowait irq softirq steal guest guest_nice`.
+///
+/// Kernels too old to report steal have fewer fields and give None.
+pub fn parse_proc_stat(content: &str) -> Option<CpuTimes> {
+    let line = content.lines().find(|line| line.split_whitespace().next() == Some("cpu"))?;
+    let values: Vec<u64> = line.split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|field| field.parse().ok())
+        .collect::<Option<_>>()?;
+    if values.len() < 8 {
+        return None;
+    }
+    Some(CpuTimes { total: values.iter().sum(), steal: values[7] })
+}
+
+/// Share of CPU time stolen between two readings, None if no time passed
+pub fn steal_percent(previous: &CpuTimes, current: &CpuTimes) -> Option<f32> {
+    let total = current.total.saturating_sub(previous.total);
+    if total == 0 {
+        return None;
+    }
+    let steal = current.steal.saturating_sub(previous.steal);
+    Some((steal as f64 / total as f64 * 100.0).clamp(0.0, 100.0) as f32)
+}
+
+/// Turns successive /proc/stat readings into the steal percentage over the last refresh
+#[derive(Debug, Default)]
+pub struct CpuStealTracker {
+    previous: Option<CpuTimes>,
+    latest: Option<f32>,
+}
+
+impl CpuStealTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a new reading; the first one only sets the baseline
+    pub fn update(&mut self, current: CpuTimes) {
+        if let Some(previous) = self.previous.replace(current) {
+            self.latest = steal_percent(&previous, &current).or(self.latest);
+        }
+    }
+
+    /// Reads /proc/stat and updates; does nothing where it isn't available
+    pub fn sample(&mut self) {
+        if let Some(times) = read_proc_stat().as_deref().and_then(parse_proc_stat) {
+            self.update(times);
+        }
+    }
+
+    pub fn latest(&self) -> Option<f32> {
+        self.latest
+    }
+}
+
+/// Names the hypervisor from the DMI vendor and product strings, falling back to the CPU's
+/// `hypervisor` flag; None on bare metal
+pub fn virtualization_platform(vendor: &str, product: &str, hypervisor_flag: bool) -> Option<String> {
+    let dmi = format!("{} {}", vendor, product).to_ascii_lowercase();
+    let known = [
+        // Chromebooks and Surface laptops share these vendors, so match the VM products
+        ("amazon ec2", "Amazon EC2"),
+        ("google compute engine", "Google Compute Engine"),
+        ("microsoft corporation virtual machine", "Hyper-V"),
+        ("vmware", "VMware"),
+        ("virtualbox", "VirtualBox"),
+        ("innotek", "VirtualBox"),
+        ("qemu", "QEMU"),
+        ("kvm", "KVM"),
+        ("xen", "Xen"),
+        ("parallels", "Parallels"),
+        ("digitalocean", "DigitalOcean"),
+        ("openstack", "OpenStack"),
+    ];
+    known.iter()
+        .find(|(needle, _)| dmi.contains(needle))
+        .map(|(_, name)| name.to_string())
+        .or_else(|| hypervisor_flag.then(|| "virtual machine".to_string()))
+}
+
+/// The hypervisor this machine runs under, from /sys/class/dmi and /proc/cpuinfo
+#[cfg(target_os = "linux")]
+pub fn detect_virtualization() -> Option<String> {
+    let read = |path: &str| std::fs::read_to_string(path).unwrap_or_default();
+    let hypervisor_flag = read("/proc/cpuinfo").lines()
+        .filter(|line| line.starts_with("flags"))
+        .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"));
+    virtualization_platform(
+        read("/sys/class/dmi/id/sys_vendor").trim(),
+        read("/sys/class/dmi/id/product_name").trim(),
+        hypervisor_flag,
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn detect_virtualization() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_stat() -> Option<String> {
+    std::fs::read_to_string("/proc/stat").ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_proc_stat() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEFORE: &str = "\
+cpu  10000 200 3000 80000 500 0 100 1200 0 0
+cpu0 5000 100 1500 40000 250 0 50 600 0 0
+intr 123456 0 0";
+
+    const AFTER: &str = "\
+cpu  10600 200 3200 80800 500 0 100 1500 0 0
+cpu0 5300 100 1600 40400 250 0 50 750 0 0
+intr 123999 0 0";
+
+    #[test]
+    fn test_steal_percentage_from_proc_stat() {
+        let before = parse_proc_stat(BEFORE).unwrap();
+        assert_eq!(before, CpuTimes { total: 95_000, steal: 1200 });
+        let after = parse_proc_stat(AFTER).unwrap();
+        // 1900 ticks passed, 300 of them stolen
+        assert!((steal_percent(&before, &after).unwrap() - 15.789).abs() < 0.01);
+        assert_eq!(steal_percent(&after, &after), None);
+
+        let mut tracker = CpuStealTracker::new();
+        tracker.update(before);
+        assert_eq!(tracker.latest(), None, "the first reading has nothing to compare against");
+        tracker.update(after);
+        assert_eq!(tracker.latest(), steal_percent(&before, &after));
+        tracker.update(after);
+        assert_eq!(tracker.latest(), steal_percent(&before, &after), "no time passed: keep the last reading");
+
+        assert!(parse_proc_stat("cpu  10000 200 3000 80000").is_none(), "no steal column on old kernels");
+        assert!(parse_proc_stat("cpu0 1 2 3 4 5 6 7 8").is_none());
+        assert!(parse_proc_stat("cpu  1 2 x 4 5 6 7 8").is_none());
+    }
+
+    #[test]
+    fn test_virtualization_from_dmi_strings() {
+        assert_eq!(virtualization_platform("Amazon EC2", "m5.large", true).as_deref(), Some("Amazon EC2"));
+        assert_eq!(virtualization_platform("Google", "Google Compute Engine", true).as_deref(), Some("Google Compute Engine"));
+        assert_eq!(virtualization_platform("QEMU", "Standard PC (Q35 + ICH9, 2009)", true).as_deref(), Some("QEMU"));
+        assert_eq!(virtualization_platform("Dell Inc.", "XPS 15 9520", true).as_deref(), Some("virtual machine"));
+        assert_eq!(virtualization_platform("Dell Inc.", "XPS 15 9520", false), None);
+        assert_eq!(virtualization_platform("Google", "Eve", false), None, "a Chromebook is bare metal");
+    }
+}
@@ -0,0 +1,630 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+use crate::message_system::{MetricKind, Severity};
+
+/// Minimum time between two spoken warnings of the same kind
+pub const WARNING_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Rate-limits a recurring warning so it fires at most once per cooldown
+#[derive(Debug, Clone)]
+pub struct WarningThrottle {
+    cooldown: Duration,
+    last_fired: Option<Instant>,
+}
+
+impl Default for WarningThrottle {
+    fn default() -> Self {
+        Self::new(WARNING_COOLDOWN)
+    }
+}
+
+impl WarningThrottle {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            last_fired: None,
+        }
+    }
+
+    /// Returns true and restarts the cooldown if the warning may fire at `now`
+    pub fn try_fire(&mut self, now: Instant) -> bool {
+        let ready = self.last_fired
+            .is_none_or(|last| now.saturating_duration_since(last) > self.cooldown);
+        if ready {
+            self.last_fired = Some(now);
+        }
+        ready
+    }
+}
+
+/// Detects a value staying above a threshold for a sustained period
+#[derive(Debug, Clone, Default)]
+pub struct SustainedThreshold {
+    above_since: Option<Instant>,
+}
+
+impl SustainedThreshold {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a sample; returns true once the value has been above `threshold` for `sustain`
+    pub fn update(&mut self, value: f64, threshold: f64, sustain: Duration, now: Instant) -> bool {
+        if value > threshold {
+            let since = *self.above_since.get_or_insert(now);
+            now.saturating_duration_since(since) >= sustain
+        } else {
+            self.above_since = None;
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AlarmState {
+    active: bool,
+    below_since: Option<Instant>,
+}
+
+/// Keeps each metric's alarm raised until it has really recovered, so a value
+/// flapping around its threshold doesn't re-arm the alarm every few seconds.
+///
+/// An alarm clears only once the metric has stayed below its recovery level
+/// (the threshold less any hysteresis margin) for the whole grace period.
+#[derive(Debug, Clone, Default)]
+pub struct AlarmLatch {
+    states: HashMap<MetricKind, AlarmState>,
+}
+
+impl AlarmLatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the metrics over their threshold and those still above their recovery level;
+    /// returns the metrics in alarm, in MetricKind::ALL order
+    pub fn update(&mut self, over: &[MetricKind], above_recovery: &[MetricKind], grace: Duration, now: Instant) -> Vec<MetricKind> {
+        for kind in MetricKind::ALL {
+            let state = self.states.entry(kind).or_default();
+            if over.contains(&kind) {
+                *state = AlarmState { active: true, below_since: None };
+            } else if !state.active || above_recovery.contains(&kind) {
+                state.below_since = None;
+            } else {
+                let since = *state.below_since.get_or_insert(now);
+                if now.saturating_duration_since(since) >= grace {
+                    *state = AlarmState::default();
+                }
+            }
+        }
+        MetricKind::ALL.into_iter()
+            .filter(|kind| self.states.get(kind).is_some_and(|state| state.active))
+            .collect()
+    }
+
+    /// When `kind` dropped below its recovery level while still in alarm
+    #[cfg(test)]
+    pub fn below_since(&self, kind: MetricKind) -> Option<Instant> {
+        self.states.get(&kind).and_then(|state| state.below_since)
+    }
+}
+
+/// Tracks data transferred since the session started against a data cap
+#[derive(Debug, Clone, Default)]
+pub struct DataBudget {
+    session_start_total: Option<u64>,
+    used_bytes: u64,
+    highest_fired_percent: f32,
+}
+
+impl DataBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the interface byte counters; returns the highest warning percentage newly crossed
+    pub fn observe(&mut self, total_bytes: u64, cap_bytes: u64, warn_percents: &[f32]) -> Option<f32> {
+        let start = *self.session_start_total.get_or_insert(total_bytes);
+        self.used_bytes = total_bytes.saturating_sub(start);
+        if cap_bytes == 0 {
+            return None;
+        }
+
+        let used_percent = self.used_percent(cap_bytes);
+        let crossed = warn_percents.iter()
+            .copied()
+            .filter(|pct| *pct > self.highest_fired_percent && used_percent >= *pct)
+            .fold(None, |max: Option<f32>, pct| Some(max.map_or(pct, |m| m.max(pct))));
+
+        if let Some(pct) = crossed {
+            self.highest_fired_percent = pct;
+        }
+        crossed
+    }
+
+    pub fn used_bytes(&self) -> u64 {
+        self.used_bytes
+    }
+
+    pub fn used_percent(&self, cap_bytes: u64) -> f32 {
+        if cap_bytes == 0 {
+            return 0.0;
+        }
+        (self.used_bytes as f64 / cap_bytes as f64 * 100.0) as f32
+    }
+}
+
+/// Readings needed before a disk's fill rate is projected
+pub const FILL_MIN_SAMPLES: usize = 3;
+
+/// Least-squares slope of `samples` of (x, y); None without two distinct x values
+pub fn linear_slope(samples: &[(f64, f64)]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (covariance, variance) = samples.iter().fold((0.0, 0.0), |(cov, var), (x, y)| {
+        (cov + (x - mean_x) * (y - mean_y), var + (x - mean_x) * (x - mean_x))
+    });
+    if variance <= f64::EPSILON {
+        return None;
+    }
+    Some(covariance / variance)
+}
+
+/// How long until `available` bytes run out at the rate `samples` of (seconds, bytes free) are
+/// shrinking; None while free space is steady or growing
+pub fn project_time_to_full(samples: &[(f64, f64)], available: u64) -> Option<Duration> {
+    let slope = linear_slope(samples)?;
+    if !slope.is_finite() || slope >= 0.0 {
+        return None;
+    }
+    let seconds = available as f64 / -slope;
+    seconds.is_finite().then(|| Duration::from_secs_f64(seconds.min(u64::MAX as f64 / 2.0)))
+}
+
+/// Rough wording for a time to full, e.g. "45 minutes" or "3 hours"
+pub fn format_time_to_full(time: Duration) -> String {
+    let minutes = (time.as_secs() / 60).max(1);
+    match minutes {
+        1 => "1 minute".to_string(),
+        2..=89 => format!("{} minutes", minutes),
+        90..=2159 => format!("{} hours", (minutes + 30) / 60),
+        _ => format!("{} days", (minutes + 720) / 1440),
+    }
+}
+
+/// Recent free space per disk, for projecting when each one fills up
+#[derive(Debug, Default)]
+pub struct DiskFillTracker {
+    samples: BTreeMap<String, VecDeque<(Instant, u64)>>,
+}
+
+impl DiskFillTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records free space for every mounted disk, keeping `window` of history; unmounted disks are forgotten
+    pub fn observe(&mut self, disks: &[(String, u64)], now: Instant, window: Duration) {
+        self.samples.retain(|mount, _| disks.iter().any(|(name, _)| name == mount));
+        for (mount, available) in disks {
+            let samples = self.samples.entry(mount.clone()).or_default();
+            samples.push_back((now, *available));
+            while samples.front().is_some_and(|(time, _)| now.saturating_duration_since(*time) > window) {
+                samples.pop_front();
+            }
+        }
+    }
+
+    /// Projected time until `mount` is full, once there are enough readings to trust the trend
+    pub fn time_to_full(&self, mount: &str) -> Option<Duration> {
+        let samples = self.samples.get(mount)?;
+        if samples.len() < FILL_MIN_SAMPLES {
+            return None;
+        }
+        let (start, _) = *samples.front()?;
+        let (_, available) = *samples.back()?;
+        let points: Vec<(f64, f64)> = samples.iter()
+            .map(|(time, free)| (time.saturating_duration_since(start).as_secs_f64(), *free as f64))
+            .collect();
+        project_time_to_full(&points, available)
+    }
+
+    /// The disk projected to fill up soonest, if any is filling at all
+    pub fn soonest(&self) -> Option<(&str, Duration)> {
+        self.samples.keys()
+            .filter_map(|mount| Some((mount.as_str(), self.time_to_full(mount)?)))
+            .min_by_key(|(_, time)| *time)
+    }
+}
+
+/// Smallest spread assumed for an adaptive baseline, in percentage points,
+/// so a perfectly flat history doesn't flag every tiny blip
+pub const ADAPTIVE_MIN_STDDEV: f32 = 2.0;
+
+/// Mean and standard deviation of a stream of samples (Welford's method)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_samples<I: IntoIterator<Item = f32>>(samples: I) -> Self {
+        let mut stats = Self::new();
+        for value in samples {
+            stats.push(value);
+        }
+        stats
+    }
+
+    pub fn push(&mut self, value: f32) {
+        let value = value as f64;
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> f32 {
+        self.mean as f32
+    }
+
+    /// Population standard deviation; 0 until there are two samples
+    pub fn stddev(&self) -> f32 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        (self.m2 / self.count as f64).sqrt() as f32
+    }
+}
+
+/// The machine's own "unusually high" level: mean + k·stddev of recent samples.
+///
+/// None until at least `min_samples` samples have been seen.
+pub fn adaptive_threshold(stats: &RunningStats, k: f32, min_samples: usize) -> Option<f32> {
+    if stats.count() < min_samples.max(1) {
+        return None;
+    }
+    Some(stats.mean() + k * stats.stddev().max(ADAPTIVE_MIN_STDDEV))
+}
+
+/// When an alert is serious enough to take over the whole window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmergencySettings {
+    pub enabled: bool,
+    /// Least severe alert that takes over the window; never below Warning
+    pub min_severity: Severity,
+    /// CPU usage that counts as pinned, in percent
+    pub cpu_percent: f32,
+    /// How long the CPU must stay pinned before it is an emergency, in seconds
+    pub cpu_sustain_secs: u64,
+    /// Disk usage that counts as nearly full, in percent
+    pub disk_percent: f32,
+}
+
+impl Default for EmergencySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_severity: Severity::Critical,
+            cpu_percent: 95.0,
+            cpu_sustain_secs: 180,
+            disk_percent: 98.0,
+        }
+    }
+}
+
+impl EmergencySettings {
+    /// Whether an alert of `severity` takes over the window
+    pub fn takes_over(&self, severity: Severity) -> bool {
+        self.enabled && severity >= self.min_severity.max(Severity::Warning)
+    }
+}
+
+/// A takeover waiting for the user to click it away
+#[derive(Debug, Clone, PartialEq)]
+pub struct Emergency {
+    pub message: String,
+    pub raised_at: Instant,
+}
+
+/// Spots the conditions that are always critical: the CPU pinned for minutes or a nearly full disk.
+///
+/// Each condition is reported once when it starts and again only after it has cleared.
+#[derive(Debug, Clone, Default)]
+pub struct EmergencyDetector {
+    cpu_pinned: SustainedThreshold,
+    cpu_reported: bool,
+    disk_reported: bool,
+}
+
+impl EmergencyDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a sample; returns a message for each condition that just became critical
+    pub fn update(&mut self, peak_cpu: f32, disk_usage: f32, settings: &EmergencySettings, now: Instant) -> Vec<String> {
+        let mut raised = Vec::new();
+
+        let pinned = self.cpu_pinned.update(
+            peak_cpu as f64,
+            settings.cpu_percent as f64,
+            Duration::from_secs(settings.cpu_sustain_secs),
+            now,
+        );
+        if pinned && !self.cpu_reported {
+            raised.push(format!("CPU pinned at {:.0}% for over {} seconds", peak_cpu, settings.cpu_sustain_secs));
+        }
+        self.cpu_reported = pinned;
+
+        let disk_full = disk_usage >= settings.disk_percent;
+        if disk_full && !self.disk_reported {
+            raised.push(format!("Disk {:.1}% full", disk_usage));
+        }
+        self.disk_reported = disk_full;
+
+        raised
+    }
+}
+
+/// Minutes in a day, the range maintenance window times are given in
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// A daily time range, e.g. a nightly backup, during which warnings for some metrics are held back
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceWindow {
+    pub label: String,
+    /// Start of the window in minutes after local midnight
+    pub start_minute: u32,
+    /// End of the window in minutes after local midnight; earlier than the start for windows crossing midnight
+    pub end_minute: u32,
+    /// Metrics whose warnings are suppressed; empty suppresses all of them
+    pub metrics: Vec<MetricKind>,
+}
+
+impl Default for MaintenanceWindow {
+    fn default() -> Self {
+        Self {
+            label: "Backup".to_string(),
+            start_minute: 3 * 60,
+            end_minute: 4 * 60,
+            metrics: Vec::new(),
+        }
+    }
+}
+
+impl MaintenanceWindow {
+    /// Whether warnings for `kind` are suppressed at `minute` (minutes after local midnight)
+    pub fn covers(&self, kind: MetricKind, minute: u32) -> bool {
+        if !self.metrics.is_empty() && !self.metrics.contains(&kind) {
+            return false;
+        }
+        let minute = minute % MINUTES_PER_DAY;
+        if self.start_minute <= self.end_minute {
+            minute >= self.start_minute && minute < self.end_minute
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+/// Whether any window suppresses warnings for `kind` at `minute`
+pub fn in_maintenance(windows: &[MaintenanceWindow], kind: MetricKind, minute: u32) -> bool {
+    windows.iter().any(|window| window.covers(kind, minute))
+}
+
+/// "03:30" for 210 minutes after midnight
+pub fn format_minute_of_day(minute: u32) -> String {
+    format!("{:02}:{:02}", minute / 60 % 24, minute % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_throttle_cooldown() {
+        let start = Instant::now();
+        let mut throttle = WarningThrottle::new(Duration::from_secs(30));
+
+        assert!(throttle.try_fire(start));
+        assert!(!throttle.try_fire(start + Duration::from_secs(10)));
+        assert!(throttle.try_fire(start + Duration::from_secs(31)));
+    }
+
+    #[test]
+    fn test_sustained_threshold() {
+        let start = Instant::now();
+        let sustain = Duration::from_secs(10);
+        let mut detector = SustainedThreshold::new();
+
+        assert!(!detector.update(20.0, 10.0, sustain, start));
+        assert!(!detector.update(20.0, 10.0, sustain, start + Duration::from_secs(5)));
+        assert!(detector.update(20.0, 10.0, sustain, start + Duration::from_secs(10)));
+
+        // Dropping below the threshold restarts the sustain period
+        assert!(!detector.update(5.0, 10.0, sustain, start + Duration::from_secs(11)));
+        assert!(!detector.update(20.0, 10.0, sustain, start + Duration::from_secs(12)));
+        assert!(detector.update(20.0, 10.0, sustain, start + Duration::from_secs(22)));
+    }
+
+    #[test]
+    fn test_alarm_latches_through_flapping() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let grace = Duration::from_secs(30);
+        let cpu = [MetricKind::Cpu];
+        let mut latch = AlarmLatch::new();
+
+        assert_eq!(latch.update(&cpu, &cpu, grace, at(0)), cpu);
+        // Flapping: dips below the recovery level keep resetting the grace period
+        for (secs, over, above_recovery) in [(5, false, false), (10, true, true), (15, false, false), (20, false, true), (30, false, false)] {
+            let over: &[MetricKind] = if over { &cpu } else { &[] };
+            let above_recovery: &[MetricKind] = if above_recovery { &cpu } else { &[] };
+            assert_eq!(latch.update(over, above_recovery, grace, at(secs)), cpu, "still latched at {}s", secs);
+        }
+        assert_eq!(latch.below_since(MetricKind::Cpu), Some(at(30)));
+        assert_eq!(latch.update(&[], &[], grace, at(59)), cpu);
+        assert!(latch.update(&[], &[], grace, at(60)).is_empty(), "cleared after a full grace period below");
+        assert_eq!(latch.below_since(MetricKind::Cpu), None);
+
+        // Without a grace period the alarm clears as soon as the metric recovers
+        let memory = [MetricKind::Memory];
+        latch.update(&memory, &memory, Duration::ZERO, at(70));
+        assert_eq!(latch.update(&[], &memory, Duration::ZERO, at(71)), memory, "over the recovery level is not recovered");
+        assert!(latch.update(&[], &[], Duration::ZERO, at(72)).is_empty());
+    }
+
+    #[test]
+    fn test_data_budget_warns_once_per_percentage() {
+        let cap = 1000;
+        let percents = [50.0, 80.0, 95.0];
+        let mut budget = DataBudget::new();
+
+        // The first observation only records the session baseline
+        assert_eq!(budget.observe(10_000, cap, &percents), None);
+        assert_eq!(budget.observe(10_400, cap, &percents), None);
+        assert_eq!(budget.observe(10_500, cap, &percents), Some(50.0));
+        assert_eq!(budget.observe(10_600, cap, &percents), None);
+
+        // Jumping past several marks reports only the highest one
+        assert_eq!(budget.observe(10_960, cap, &percents), Some(95.0));
+        assert_eq!(budget.observe(11_000, cap, &percents), None);
+        assert_eq!(budget.used_bytes(), 1000);
+    }
+
+    #[test]
+    fn test_data_budget_counter_reset() {
+        let mut budget = DataBudget::new();
+        budget.observe(5_000, 1000, &[50.0]);
+        assert_eq!(budget.observe(100, 1000, &[50.0]), None);
+        assert_eq!(budget.used_bytes(), 0);
+    }
+
+    #[test]
+    fn test_fill_rate_projects_time_to_full() {
+        // 10 GB free, losing 50 MB every 30 s: 100 KB/s with a little jitter
+        let series: Vec<(f64, f64)> = (0..20)
+            .map(|i| {
+                let jitter = if i % 2 == 0 { 200_000.0 } else { -200_000.0 };
+                (i as f64 * 30.0, 10_000_000_000.0 - i as f64 * 3_000_000.0 + jitter)
+            })
+            .collect();
+        let slope = linear_slope(&series).unwrap();
+        assert!((slope + 100_000.0).abs() < 1_000.0, "slope {}", slope);
+        let projected = project_time_to_full(&series, 9_943_000_000).unwrap();
+        assert!((projected.as_secs_f64() - 99_430.0).abs() < 1_500.0, "{:?}", projected);
+
+        assert_eq!(linear_slope(&[(5.0, 1.0), (5.0, 2.0)]), None, "one instant has no trend");
+        assert_eq!(project_time_to_full(&[(0.0, 100.0), (10.0, 100.0), (20.0, 120.0)], 120), None, "steady or freeing up");
+
+        let mut tracker = DiskFillTracker::new();
+        let start = Instant::now();
+        for i in 0..5u64 {
+            tracker.observe(&[("/".to_string(), 1_000_000 - i * 10_000), ("/data".to_string(), 5_000)], start + Duration::from_secs(i * 10), Duration::from_secs(600));
+        }
+        assert_eq!(tracker.time_to_full("/"), Some(Duration::from_secs(960)));
+        assert_eq!(tracker.soonest(), Some(("/", Duration::from_secs(960))));
+        tracker.observe(&[("/data".to_string(), 5_000)], start + Duration::from_secs(50), Duration::from_secs(600));
+        assert_eq!(tracker.time_to_full("/"), None, "an unmounted disk is forgotten");
+
+        assert_eq!(format_time_to_full(Duration::from_secs(45 * 60)), "45 minutes");
+        assert_eq!(format_time_to_full(Duration::from_secs(2 * 3600 + 10 * 60)), "2 hours");
+        assert_eq!(format_time_to_full(Duration::from_secs(4 * 86400)), "4 days");
+    }
+
+    #[test]
+    fn test_running_stats_mean_and_stddev() {
+        let stats = RunningStats::from_samples([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(stats.count(), 8);
+        assert!((stats.mean() - 5.0).abs() < 1e-6);
+        assert!((stats.stddev() - 2.0).abs() < 1e-6);
+        assert_eq!(RunningStats::from_samples([42.0]).stddev(), 0.0);
+    }
+
+    #[test]
+    fn test_adaptive_threshold_flags_anomalies() {
+        let busy = RunningStats::from_samples((0..60).map(|i| if i % 2 == 0 { 60.0 } else { 70.0 }));
+        assert_eq!(adaptive_threshold(&busy, 3.0, 100), None);
+
+        // Busy machine: 65 ± 5, so 3σ puts the line at 80
+        let threshold = adaptive_threshold(&busy, 3.0, 30).unwrap();
+        assert!((threshold - 80.0).abs() < 1e-3);
+
+        // Idle machine: a flat 3% history still needs a real jump to count as anomalous
+        let idle = RunningStats::from_samples(std::iter::repeat_n(3.0, 60));
+        let threshold = adaptive_threshold(&idle, 3.0, 30).unwrap();
+        assert!((threshold - 9.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_critical_conditions_arm_takeover() {
+        let start = Instant::now();
+        let settings = EmergencySettings { enabled: true, ..EmergencySettings::default() };
+        let mut detector = EmergencyDetector::new();
+
+        // A busy but not pinned CPU and a roomy disk are not emergencies
+        assert!(detector.update(80.0, 50.0, &settings, start).is_empty());
+        assert!(detector.update(99.0, 50.0, &settings, start + Duration::from_secs(10)).is_empty());
+        let pinned = detector.update(99.0, 50.0, &settings, start + Duration::from_secs(190));
+        assert_eq!(pinned, vec!["CPU pinned at 99% for over 180 seconds".to_string()]);
+        assert!(detector.update(99.0, 50.0, &settings, start + Duration::from_secs(200)).is_empty(), "raised once per episode");
+
+        let disk = detector.update(10.0, 98.5, &settings, start + Duration::from_secs(210));
+        assert_eq!(disk, vec!["Disk 98.5% full".to_string()]);
+        detector.update(10.0, 90.0, &settings, start + Duration::from_secs(220));
+        assert_eq!(detector.update(10.0, 99.0, &settings, start + Duration::from_secs(230)).len(), 1, "re-armed after clearing");
+
+        // Only alerts at or above the configured severity take over, and never plain info
+        assert!(settings.takes_over(Severity::Critical));
+        assert!(!settings.takes_over(Severity::Warning));
+        let eager = EmergencySettings { min_severity: Severity::Info, ..settings.clone() };
+        assert!(eager.takes_over(Severity::Warning) && !eager.takes_over(Severity::Info));
+        assert!(!EmergencySettings::default().takes_over(Severity::Critical), "off unless enabled");
+    }
+
+    #[test]
+    fn test_maintenance_window_suppresses_only_inside_it() {
+        let backup = MaintenanceWindow {
+            label: "Nightly backup".to_string(),
+            start_minute: 23 * 60 + 30,
+            end_minute: 4 * 60,
+            metrics: vec![MetricKind::Disk],
+        };
+        let windows = vec![backup];
+        let at = |hour: u32, minute: u32| hour * 60 + minute;
+
+        // Inside the window, on both sides of midnight
+        assert!(in_maintenance(&windows, MetricKind::Disk, at(23, 45)));
+        assert!(in_maintenance(&windows, MetricKind::Disk, at(3, 0)));
+        // Outside it the disk warning fires as usual
+        assert!(!in_maintenance(&windows, MetricKind::Disk, at(4, 0)));
+        assert!(!in_maintenance(&windows, MetricKind::Disk, at(12, 0)));
+        assert!(!in_maintenance(&windows, MetricKind::Disk, at(23, 29)));
+        // Other metrics are never held back by a disk-only window
+        assert!(!in_maintenance(&windows, MetricKind::Cpu, at(3, 0)));
+
+        // A window without metrics covers all of them
+        let all = MaintenanceWindow::default();
+        assert!(MetricKind::ALL.iter().all(|kind| all.covers(*kind, at(3, 15))));
+        assert!(!all.covers(MetricKind::Cpu, at(4, 15)));
+        assert_eq!(format_minute_of_day(at(3, 5)), "03:05");
+    }
+}